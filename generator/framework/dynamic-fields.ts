@@ -0,0 +1,211 @@
+/**
+ * Dynamic field access for sui-client-gen generated code.
+ *
+ * Looks up dynamic fields (`0x2::dynamic_field`) and dynamic object fields (`0x2::dynamic_object_field`)
+ * by typed key, and the collections built on top of them (`Table`, `Bag`, `ObjectTable`, `ObjectBag`,
+ * `LinkedTable`). Values are decoded with the reified classes from the generated `structs.ts` files.
+ *
+ * The helpers are generic over the reified key and value types passed to them; no code is generated
+ * per collection or per key and value type.
+ */
+
+import { bcs } from '@mysten/sui/bcs'
+import type { ClientWithCoreApi } from '@mysten/sui/client'
+import {
+  decodeFromFields,
+  extractType,
+  KeyStructClassReified,
+  Reified,
+  StructClass,
+  toBcs,
+  ToField,
+  ToTypeArgument,
+  TypeArgument,
+} from './reified'
+import { compressSuiType, getBcsForType, typeArgIsPure } from './util'
+
+/**
+ * Key of a dynamic field of type `K`. Keys of pure types (primitives, strings, IDs, and options and
+ * vectors of those) are given like pure function arguments, e.g. `1n` or `'name'`. Other keys are
+ * given as the input of their BCS type (an object keyed by the Move field names).
+ */
+export type DynamicFieldKey<K extends TypeArgument> = K extends
+  | { $typeName: '0x1::string::String' }
+  | { $typeName: '0x1::ascii::String' }
+  | { $typeName: '0x2::object::ID' } ? string
+  : K extends { $typeName: '0x1::option::Option'; __inner: infer U extends TypeArgument }
+    ? DynamicFieldKey<U> | null
+  : K extends StructClass ? Record<string, any>
+  : ToField<K>
+
+/** A collection (`Table`, `Bag`, ...) instance or the ID of the object holding its fields. */
+export type Collection = string | { id: string; $typeArgs?: string[] }
+
+/** Serialize a key with the BCS type pure arguments use, or with the key type's own BCS type. */
+function serializeKey<K extends Reified<TypeArgument, any>>(
+  keyType: K,
+  key: DynamicFieldKey<ToTypeArgument<K>>,
+): Uint8Array {
+  const type = compressSuiType(extractType(keyType))
+  const bcsType = typeArgIsPure(type) ? getBcsForType(type) : toBcs(keyType)
+  return bcsType.serialize(key).toBytes()
+}
+
+/** Fetch the BCS bytes of the value of the dynamic field named by a key of type `nameType`. */
+async function fetchFieldValue<K extends Reified<TypeArgument, any>>(
+  client: ClientWithCoreApi,
+  parentId: string,
+  nameType: string,
+  keyType: K,
+  key: DynamicFieldKey<ToTypeArgument<K>>,
+): Promise<Uint8Array> {
+  const { dynamicField } = await client.core.getDynamicField({
+    parentId,
+    name: { type: nameType, bcs: serializeKey(keyType, key) },
+  })
+  return dynamicField.value.bcs
+}
+
+function collectionId(collection: Collection): string {
+  return typeof collection === 'string' ? collection : collection.id
+}
+
+/** Check the given key and value types against the type arguments of a typed collection instance. */
+function assertCollectionTypes(collection: Collection, types: string[]) {
+  if (typeof collection === 'string' || !collection.$typeArgs) {
+    return
+  }
+  const expected = collection.$typeArgs.map(t => compressSuiType(t))
+  const actual = types.map(t => compressSuiType(t))
+  if (expected.join(', ') !== actual.join(', ')) {
+    throw new Error(
+      `Collection type mismatch: expected <${expected.join(', ')}>, got <${actual.join(', ')}>`,
+    )
+  }
+}
+
+/**
+ * Fetch a dynamic field by key and decode its value.
+ *
+ * @param client - Any client implementing the core API
+ * @param parentId - ID of the object the field is attached to
+ * @param keyType - Reified type of the key, e.g. `'u64'` or `String.reified()`
+ * @param key - The key, e.g. `1n` or `'name'`
+ * @param valueType - Reified type of the value
+ */
+export async function getDynamicField<
+  K extends Reified<TypeArgument, any>,
+  V extends Reified<TypeArgument, any>,
+>(
+  client: ClientWithCoreApi,
+  parentId: string,
+  keyType: K,
+  key: DynamicFieldKey<ToTypeArgument<K>>,
+  valueType: V,
+): Promise<ToField<ToTypeArgument<V>>> {
+  const value = await fetchFieldValue(client, parentId, extractType(keyType), keyType, key)
+  return decodeFromFields(valueType, toBcs(valueType).parse(value))
+}
+
+/**
+ * Fetch a dynamic object field by key and load the object it holds.
+ *
+ * @param client - Any client implementing the core API
+ * @param parentId - ID of the object the field is attached to
+ * @param keyType - Reified type of the key
+ * @param key - The key
+ * @param valueType - Reified type of the object, which must have the `key` ability
+ */
+export async function getDynamicObjectField<
+  K extends Reified<TypeArgument, any>,
+  V extends StructClass,
+>(
+  client: ClientWithCoreApi,
+  parentId: string,
+  keyType: K,
+  key: DynamicFieldKey<ToTypeArgument<K>>,
+  valueType: KeyStructClassReified<V, any>,
+): Promise<V> {
+  const nameType = `0x2::dynamic_object_field::Wrapper<${extractType(keyType)}>`
+  const value = await fetchFieldValue(client, parentId, nameType, keyType, key)
+  return valueType.fetch(client, bcs.Address.parse(value))
+}
+
+/** Get the value stored under `key` in a `0x2::table::Table<K, V>`. */
+export async function tableGet<K extends Reified<TypeArgument, any>, V extends Reified<TypeArgument, any>>(
+  client: ClientWithCoreApi,
+  table: Collection,
+  keyType: K,
+  key: DynamicFieldKey<ToTypeArgument<K>>,
+  valueType: V,
+): Promise<ToField<ToTypeArgument<V>>> {
+  assertCollectionTypes(table, [extractType(keyType), extractType(valueType)])
+  return getDynamicField(client, collectionId(table), keyType, key, valueType)
+}
+
+/** Get the value stored under `key` in a `0x2::bag::Bag`. Bags are heterogeneous, so both types are given per lookup. */
+export function bagGet<K extends Reified<TypeArgument, any>, V extends Reified<TypeArgument, any>>(
+  client: ClientWithCoreApi,
+  bag: Collection,
+  keyType: K,
+  key: DynamicFieldKey<ToTypeArgument<K>>,
+  valueType: V,
+): Promise<ToField<ToTypeArgument<V>>> {
+  return getDynamicField(client, collectionId(bag), keyType, key, valueType)
+}
+
+/** Load the object stored under `key` in a `0x2::object_table::ObjectTable<K, V>`. */
+export async function objectTableGet<K extends Reified<TypeArgument, any>, V extends StructClass>(
+  client: ClientWithCoreApi,
+  table: Collection,
+  keyType: K,
+  key: DynamicFieldKey<ToTypeArgument<K>>,
+  valueType: KeyStructClassReified<V, any>,
+): Promise<V> {
+  assertCollectionTypes(table, [extractType(keyType), extractType(valueType)])
+  return getDynamicObjectField(client, collectionId(table), keyType, key, valueType)
+}
+
+/** Load the object stored under `key` in a `0x2::object_bag::ObjectBag`. */
+export function objectBagGet<K extends Reified<TypeArgument, any>, V extends StructClass>(
+  client: ClientWithCoreApi,
+  bag: Collection,
+  keyType: K,
+  key: DynamicFieldKey<ToTypeArgument<K>>,
+  valueType: KeyStructClassReified<V, any>,
+): Promise<V> {
+  return getDynamicObjectField(client, collectionId(bag), keyType, key, valueType)
+}
+
+/**
+ * Get the value stored under `key` in a `0x2::linked_table::LinkedTable<K, V>`. Entries are stored
+ * as `0x2::linked_table::Node<K, V>`, of which only the value is returned. The node's layout is
+ * built from the key and value types, so `Node` doesn't need to be generated.
+ */
+export async function linkedTableGet<
+  K extends Reified<TypeArgument, any>,
+  V extends Reified<TypeArgument, any>,
+>(
+  client: ClientWithCoreApi,
+  table: Collection,
+  keyType: K,
+  key: DynamicFieldKey<ToTypeArgument<K>>,
+  valueType: V,
+): Promise<ToField<ToTypeArgument<V>>> {
+  assertCollectionTypes(table, [extractType(keyType), extractType(valueType)])
+
+  const keyBcs = toBcs(keyType)
+  const nodeBcs = bcs.struct('Node', {
+    prev: bcs.option(keyBcs),
+    next: bcs.option(keyBcs),
+    value: toBcs(valueType),
+  })
+  const value = await fetchFieldValue(
+    client,
+    collectionId(table),
+    extractType(keyType),
+    keyType,
+    key,
+  )
+  return decodeFromFields(valueType, nodeBcs.parse(value).value)
+}
@@ -0,0 +1,125 @@
+/**
+ * Abort code decoding for sui-client-gen generated code.
+ *
+ * Maps the abort code of a failed transaction back to the error constant of the
+ * Move module that raised it, including Move 2024 clever errors (`#[error]`).
+ */
+
+import { getEnv, getRegisteredEnvs } from './env'
+import { abortCodeLoaders } from './init-errors'
+import { compressSuiAddress } from './util'
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/**
+ * Abort codes raised by a single Move module. Generated into `<module>/constants.ts`.
+ */
+export interface ModuleAbortCodes {
+  /** Plain abort codes: code -> names of the `E*` constants with that value */
+  codes: Record<string, string[]>
+  /** Clever errors: constant pool index -> error constant name */
+  identifiers: Record<number, string>
+  /** Clever errors: constant pool index -> error message */
+  messages: Record<number, string>
+}
+
+/**
+ * A decoded abort code.
+ */
+export interface DecodedAbort {
+  /** Kebab-case name of the package that raised the abort */
+  packageName: string
+  /** Move module name */
+  module: string
+  /** The raw abort code */
+  code: bigint
+  /** Name of the error constant, if known */
+  name?: string
+  /** Error message (clever errors with a string value), if known */
+  message?: string
+  /** Source line of the abort (clever errors only) */
+  line?: number
+}
+
+// ============================================================================
+// Decoding
+// ============================================================================
+
+const CLEVER_ERROR_TAG = 1n << 63n
+const CLEVER_ERROR_NO_INDEX = 0xffff
+
+/**
+ * Find the package name of a package ID across all registered environments.
+ * Matches original IDs, published-at IDs and type origin (upgrade) IDs.
+ */
+function findPackageName(packageId: string): string | undefined {
+  const id = compressSuiAddress(packageId)
+  for (const envName of getRegisteredEnvs()) {
+    const env = getEnv(envName)
+    for (const packages of [env.packages, env.dependencies]) {
+      for (const [name, pkg] of Object.entries(packages)) {
+        const ids = [pkg.originalId, pkg.publishedAt, ...Object.values(pkg.typeOrigins)]
+        if (ids.some(other => compressSuiAddress(other) === id)) {
+          return name
+        }
+      }
+    }
+  }
+  return undefined
+}
+
+/**
+ * Decode an abort code raised by a generated module.
+ *
+ * The module's abort codes are imported on first use, so only the constants of modules
+ * whose aborts are decoded end up in a bundle.
+ *
+ * @param packageId - ID of the package the aborting module belongs to (any version)
+ * @param module - Move module name
+ * @param code - The abort code
+ * @returns The decoded abort, or `undefined` if the package or module is unknown.
+ *
+ * @example
+ *   // MoveAbort(MoveLocation { module: ModuleId { address: 0x..., name: "pool" }, ... }, 2)
+ *   const decoded = await decodeAbort('0x...', 'pool', 2)
+ *   console.log(decoded?.name) // 'EInsufficientLiquidity'
+ */
+export async function decodeAbort(
+  packageId: string,
+  module: string,
+  code: bigint | number | string
+): Promise<DecodedAbort | undefined> {
+  const packageName = findPackageName(packageId)
+  if (packageName === undefined) {
+    return undefined
+  }
+  const loadModuleCodes = abortCodeLoaders[packageName]?.[module]
+  if (loadModuleCodes === undefined) {
+    return undefined
+  }
+  const moduleCodes = await loadModuleCodes()
+
+  const value = BigInt(code)
+  const decoded: DecodedAbort = { packageName, module, code: value }
+
+  if ((value & CLEVER_ERROR_TAG) !== 0n) {
+    decoded.line = Number((value >> 32n) & 0xffffn)
+    const identifierIdx = Number((value >> 16n) & 0xffffn)
+    const constantIdx = Number(value & 0xffffn)
+    if (identifierIdx !== CLEVER_ERROR_NO_INDEX) {
+      decoded.name = moduleCodes.identifiers[identifierIdx]
+    }
+    if (constantIdx !== CLEVER_ERROR_NO_INDEX) {
+      decoded.message = moduleCodes.messages[constantIdx]
+    }
+  } else {
+    const names = moduleCodes.codes[value.toString()]
+    if (names !== undefined) {
+      decoded.name = names.join(' | ')
+    }
+  }
+
+  return decoded
+}
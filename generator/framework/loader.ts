@@ -1,5 +1,8 @@
+import type { EnvConfig } from './env'
+import { getTypeOrigin } from './env'
 import { registerClasses } from './init-loader'
 import {
+  Ability,
   phantom,
   PhantomReified,
   PhantomTypeArgument,
@@ -12,7 +15,7 @@ import {
   VectorClass,
   VectorClassReified,
 } from './reified'
-import { compressSuiType, parseTypeName } from './util'
+import { compressSuiAddress, compressSuiType, parseTypeName } from './util'
 
 export type PrimitiveValue = string | number | boolean | bigint
 
@@ -20,6 +23,7 @@ interface _StructClass {
   $typeName: string
   $numTypeParams: number
   $isPhantom: readonly boolean[]
+  $abilities: readonly Ability[]
   reified(
     ...Ts: Array<Reified<TypeArgument, any> | PhantomReified<PhantomTypeArgument>>
   ): StructClassReified<StructClass, any>
@@ -33,11 +37,16 @@ export class StructClassLoader {
     this.classes.push(...classes)
   }
 
-  reified<T extends Primitive>(type: T): T
-  reified(type: `vector<${string}>`): VectorClassReified<VectorClass, any>
-  reified(type: string): StructClassReified<StructClass, any>
+  /**
+   * Reified type of a fully instantiated Move type. If `env` is given, the type's
+   * addresses are those of `env` rather than of the active environment.
+   */
+  reified<T extends Primitive>(type: T, env?: EnvConfig): T
+  reified(type: `vector<${string}>`, env?: EnvConfig): VectorClassReified<VectorClass, any>
+  reified(type: string, env?: EnvConfig): StructClassReified<StructClass, any>
   reified(
     type: string,
+    env?: EnvConfig,
   ): StructClassReified<StructClass, any> | VectorClassReified<VectorClass, any> | string {
     const { typeName, typeArgs } = parseTypeName(compressSuiType(type))
     switch (typeName) {
@@ -54,11 +63,12 @@ export class StructClassLoader {
         if (typeArgs.length !== 1) {
           throw new Error(`Vector expects 1 type argument, but got ${typeArgs.length}`)
         }
-        return vector(this.reified(typeArgs[0]))
+        return vector(this.reified(typeArgs[0], env))
       }
     }
 
-    const cls = this.classes.find(c => c.$typeName === typeName)
+    const activeTypeName = env ? toActiveTypeName(typeName, env) : typeName
+    const cls = this.classes.find(c => c.$typeName === activeTypeName)
     if (!cls) {
       throw new Error(`Unknown type ${typeName}`)
     }
@@ -75,13 +85,79 @@ export class StructClassLoader {
       if (cls.$isPhantom[i]) {
         reifiedTypeArgs.push(phantom(typeArgs[i]))
       } else {
-        reifiedTypeArgs.push(this.reified(typeArgs[i]))
+        reifiedTypeArgs.push(this.reified(typeArgs[i], env))
       }
     }
 
     return cls.reified(...reifiedTypeArgs)
   }
+
+  /** Abilities of a fully instantiated type, e.g. `['copy', 'drop', 'store']` for `u64`. */
+  abilities(type: string): Ability[] {
+    const { typeName, typeArgs } = parseTypeName(compressSuiType(type))
+    switch (typeName) {
+      case 'bool':
+      case 'u8':
+      case 'u16':
+      case 'u32':
+      case 'u64':
+      case 'u128':
+      case 'u256':
+      case 'address':
+        return ['copy', 'drop', 'store']
+      case 'vector': {
+        if (typeArgs.length !== 1) {
+          throw new Error(`Vector expects 1 type argument, but got ${typeArgs.length}`)
+        }
+        return this.abilities(typeArgs[0]).filter(a => a !== 'key')
+      }
+    }
+
+    const cls = this.classes.find(c => c.$typeName === typeName)
+    if (!cls) {
+      throw new Error(`Unknown type ${typeName}`)
+    }
+
+    // A declared ability only holds if every non-phantom type argument has it
+    // (`store` in case of `key`)
+    let abilities = [...cls.$abilities]
+    for (let i = 0; i < typeArgs.length; i++) {
+      if (cls.$isPhantom[i]) {
+        continue
+      }
+      const argAbilities = this.abilities(typeArgs[i])
+      abilities = abilities.filter(a => argAbilities.includes(a === 'key' ? 'store' : a))
+    }
+    return abilities
+  }
+}
+
+/**
+ * Translate the type name of a datatype defined in `env` (`address::module::Name`) to its
+ * type name in the active environment, which the generated classes are matched against.
+ * Types of packages `env` doesn't know (e.g. system packages) keep their name.
+ */
+export function toActiveTypeName(typeName: string, env: EnvConfig): string {
+  const [address, ...path] = typeName.split('::')
+  const moduleTypePath = path.join('::')
+  const configs = [...Object.entries(env.packages), ...Object.entries(env.dependencies)]
+  for (const [pkgName, config] of configs) {
+    const origin = config.typeOrigins[moduleTypePath]
+    if (origin !== undefined && compressSuiAddress(origin) === address) {
+      return `${getTypeOrigin(pkgName, moduleTypePath)}::${moduleTypePath}`
+    }
+  }
+  return typeName
 }
 
 export const loader: StructClassLoader = new StructClassLoader()
 registerClasses(loader)
+
+/** Throws if `type` is missing any of the `required` abilities (e.g. for a `T: store` type parameter). */
+export function assertAbilities(type: string, required: readonly Ability[]): void {
+  const abilities = loader.abilities(type)
+  const missing = required.filter(a => !abilities.includes(a))
+  if (missing.length > 0) {
+    throw new Error(`type ${type} is missing required abilities: ${missing.join(', ')}`)
+  }
+}
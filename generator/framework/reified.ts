@@ -44,6 +44,7 @@ export interface EnumVariantClass {
 }
 
 export type Primitive = 'bool' | 'u8' | 'u16' | 'u32' | 'u64' | 'u128' | 'u256' | 'address'
+export type Ability = 'copy' | 'drop' | 'store' | 'key'
 export type TypeArgument = StructClass | Primitive | VectorClass | EnumVariantClass
 
 export interface StructClassReified<T extends StructClass, Fields> {
@@ -58,14 +59,21 @@ export interface StructClassReified<T extends StructClass, Fields> {
   fromBcs(data: Uint8Array): T
   fromJSONField: (field: any) => T
   fromJSON: (json: Record<string, any>) => T
+  new: (fields: Fields) => T
+  /** Checks a JSON field representation, only generated with `json-validators = true` */
+  validateJSONField?: (field: unknown, path: string) => void
+  kind: 'StructClassReified'
+}
+
+/** Reified struct with the `key` ability, whose instances are objects that can be loaded by ID. */
+export interface KeyStructClassReified<T extends StructClass, Fields>
+  extends StructClassReified<T, Fields> {
   fromCoreObject: (obj: SuiClientTypes.Object<{ content: true }>) => T
-  /** @deprecated `SuiParsedData` is a JSON-RPC-only type that is being phased out upstream. Use {@link StructClassReified.fromCoreObject} together with `client.core.getObject({ include: { content: true } })` for transport-agnostic parsing. */
+  /** @deprecated `SuiParsedData` is a JSON-RPC-only type that is being phased out upstream. Use {@link KeyStructClassReified.fromCoreObject} together with `client.core.getObject({ include: { content: true } })` for transport-agnostic parsing. */
   fromSuiParsedData: (content: SuiParsedData) => T
-  /** @deprecated `SuiObjectData` is a JSON-RPC-only type that is being phased out upstream. Use {@link StructClassReified.fromCoreObject} together with `client.core.getObject({ include: { content: true } })` for transport-agnostic parsing. */
+  /** @deprecated `SuiObjectData` is a JSON-RPC-only type that is being phased out upstream. Use {@link KeyStructClassReified.fromCoreObject} together with `client.core.getObject({ include: { content: true } })` for transport-agnostic parsing. */
   fromSuiObjectData: (data: SuiObjectData) => T
   fetch: (client: ClientWithCoreApi, id: string) => Promise<T>
-  new: (fields: Fields) => T
-  kind: 'StructClassReified'
 }
 
 export interface VectorClassReified<T extends VectorClass, Elements> {
@@ -97,6 +105,8 @@ export interface EnumClassReified<T extends EnumVariantClass, Fields> {
   fromJSONField: (field: any) => T
   fromJSON: (json: Record<string, any>) => T
   new: (variant: string, fields: Fields) => T
+  /** Checks a JSON field representation, only generated with `json-validators = true` */
+  validateJSONField?: (field: unknown, path: string) => void
   kind: 'EnumClassReified'
 }
 
@@ -270,6 +280,14 @@ export function extractType(reified: Reified<TypeArgument, any> | PhantomReified
   throw new Error('unreachable')
 }
 
+/** A type argument of a generated function wrapper: a type string or a reified type. */
+export type TypeArg = string | Reified<TypeArgument, any> | PhantomReified<PhantomTypeArgument>
+
+/** Resolve a function wrapper type argument to a type string (see `extractType`). */
+export function resolveTypeArg(typeArg: TypeArg): string {
+  return typeof typeArg === 'string' ? typeArg : extractType(typeArg)
+}
+
 export function decodeFromFields(reified: Reified<TypeArgument, any>, field: any): any {
   switch (reified) {
     case 'bool':
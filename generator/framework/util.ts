@@ -4,6 +4,7 @@ import {
   TransactionArgument,
   TransactionObjectArgument,
   TransactionObjectInput,
+  TransactionResult,
 } from '@mysten/sui/transactions'
 
 export interface FieldsWithTypes {
@@ -28,6 +29,41 @@ export type GenericArg =
   | Array<PureArg>
   | Array<GenericArg>
 
+/**
+ * A transaction argument tagged with the Move type of the value it refers to. The tag
+ * only exists at the type level and is used to type-check passing the result of one
+ * generated call into another.
+ */
+export type TypedArgument<T = string> = TransactionArgument & { readonly $moveType?: T }
+
+/**
+ * The result of a generated Move call returning values of Move types `T`. Destructure it
+ * to get the individual results (`const [coin, receipt] = borrow(tx, ...)`). A result of
+ * a call returning a single value can also be passed on directly.
+ */
+export type TypedResult<T extends unknown[]> = TransactionResult & {
+  [K in keyof T]: TypedArgument<T[K]>
+} & (T extends [infer U] ? { readonly $moveType?: U } : unknown)
+
+/**
+ * An object input for a parameter of Move type `T`. Accepts anything `TransactionObjectInput`
+ * does, except typed results of generated calls holding a different Move type.
+ */
+export type ObjectArg<T = string> =
+  | Exclude<TransactionObjectInput, TransactionArgument>
+  | (Extract<TransactionObjectInput, TransactionArgument> & { readonly $moveType?: T })
+
+/**
+ * A Move event as returned by the client (e.g. the events of a transaction). Only the
+ * fields needed for decoding are required.
+ */
+export interface MoveEvent {
+  /** Full Move type of the event, e.g. `0x123::pool::PoolCreationEvent` */
+  eventType: string
+  /** BCS bytes of the event */
+  bcs: Uint8Array
+}
+
 export function splitGenericParameters(
   str: string,
   genericSeparators: [string, string] = ['<', '>'],
@@ -99,6 +135,40 @@ export function obj(tx: Transaction, arg: TransactionObjectInput): TransactionAr
   return isTransactionArgument(arg) ? arg : tx.object(arg)
 }
 
+/** BCS type of a pure type (see `typeArgIsPure`), which takes the same values as `pure`. */
+export function getBcsForType(type: string): BcsType<any> {
+  const { typeName, typeArgs } = parseTypeName(type)
+  switch (typeName) {
+    case 'bool':
+      return bcs.Bool
+    case 'u8':
+      return bcs.U8
+    case 'u16':
+      return bcs.U16
+    case 'u32':
+      return bcs.U32
+    case 'u64':
+      return bcs.U64
+    case 'u128':
+      return bcs.U128
+    case 'u256':
+      return bcs.U256
+    case 'address':
+      return bcs.Address
+    case '0x1::string::String':
+    case '0x1::ascii::String':
+      return bcs.String
+    case '0x2::object::ID':
+      return bcs.Address
+    case '0x1::option::Option':
+      return bcs.option(getBcsForType(typeArgs[0]))
+    case 'vector':
+      return bcs.vector(getBcsForType(typeArgs[0]))
+    default:
+      throw new Error(`invalid primitive type ${type}`)
+  }
+}
+
 export function pure(tx: Transaction, arg: PureArg, type: string): TransactionArgument {
   if (isTransactionArgument(arg)) {
     if (typeof arg === 'function') {
@@ -107,39 +177,6 @@ export function pure(tx: Transaction, arg: PureArg, type: string): TransactionAr
     return obj(tx, arg)
   }
 
-  function getBcsForType(type: string): BcsType<any> {
-    const { typeName, typeArgs } = parseTypeName(type)
-    switch (typeName) {
-      case 'bool':
-        return bcs.Bool
-      case 'u8':
-        return bcs.U8
-      case 'u16':
-        return bcs.U16
-      case 'u32':
-        return bcs.U32
-      case 'u64':
-        return bcs.U64
-      case 'u128':
-        return bcs.U128
-      case 'u256':
-        return bcs.U256
-      case 'address':
-        return bcs.Address
-      case '0x1::string::String':
-      case '0x1::ascii::String':
-        return bcs.String
-      case '0x2::object::ID':
-        return bcs.Address
-      case '0x1::option::Option':
-        return bcs.option(getBcsForType(typeArgs[0]))
-      case 'vector':
-        return bcs.vector(getBcsForType(typeArgs[0]))
-      default:
-        throw new Error(`invalid primitive type ${type}`)
-    }
-  }
-
   function hasUndefinedOrNull(items: PureArg[]) {
     for (const item of items) {
       if (typeof item === 'undefined' || item === null) {
@@ -0,0 +1,119 @@
+/**
+ * JSON validation for sui-client-gen generated code.
+ *
+ * Checks JSON representations (as produced by `toJSON` / `toJSONField`) before they're decoded.
+ * Backs the `validate<Name>JSONField` functions generated with `json-validators = true`, which
+ * `fromJSON` runs so that malformed input fails with the path of the offending value instead of
+ * a confusing error deep inside `decodeFromJSONField`.
+ */
+
+import type { Reified, TypeArgument } from './reified'
+
+/** Thrown when a JSON value doesn't match the expected representation. */
+export class JSONValidationError extends Error {
+  constructor(
+    /** Path of the offending value, e.g. `$.pool.balance` */
+    readonly path: string,
+    /** Description of the expected representation */
+    readonly expected: string,
+    readonly value: unknown,
+  ) {
+    super(`invalid JSON at ${path}: expected ${expected}, got ${describe(value)}`)
+    this.name = 'JSONValidationError'
+  }
+}
+
+function describe(value: unknown): string {
+  if (value === null) {
+    return 'null'
+  }
+  if (Array.isArray(value)) {
+    return 'array'
+  }
+  if (typeof value === 'string') {
+    return `string '${value.length > 32 ? `${value.slice(0, 32)}...` : value}'`
+  }
+  return typeof value
+}
+
+/** Checks that `field` is a JSON object and returns it. */
+export function validateObject(field: unknown, path: string): Record<string, unknown> {
+  if (typeof field !== 'object' || field === null || Array.isArray(field)) {
+    throw new JSONValidationError(path, 'object', field)
+  }
+  return field as Record<string, unknown>
+}
+
+function validateString(field: unknown, path: string, pattern?: RegExp, expected = 'string') {
+  if (typeof field !== 'string' || (pattern && !pattern.test(field))) {
+    throw new JSONValidationError(path, expected, field)
+  }
+}
+
+function validateInteger(field: unknown, path: string, bits: number) {
+  if (typeof field !== 'number' || !Number.isInteger(field) || field < 0 || field >= 2 ** bits) {
+    throw new JSONValidationError(path, `u${bits} number`, field)
+  }
+}
+
+const ADDRESS_PATTERN = /^0x[0-9a-fA-F]{1,64}$/
+const INTEGER_PATTERN = /^\d+$/
+
+/**
+ * Checks that `field` is a valid JSON representation of the given type (see `fieldToJSON`).
+ * Structs and enums are checked with their generated validators.
+ */
+export function validateJSONField(
+  reified: Reified<TypeArgument, any>,
+  field: unknown,
+  path: string,
+): void {
+  switch (reified) {
+    case 'bool':
+      if (typeof field !== 'boolean') {
+        throw new JSONValidationError(path, 'boolean', field)
+      }
+      return
+    case 'u8':
+      return validateInteger(field, path, 8)
+    case 'u16':
+      return validateInteger(field, path, 16)
+    case 'u32':
+      return validateInteger(field, path, 32)
+    case 'u64':
+    case 'u128':
+    case 'u256':
+      return validateString(field, path, INTEGER_PATTERN, `${reified} integer string`)
+    case 'address':
+      return validateString(field, path, ADDRESS_PATTERN, 'address')
+  }
+  if (reified.kind === 'VectorClassReified') {
+    if (!Array.isArray(field)) {
+      throw new JSONValidationError(path, 'array', field)
+    }
+    field.forEach((item, i) => validateJSONField(reified.reifiedTypeArgs[0], item, `${path}[${i}]`))
+    return
+  }
+  switch (reified.typeName) {
+    case '0x1::string::String':
+    case '0x1::ascii::String':
+    case '0x2::url::Url':
+    case '0x1::type_name::TypeName':
+      return validateString(field, path)
+    case '0x2::object::ID':
+    case '0x2::object::UID':
+      return validateString(field, path, ADDRESS_PATTERN, 'object ID')
+    case '0x1::option::Option': {
+      if (field === null) {
+        return
+      }
+      return validateJSONField(reified.reifiedTypeArgs[0] as any, field, path)
+    }
+    default:
+      if (reified.validateJSONField) {
+        reified.validateJSONField(field, path)
+      } else {
+        validateObject(field, path)
+      }
+  }
+}
@@ -0,0 +1,63 @@
+/**
+ * View calls for sui-client-gen generated code.
+ *
+ * Runs a Move call in a simulated transaction (dev-inspect) and decodes its return
+ * values using the reified BCS types. Backs the `<function>View` variants in `functions.ts`.
+ */
+
+import type { ClientWithCoreApi } from '@mysten/sui/client'
+import { Transaction } from '@mysten/sui/transactions'
+import type { EnvConfig } from './env'
+import { loader } from './loader'
+import { decodeFromFields, toBcs } from './reified'
+
+/** Sender used when none is given. Nothing is executed, so any address will do. */
+const DEFAULT_SENDER = '0x0000000000000000000000000000000000000000000000000000000000000000'
+
+export interface ViewOptions {
+  /** Environment the return types are resolved in (defaults to the active environment) */
+  env?: EnvConfig
+  /** Sender of the simulated transaction (matters for functions checking `ctx.sender()`) */
+  sender?: string
+}
+
+/**
+ * Simulate a transaction containing the Move call built by `build` and decode the call's
+ * return values.
+ *
+ * @param client - Any client implementing the core API
+ * @param build - Adds the Move call to the transaction (must be the last command)
+ * @param returnTypes - Full Move types of the return values in `options.env`, e.g.
+ *   `0x2::coin::Coin<0x2::sui::SUI>`
+ * @returns The decoded return values, in order
+ */
+export async function viewCall(
+  client: ClientWithCoreApi,
+  build: (tx: Transaction) => unknown,
+  returnTypes: string[],
+  options?: ViewOptions,
+): Promise<unknown[]> {
+  const tx = new Transaction()
+  tx.setSender(options?.sender ?? DEFAULT_SENDER)
+  build(tx)
+
+  const result = await client.core.simulateTransaction({
+    transaction: tx,
+    include: { commandResults: true },
+  })
+  if (result.$kind === 'FailedTransaction') {
+    throw new Error(`View call failed: ${JSON.stringify(result.FailedTransaction.status.error)}`)
+  }
+
+  const returnValues = result.commandResults?.at(-1)?.returnValues ?? []
+  if (returnValues.length !== returnTypes.length) {
+    throw new Error(
+      `View call returned ${returnValues.length} values, expected ${returnTypes.length}`,
+    )
+  }
+
+  return returnTypes.map((type, i) => {
+    const reified = loader.reified(type, options?.env)
+    return decodeFromFields(reified, toBcs(reified).parse(returnValues[i].bcs))
+  })
+}
@@ -6,20 +6,29 @@
 use std::collections::BTreeMap;
 use std::io::Write;
 use std::path::PathBuf;
+use std::time::Duration;
 
-use anyhow::Result;
+use anyhow::{anyhow, bail, Context, Result};
 use colored::*;
 use move_core_types::account_address::AccountAddress;
-use move_model_2::source_model;
+use move_model_2::{model, source_model};
 use move_symbol_pool::Symbol;
 
-use crate::graphql::GraphQLCache;
-use crate::io::{clean_output, write_str_to_file, write_ts_file};
-use crate::layout::OutputLayout;
-use crate::manifest::{is_default_environment, parse_gen_manifest_from_file};
+use crate::filter::{ModuleSelection, Selection};
+use crate::graphql::{ClientOptions, DiskCache, GraphQLCache};
+use crate::io::{
+    clean_output, content_hash, prune_stale_files, relative_path, FileDrift, GeneratedManifest,
+    RenderedFiles, GENERATED_MANIFEST_NAME,
+};
+use crate::layout::{resolve_package_key, OutputLayout, PackageLayout};
+use crate::lockfile::{lockfile_path, GenLock};
+use crate::manifest::{is_default_environment, parse_gen_manifest_from_file, Naming};
 use crate::model_builder::{TypeOriginTable, VersionTable};
 use crate::multi_env::{build_multi_env_models, MultiEnvResult};
-use crate::ts_gen::{self, gen_envs_index, gen_module_structs, EnvConfigIR, EnvPackageConfigIR, TsFormatter};
+use crate::ts_gen::{
+    self, datatype_ts_name, function_ts_name, gen_envs_index, gen_module_structs, EnvConfigIR,
+    EnvPackageConfigIR, IdentifierRenames, TsFormatter,
+};
 use crate::framework_sources;
 
 /// Options for running the new generator.
@@ -34,6 +43,18 @@ pub struct RunOptions {
     pub graphql: Option<String>,
     /// Whether to clean the output directory first
     pub clean: bool,
+    /// Render everything in memory and compare against the output directory
+    /// instead of writing. Fails if any generated file is missing or differs.
+    pub check: bool,
+    /// Reuse the previous output of modules whose compiled bytecode (and generation
+    /// context) is unchanged since the last run instead of regenerating them.
+    pub incremental: bool,
+    /// Directory of the persistent GraphQL cache (defaults to the user cache directory)
+    pub cache_dir: Option<PathBuf>,
+    /// Serve chain ids and type origins exclusively from the GraphQL cache
+    pub offline: bool,
+    /// Ignore the existing gen.lock and re-resolve all on-chain data
+    pub update_lock: bool,
 }
 
 /// Run the code generator with the new package system.
@@ -72,6 +93,8 @@ pub async fn run(opts: RunOptions) -> Result<()> {
     }
     if let Some(ref graphql_override) = opts.graphql {
         manifest.config.graphql = Some(graphql_override.clone());
+        // Headers in gen.toml were configured for other endpoints, don't leak them to this one
+        manifest.config.graphql_headers.clear();
     }
 
     // Build models for all environments with compatibility checking
@@ -80,8 +103,49 @@ pub async fn run(opts: RunOptions) -> Result<()> {
         "{}",
         "BUILDING MODELS FOR ALL ENVIRONMENTS".green().bold()
     )?;
-    let mut graphql_cache = GraphQLCache::new();
-    let multi_env_result = build_multi_env_models(&manifest, &opts.manifest_path, &mut graphql_cache).await?;
+    let mut client_options = ClientOptions::default();
+    if let Some(batch_size) = manifest.config.graphql_batch_size {
+        client_options.batch_size = batch_size;
+    }
+    if let Some(timeout_secs) = manifest.config.graphql_timeout_secs {
+        client_options.timeout = Duration::from_secs(timeout_secs);
+    }
+    if let Some(max_retries) = manifest.config.graphql_max_retries {
+        client_options.max_retries = max_retries;
+    }
+    let mut graphql_cache = GraphQLCache::new()
+        .with_offline(opts.offline)
+        .with_client_options(client_options);
+    match opts.cache_dir.clone().or_else(DiskCache::default_dir) {
+        Some(cache_dir) => graphql_cache = graphql_cache.with_disk_cache(cache_dir),
+        None if opts.offline => {
+            return Err(anyhow::anyhow!(
+                "--offline requires a GraphQL cache directory. Pass one with --cache-dir."
+            ));
+        }
+        None => (),
+    }
+    let lock_path = lockfile_path(&opts.manifest_path);
+    let existing_lock = if opts.update_lock {
+        None
+    } else {
+        GenLock::load(&lock_path)?
+    };
+    let multi_env_result = build_multi_env_models(
+        &manifest,
+        &opts.manifest_path,
+        &mut graphql_cache,
+        existing_lock.as_ref(),
+    )
+    .await?;
+
+    // Record the resolved on-chain data (check mode never writes anything)
+    if !opts.check {
+        let lock = GenLock::new(multi_env_result.env_locks.clone());
+        if lock.write_if_changed(&lock_path)? {
+            writeln!(progress_output, "  Updated {}", lock_path.display())?;
+        }
+    }
 
     writeln!(
         progress_output,
@@ -96,8 +160,8 @@ pub async fn run(opts: RunOptions) -> Result<()> {
         multi_env_result.default_model.model.modules().count()
     )?;
 
-    // Clean output if requested
-    if opts.clean {
+    // Clean output if requested (never in check mode, which must not touch the output)
+    if opts.clean && !opts.check {
         clean_output(&out_dir)?;
     }
 
@@ -111,14 +175,44 @@ pub async fn run(opts: RunOptions) -> Result<()> {
 
     // Setup output layout
     let output = OutputLayout::new(out_dir);
-    std::fs::create_dir_all(&output.root)?;
+
+    // Modules and items to generate, after applying [filters]
+    let selection = Selection::new(
+        &pkgs,
+        &multi_env_result.default_model.id_map,
+        &multi_env_result.top_level_addr_map,
+        &manifest.filters,
+        &manifest.dep_functions,
+        manifest.config.all_functions,
+    )?;
+
+    // TypeScript names overridden in [naming.identifiers]
+    let renames = resolve_identifier_renames(
+        &manifest.naming,
+        &pkgs,
+        &multi_env_result.default_model.id_map,
+        &multi_env_result.top_level_addr_map,
+    )?;
+
+    // Files emitted by the previous run (if any), used for pruning and incremental builds
+    let previous_manifest = GeneratedManifest::load(&output.root)?;
 
     // Create TypeScript formatter
     let formatter = TsFormatter::new();
 
+    // All files are rendered in memory first and written (or checked) at the end
+    let mut files = RenderedFiles::new();
+
     // Generate _framework
     writeln!(progress_output, "{}", "GENERATING FRAMEWORK".green().bold())?;
-    generate_framework(&output, &pkgs, &multi_env_result, &formatter)?;
+    generate_framework(
+        &output,
+        &pkgs,
+        &selection,
+        &multi_env_result,
+        &formatter,
+        &mut files,
+    )?;
 
     // Generate packages
     writeln!(progress_output, "{}", "GENERATING PACKAGES".green().bold())?;
@@ -131,53 +225,162 @@ pub async fn run(opts: RunOptions) -> Result<()> {
         &multi_env_result.default_model.version_table,
         &output,
         &formatter,
+        manifest.config.all_functions,
+        manifest.config.json_validators,
+        &selection,
+        &renames,
+        // Check mode always renders from scratch so it can't be fooled by reused output
+        previous_manifest
+            .as_ref()
+            .filter(|_| opts.incremental && !opts.check),
+        &mut files,
     )?;
 
     // Generate ignore files to skip linting/formatting of generated code
-    write_str_to_file("*\n", &output.root.join(".prettierignore"))?;
-    write_str_to_file("*\n", &output.root.join(".eslintignore"))?;
+    files.add_str("*\n", &output.root.join(".prettierignore"));
+    files.add_str("*\n", &output.root.join(".eslintignore"));
+
+    // Files emitted by the previous run that are no longer generated (e.g. removed modules)
+    let stale = match &previous_manifest {
+        Some(previous) => files.stale_files(&output.root, previous),
+        None => vec![],
+    };
+
+    // Record emitted files so the next run can prune the ones it no longer generates
+    let generated_manifest = files.generated_manifest(&output.root);
+    files.add_str(
+        &generated_manifest.to_json()?,
+        &output.root.join(GENERATED_MANIFEST_NAME),
+    );
+
+    if opts.check {
+        writeln!(progress_output, "{}", "CHECKING OUTPUT".green().bold())?;
+        let drifts = files.check_drift(&stale)?;
+        if !drifts.is_empty() {
+            report_drift(&mut std::io::stdout(), &drifts)?;
+            return Err(anyhow::anyhow!(
+                "Generated output in {} is out of date ({} files differ). \
+                 Re-run sui-client-gen without --check to update it.",
+                output.root.display(),
+                drifts.len()
+            ));
+        }
+        writeln!(progress_output, "  {} files up to date", files.len())?;
+    } else {
+        writeln!(progress_output, "{}", "WRITING FILES".green().bold())?;
+        let written = files.write_all()?;
+        writeln!(
+            progress_output,
+            "  Wrote {} files ({} unchanged)",
+            written,
+            files.len() - written
+        )?;
+        if !stale.is_empty() {
+            prune_stale_files(&output.root, &stale)?;
+            writeln!(progress_output, "  Removed {} stale files", stale.len())?;
+        }
+    }
 
     writeln!(progress_output, "{}", "DONE".green().bold())?;
     Ok(())
 }
 
+/// Print a per-file summary of drifted files followed by their unified diffs.
+fn report_drift(out: &mut impl Write, drifts: &[FileDrift]) -> Result<()> {
+    writeln!(out, "{}", "Out of date files:".yellow().bold())?;
+    for drift in drifts {
+        match drift {
+            FileDrift::Missing { path } => {
+                writeln!(out, "  {} {}", "missing".red(), path.display())?;
+            }
+            FileDrift::Changed {
+                path,
+                insertions,
+                deletions,
+                ..
+            } => {
+                writeln!(
+                    out,
+                    "  {} {} ({}, {})",
+                    "changed".yellow(),
+                    path.display(),
+                    format!("+{}", insertions).green(),
+                    format!("-{}", deletions).red()
+                )?;
+            }
+            FileDrift::Stale { path } => {
+                writeln!(out, "  {} {}", "stale".red(), path.display())?;
+            }
+        }
+    }
+
+    for drift in drifts {
+        if let FileDrift::Changed { diff, .. } = drift {
+            writeln!(out)?;
+            write!(out, "{}", diff)?;
+        }
+    }
+
+    Ok(())
+}
+
 /// Generate the _framework directory contents.
 fn generate_framework(
     output: &OutputLayout,
     pkgs: &BTreeMap<AccountAddress, source_model::Package>,
+    selection: &Selection,
     multi_env: &MultiEnvResult,
     formatter: &TsFormatter,
+    files: &mut RenderedFiles,
 ) -> Result<()> {
-    std::fs::create_dir_all(&output.framework_dir)?;
-
-    write_ts_file(
+    files.add_ts(
         formatter,
         framework_sources::LOADER,
         &output.framework_dir.join("loader.ts"),
     )?;
-    write_ts_file(
+    files.add_ts(
         formatter,
         framework_sources::UTIL,
         &output.framework_dir.join("util.ts"),
     )?;
-    write_ts_file(
+    files.add_ts(
         formatter,
         framework_sources::REIFIED,
         &output.framework_dir.join("reified.ts"),
     )?;
-    write_ts_file(
+    files.add_ts(
         formatter,
         framework_sources::VECTOR,
         &output.framework_dir.join("vector.ts"),
     )?;
-    write_ts_file(
+    files.add_ts(
         formatter,
         framework_sources::ENV,
         &output.framework_dir.join("env.ts"),
     )?;
+    files.add_ts(
+        formatter,
+        framework_sources::ERRORS,
+        &output.framework_dir.join("errors.ts"),
+    )?;
+    files.add_ts(
+        formatter,
+        framework_sources::VIEW,
+        &output.framework_dir.join("view.ts"),
+    )?;
+    files.add_ts(
+        formatter,
+        framework_sources::DYNAMIC_FIELDS,
+        &output.framework_dir.join("dynamic-fields.ts"),
+    )?;
+    files.add_ts(
+        formatter,
+        framework_sources::VALIDATE,
+        &output.framework_dir.join("validate.ts"),
+    )?;
 
     // Generate init-loader.ts
-    write_ts_file(
+    files.add_ts(
         formatter,
         &ts_gen::gen_init_loader(
             &pkgs.keys().copied().collect::<Vec<_>>(),
@@ -187,9 +390,32 @@ fn generate_framework(
         &output.framework_dir.join("init-loader.ts"),
     )?;
 
+    // Generate init-errors.ts (abort code tables of all modules with constants)
+    let modules_with_constants: BTreeMap<AccountAddress, Vec<Symbol>> = pkgs
+        .iter()
+        .map(|(pkg_id, pkg)| {
+            let modules = pkg
+                .modules()
+                .filter(|module| selection.is_module_selected(*pkg_id, module.name()))
+                .filter(|module| !ts_gen::build_module_constants_ir(module).is_empty())
+                .map(|module| module.name())
+                .collect::<Vec<_>>();
+            (*pkg_id, modules)
+        })
+        .filter(|(_, modules)| !modules.is_empty())
+        .collect();
+    files.add_ts(
+        formatter,
+        &ts_gen::gen_init_errors(
+            &modules_with_constants,
+            &multi_env.folder_names,
+            &multi_env.top_level_addr_map,
+        ),
+        &output.framework_dir.join("init-errors.ts"),
+    )?;
+
     // Generate _envs/ directory (at top level, sibling to _framework/)
     let envs_dir = output.root.join("_envs");
-    std::fs::create_dir_all(&envs_dir)?;
 
     // Generate _envs/<env>.ts for each environment
     for env_name in &multi_env.all_envs {
@@ -218,11 +444,11 @@ fn generate_framework(
         );
 
         // Generate _envs/<env_name>.ts
-        write_ts_file(formatter, &env_config.emit(), &envs_dir.join(format!("{}.ts", env_name)))?;
+        files.add_ts(formatter, &env_config.emit(), &envs_dir.join(format!("{}.ts", env_name)))?;
     }
 
     // Generate _envs/index.ts
-    write_ts_file(
+    files.add_ts(
         formatter,
         &gen_envs_index(&multi_env.all_envs, &multi_env.default_env),
         &envs_dir.join("index.ts"),
@@ -293,7 +519,132 @@ fn build_env_config(
     }
 }
 
+/// Datatypes whose generated classes are referenced by name in function bindings.
+const UNRENAMEABLE_DATATYPES: &[(AccountAddress, &str, &str)] = &[
+    (AccountAddress::ONE, "string", "String"),
+    (AccountAddress::ONE, "ascii", "String"),
+    (AccountAddress::ONE, "option", "Option"),
+    (AccountAddress::TWO, "object", "ID"),
+];
+
+/// Resolve `[naming.identifiers]` to the structs, enums and functions they rename.
+///
+/// Packages are referred to like in `[naming.folders]`, by package name or address (see
+/// `resolve_package_key`); names matching several packages are rejected.
+pub fn resolve_identifier_renames(
+    naming: &Naming,
+    pkgs: &BTreeMap<AccountAddress, source_model::Package>,
+    id_map: &BTreeMap<AccountAddress, move_package_alt::schema::PackageName>,
+    top_level_pkg_names: &BTreeMap<AccountAddress, Symbol>,
+) -> Result<IdentifierRenames> {
+    let find_pkg = |key: &str| -> Result<_> {
+        let pkg_id = resolve_package_key(key, id_map, top_level_pkg_names)
+            .context("Error in [naming.identifiers]")?;
+        Ok(pkg_id.and_then(|pkg_id| pkgs.get_key_value(&pkg_id)))
+    };
+
+    let mut renames = IdentifierRenames::new();
+    for (path, ts_name) in &naming.identifiers {
+        // Paths are validated as `package::module::name` when parsing the manifest
+        let parts: Vec<&str> = path.split("::").collect();
+        let [pkg_key, module_name, name] = parts[..] else {
+            bail!("Invalid identifier path '{}'", path);
+        };
+        let Some((pkg_id, pkg)) = find_pkg(pkg_key)? else {
+            bail!(
+                "[naming.identifiers] lists unknown package '{}' in '{}'",
+                pkg_key,
+                path
+            );
+        };
+        let Some(module) = pkg.modules().find(|m| m.name().as_str() == module_name) else {
+            bail!(
+                "[naming.identifiers] lists unknown module '{}' in '{}'",
+                module_name,
+                path
+            );
+        };
+
+        let is_datatype = module.structs().any(|s| s.name().as_str() == name)
+            || module.enums().any(|e| e.name().as_str() == name);
+        if !is_datatype && !module.functions().any(|f| f.name().as_str() == name) {
+            bail!(
+                "[naming.identifiers] lists '{}', which is not a struct, enum or function",
+                path
+            );
+        }
+        if is_datatype
+            && UNRENAMEABLE_DATATYPES
+                .iter()
+                .any(|(addr, m, n)| addr == pkg_id && *m == module_name && *n == name)
+        {
+            bail!(
+                "'{}' can't be renamed, its class name is used by the generated code",
+                path
+            );
+        }
+        renames.insert(
+            (*pkg_id, module.name(), Symbol::from(name)),
+            ts_name.clone(),
+        );
+    }
+
+    // Renamed items must not clash with the (possibly renamed) items next to them
+    for ((pkg_id, module_name, name), ts_name) in &renames {
+        let Some(module) = pkgs
+            .get(pkg_id)
+            .and_then(|pkg| pkg.modules().find(|m| m.name() == *module_name))
+        else {
+            bail!(
+                "[naming.identifiers] renames '{}::{}', whose module doesn't exist",
+                module_name,
+                name
+            );
+        };
+        let datatypes: Vec<(Symbol, String)> = module
+            .structs()
+            .map(|s| s.name())
+            .chain(module.enums().map(|e| e.name()))
+            .map(|n| {
+                let ts_name = datatype_ts_name(Some(&renames), *pkg_id, *module_name, n);
+                (n, ts_name)
+            })
+            .collect();
+        let siblings = if datatypes.iter().any(|(n, _)| n == name) {
+            datatypes
+        } else {
+            module
+                .functions()
+                .map(|f| {
+                    let ts_name = renames
+                        .get(&(*pkg_id, *module_name, f.name()))
+                        .cloned()
+                        .unwrap_or_else(|| function_ts_name(f.name().as_str()));
+                    (f.name(), ts_name)
+                })
+                .collect()
+        };
+        if let Some((sibling, _)) = siblings.iter().find(|(n, t)| n != name && t == ts_name) {
+            bail!(
+                "[naming.identifiers] renames '{}::{}' to '{}', which is already the name of '{}::{}'",
+                module_name,
+                name,
+                ts_name,
+                module_name,
+                sibling
+            );
+        }
+    }
+
+    Ok(renames)
+}
+
 /// Generate TypeScript code for all packages.
+///
+/// If `previous` is set (incremental mode), modules whose input hash matches the
+/// one recorded in the previous manifest are not rebuilt; their previously
+/// generated files are reused as-is.
+#[allow(clippy::too_many_arguments)]
 fn gen_packages(
     pkgs: BTreeMap<AccountAddress, source_model::Package>,
     folder_names: &BTreeMap<AccountAddress, String>,
@@ -303,40 +654,106 @@ fn gen_packages(
     version_table: &VersionTable,
     output: &OutputLayout,
     formatter: &TsFormatter,
+    all_functions: bool,
+    json_validators: bool,
+    selection: &Selection,
+    renames: &IdentifierRenames,
+    previous: Option<&GeneratedManifest>,
+    files: &mut RenderedFiles,
 ) -> Result<()> {
     if pkgs.is_empty() {
         return Ok(());
     }
 
+    // Everything besides a module's own bytecode that affects its generated output
+    let context_hash = content_hash(
+        format!(
+            "{}|{:?}|{:?}|{:?}|{:?}|{}|{}|{:?}",
+            env!("CARGO_PKG_VERSION"),
+            type_origin_table,
+            version_table,
+            folder_names,
+            top_level_pkg_names,
+            all_functions,
+            json_validators,
+            renames
+        )
+        .as_bytes(),
+    );
+
     for (pkg_id, pkg) in pkgs.iter() {
         let pkg_layout = output.package_path(pkg_id, folder_names, top_level_pkg_names);
-        std::fs::create_dir_all(&pkg_layout.path)?;
 
         // Note: index.ts is no longer generated per-package
         // Package addresses are now in _envs/<env>.ts
 
         // Generate init.ts
-        write_ts_file(
+        files.add_ts(
             formatter,
-            &ts_gen::gen_package_init(pkg, &pkg_layout.framework_rel_path_for_init()),
+            &ts_gen::gen_package_init(
+                pkg,
+                &pkg_layout.framework_rel_path_for_init(),
+                selection,
+                renames,
+            ),
             &pkg_layout.path.join("init.ts"),
         )?;
 
+        // Generate events.ts (only for packages that emit events)
+        let content = ts_gen::gen_package_events(
+            pkg,
+            &pkg_layout.framework_rel_path_for_init(),
+            selection,
+            renames,
+        );
+        if !content.is_empty() {
+            files.add_ts(formatter, &content, &pkg_layout.path.join("events.ts"))?;
+        }
+
         // Generate modules
         for module in pkg.modules() {
+            // Skip modules that are filtered out and not referenced by generated code
+            let Some(module_selection) = selection.module(*pkg_id, module.name()) else {
+                continue;
+            };
             let module_path = pkg_layout.module_path(module.name());
-            std::fs::create_dir_all(&module_path)?;
 
-            // Generate <module>/functions.ts (only for top-level packages)
-            if pkg_layout.is_top_level {
+            // Skip modules that haven't changed since the previous run
+            let module_rel_dir = relative_path(&output.root, &module_path).ok_or_else(|| {
+                anyhow!(
+                    "Module path {} is not under the output directory {}",
+                    module_path.display(),
+                    output.root.display()
+                )
+            })?;
+            let module_hash =
+                module_input_hash(&module, module_selection, &pkg_layout, &context_hash)?;
+            let unchanged = previous
+                .is_some_and(|prev| prev.modules.get(&module_rel_dir) == Some(&module_hash));
+            let reused = match previous {
+                Some(prev) if unchanged => {
+                    files.reuse_previous_module(&output.root, prev, &module_rel_dir)?
+                }
+                _ => false,
+            };
+            files.record_module_hash(module_rel_dir, module_hash);
+            if reused {
+                continue;
+            }
+
+            // Generate <module>/functions.ts (only for top-level packages and [dep-functions])
+            if !module_selection.functions.is_empty() {
                 let content = ts_gen::gen_module_functions(
                     &module,
                     folder_names,
                     top_level_pkg_names,
                     pkg_layout.levels_from_root,
+                    all_functions,
+                    module_selection,
+                    renames,
                 );
                 if !content.is_empty() {
-                    write_ts_file(formatter, &content, &module_path.join("functions.ts"))?;
+                    files.add_ts(formatter, &content, &module_path.join("functions.ts"))?;
                 }
             }
 
@@ -348,10 +765,46 @@ fn gen_packages(
                 folder_names,
                 top_level_pkg_names,
                 pkg_layout.levels_from_root,
+                json_validators,
+                module_selection,
+                renames,
             );
-            write_ts_file(formatter, &content, &module_path.join("structs.ts"))?;
+            files.add_ts(formatter, &content, &module_path.join("structs.ts"))?;
+
+            // Generate <module>/constants.ts (only for selected modules with constants)
+            if module_selection.selected {
+                let content = ts_gen::gen_module_constants(&module, pkg_layout.levels_from_root);
+                if !content.is_empty() {
+                    files.add_ts(formatter, &content, &module_path.join("constants.ts"))?;
+                }
+            }
         }
     }
 
     Ok(())
 }
+
+/// Hash of everything a module's generated output is derived from: its compiled bytecode,
+/// its source summary (doc comments, parameter and type parameter names), its selection
+/// and the generation context (generator version and manifest options).
+fn module_input_hash(
+    module: &source_model::Module,
+    selection: &ModuleSelection,
+    pkg_layout: &PackageLayout,
+    context_hash: &str,
+) -> Result<String> {
+    let compiled = &module.compiled().module;
+    let mut bytes = vec![];
+    compiled.serialize_with_version(compiled.version, &mut bytes)?;
+    if let model::Kind::WithSource(m) = module.kind() {
+        bytes.extend_from_slice(&serde_json::to_vec(m.summary())?);
+    }
+    bytes.extend_from_slice(
+        format!(
+            "|{}|{:?}|{}|{}",
+            context_hash, selection, pkg_layout.is_top_level, pkg_layout.levels_from_root
+        )
+        .as_bytes(),
+    );
+    Ok(content_hash(&bytes))
+}
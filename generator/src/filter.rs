@@ -0,0 +1,444 @@
+//! Module and item filters (`[filters.<package>]` sections of gen.toml).
+//!
+//! Filters narrow down which modules and items of a package are generated. Structs
+//! and enums referenced by anything that is generated (struct and enum fields,
+//! function signatures) are always kept, even if filtered out, so that imports
+//! between generated files never break.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::{bail, Context, Result};
+use move_binary_format::file_format::Visibility;
+use move_core_types::account_address::AccountAddress;
+use move_model_2::model::{self, Datatype};
+use move_model_2::normalized::Type;
+use move_model_2::source_kind::SourceKind;
+use move_package_alt::schema::PackageName;
+use move_symbol_pool::Symbol;
+
+use crate::layout::resolve_package_key;
+use crate::manifest::{DepFunctions, Filters, PackageFilter};
+
+/// A struct or enum, identified by package address, module name and name.
+type DatatypeId = (AccountAddress, Symbol, Symbol);
+
+/// What to generate for a single module.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModuleSelection {
+    /// Whether the module passes the module filters. Only selected modules get
+    /// functions.ts and constants.ts.
+    pub selected: bool,
+    /// Structs and enums to generate in structs.ts.
+    pub datatypes: BTreeSet<Symbol>,
+    /// Functions to generate in functions.ts.
+    pub functions: BTreeSet<Symbol>,
+}
+
+/// The modules and items to generate, for all packages.
+///
+/// Modules without an entry are not generated at all.
+#[derive(Debug, Default)]
+pub struct Selection {
+    modules: BTreeMap<(AccountAddress, Symbol), ModuleSelection>,
+}
+
+impl Selection {
+    /// Apply the manifest filters to the packages.
+    ///
+    /// Filters and `[dep-functions]` are keyed like `[naming]`, by package name or address
+    /// (see `resolve_package_key`). Packages without a filter are generated in full.
+    /// Functions are generated for top-level packages and for the dependency modules
+    /// listed in `[dep-functions]`.
+    pub fn new<HasSource: SourceKind>(
+        pkgs: &BTreeMap<AccountAddress, model::Package<HasSource>>,
+        id_map: &BTreeMap<AccountAddress, PackageName>,
+        top_level_pkg_names: &BTreeMap<AccountAddress, Symbol>,
+        filters: &Filters,
+        dep_functions: &DepFunctions,
+        all_functions: bool,
+    ) -> Result<Self> {
+        let find_pkg = |key: &str| -> Result<Option<AccountAddress>> {
+            let pkg_id = resolve_package_key(key, id_map, top_level_pkg_names)?;
+            Ok(pkg_id.filter(|pkg_id| pkgs.contains_key(pkg_id)))
+        };
+
+        let mut dep_modules = BTreeMap::new();
+        for (key, patterns) in dep_functions {
+            match find_pkg(key).context("Error in [dep-functions]")? {
+                Some(pkg_id) if top_level_pkg_names.contains_key(&pkg_id) => bail!(
+                    "[dep-functions] lists top-level package '{}', whose functions are always generated",
+                    key
+                ),
+                Some(pkg_id) => {
+                    dep_modules.insert(pkg_id, patterns.as_slice());
+                }
+                None => bail!(
+                    "[dep-functions] lists unknown dependency '{}'. Expected the package name \
+                     or address of a dependency",
+                    key
+                ),
+            }
+        }
+
+        let mut pkg_filters = BTreeMap::new();
+        for (key, filter) in filters {
+            match find_pkg(key).context("Error in [filters]")? {
+                Some(pkg_id) => {
+                    pkg_filters.insert(pkg_id, filter);
+                }
+                None => bail!(
+                    "Filter for unknown package '{}'. Expected the name of a package in [packages], \
+                     or the package name or address of a dependency",
+                    key
+                ),
+            }
+        }
+
+        let no_filter = PackageFilter::default();
+        let mut modules = BTreeMap::new();
+        // Datatypes referenced by the generated functions
+        let mut referenced = Vec::new();
+
+        for (pkg_id, pkg) in pkgs {
+            let filter = pkg_filters.get(pkg_id).copied().unwrap_or(&no_filter);
+            let is_top_level = top_level_pkg_names.contains_key(pkg_id);
+
+            for module in pkg.modules() {
+                let module_name = module.name();
+                if !filter.selects_module(module_name.as_str()) {
+                    continue;
+                }
+
+                let mut selection = ModuleSelection {
+                    selected: true,
+                    ..Default::default()
+                };
+                let datatype_names = module
+                    .structs()
+                    .map(|s| s.name())
+                    .chain(module.enums().map(|e| e.name()));
+                for name in datatype_names {
+                    if filter.selects_item(module_name.as_str(), name.as_str()) {
+                        selection.datatypes.insert(name);
+                    }
+                }
+
+                // functions.ts is only generated for top-level packages and [dep-functions]
+                if is_top_level
+                    || dep_functions_selects(&dep_modules, *pkg_id, module_name.as_str())
+                {
+                    for func in module.functions() {
+                        // Skip functions without compiled representation (e.g., macros)
+                        let Some(compiled) = func.maybe_compiled() else {
+                            continue;
+                        };
+                        let callable =
+                            compiled.is_entry || compiled.visibility == Visibility::Public;
+                        if !(all_functions || callable)
+                            || !filter.selects_item(module_name.as_str(), func.name().as_str())
+                        {
+                            continue;
+                        }
+                        selection.functions.insert(func.name());
+                        for ty in compiled.parameters.iter().chain(compiled.return_.iter()) {
+                            collect_datatypes(ty, &mut referenced);
+                        }
+                    }
+                }
+
+                modules.insert((*pkg_id, module_name), selection);
+            }
+        }
+
+        // Keep everything reachable from the generated datatypes and functions
+        let roots = modules.iter().flat_map(|((pkg_id, module), selection)| {
+            selection
+                .datatypes
+                .iter()
+                .map(move |name| (*pkg_id, *module, *name))
+        });
+        let kept = transitive_closure(roots.chain(referenced).collect(), |id| {
+            datatype_references(pkgs, id)
+        });
+        for (pkg_id, module, name) in kept {
+            modules
+                .entry((pkg_id, module))
+                .or_default()
+                .datatypes
+                .insert(name);
+        }
+
+        Ok(Selection { modules })
+    }
+
+    /// What to generate for a module, or `None` if nothing is.
+    pub fn module(&self, pkg_id: AccountAddress, module: Symbol) -> Option<&ModuleSelection> {
+        self.modules.get(&(pkg_id, module))
+    }
+
+    /// Whether the module passes the module filters.
+    pub fn is_module_selected(&self, pkg_id: AccountAddress, module: Symbol) -> bool {
+        self.module(pkg_id, module).is_some_and(|m| m.selected)
+    }
+
+    /// Whether the struct or enum is generated.
+    pub fn keeps_datatype(&self, pkg_id: AccountAddress, module: Symbol, name: Symbol) -> bool {
+        self.module(pkg_id, module)
+            .is_some_and(|m| m.datatypes.contains(&name))
+    }
+}
+
+impl PackageFilter {
+    /// Whether the module passes `include-modules` and `exclude-modules`.
+    fn selects_module(&self, module: &str) -> bool {
+        (self.include_modules.is_empty()
+            || self.include_modules.iter().any(|p| glob_match(p, module)))
+            && !self.exclude_modules.iter().any(|p| glob_match(p, module))
+    }
+
+    /// Whether the item passes the item patterns. The last matching pattern wins;
+    /// items matching no pattern are only included if all patterns are negated.
+    fn selects_item(&self, module: &str, item: &str) -> bool {
+        let mut selected = self.items.iter().all(|p| p.starts_with('!'));
+        for pattern in &self.items {
+            let (negated, pattern) = match pattern.strip_prefix('!') {
+                Some(pattern) => (true, pattern),
+                None => (false, pattern.as_str()),
+            };
+            let (module_pattern, item_pattern) = pattern.split_once("::").unwrap_or_default();
+            if glob_match(module_pattern, module) && glob_match(item_pattern, item) {
+                selected = !negated;
+            }
+        }
+        selected
+    }
+}
+
+/// Whether `[dep-functions]`, resolved to the module patterns of each dependency, selects
+/// the module of the dependency.
+fn dep_functions_selects(
+    dep_modules: &BTreeMap<AccountAddress, &[String]>,
+    pkg_id: AccountAddress,
+    module: &str,
+) -> bool {
+    dep_modules
+        .get(&pkg_id)
+        .is_some_and(|patterns| patterns.iter().any(|p| glob_match(p, module)))
+}
+
+/// Match `text` against a glob pattern, where `*` matches any sequence of characters
+/// and `?` matches a single character.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // Position of the last `*` in the pattern and the text position it was tried at
+    let mut backtrack = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(c) if *c == '?' || *c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                // Let the last `*` consume one more character
+                Some((star_p, star_t)) => {
+                    backtrack = Some((star_p, star_t + 1));
+                    p = star_p + 1;
+                    t = star_t + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// All datatypes reachable from `roots` by following `references`.
+fn transitive_closure(
+    roots: Vec<DatatypeId>,
+    references: impl Fn(&DatatypeId) -> Vec<DatatypeId>,
+) -> BTreeSet<DatatypeId> {
+    let mut kept = BTreeSet::new();
+    let mut pending = roots;
+    while let Some(id) = pending.pop() {
+        if kept.insert(id) {
+            pending.extend(references(&id));
+        }
+    }
+    kept
+}
+
+/// Datatypes used in the fields of a struct or the variants of an enum.
+fn datatype_references<HasSource: SourceKind>(
+    pkgs: &BTreeMap<AccountAddress, model::Package<HasSource>>,
+    (pkg_id, module, name): &DatatypeId,
+) -> Vec<DatatypeId> {
+    let Some(module) = pkgs
+        .get(pkg_id)
+        .and_then(|pkg| pkg.modules().find(|m| m.name() == *module))
+    else {
+        return vec![];
+    };
+
+    let mut refs = Vec::new();
+    match module.datatype(*name) {
+        Datatype::Struct(strct) => {
+            for (_, field) in strct.compiled().fields.0.iter() {
+                collect_datatypes(&field.type_, &mut refs);
+            }
+        }
+        Datatype::Enum(enum_) => {
+            for variant in enum_.variants() {
+                for (_, field) in variant.compiled().fields.0.iter() {
+                    collect_datatypes(&field.type_, &mut refs);
+                }
+            }
+        }
+    }
+    refs
+}
+
+/// Collect the datatypes used in a type, including in type arguments.
+fn collect_datatypes(ty: &Type, out: &mut Vec<DatatypeId>) {
+    match ty {
+        Type::Datatype(dt) => {
+            out.push((dt.module.address, dt.module.name, dt.name));
+            for arg in dt.type_arguments.iter() {
+                collect_datatypes(arg, out);
+            }
+        }
+        Type::Vector(inner) | Type::Reference(_, inner) => collect_datatypes(inner, out),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(include: &[&str], exclude: &[&str], items: &[&str]) -> PackageFilter {
+        let strings = |v: &[&str]| v.iter().map(|s| s.to_string()).collect();
+        PackageFilter {
+            include_modules: strings(include),
+            exclude_modules: strings(exclude),
+            items: strings(items),
+        }
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("pool", "pool"));
+        assert!(!glob_match("pool", "pools"));
+        assert!(glob_match("*", ""));
+        assert!(glob_match("*", "anything"));
+        assert!(glob_match("test_*", "test_utils"));
+        assert!(!glob_match("test_*", "my_test_utils"));
+        assert!(glob_match("*_test", "pool_test"));
+        assert!(glob_match("*pool*", "liquidity_pool_v2"));
+        assert!(glob_match("a*b*c", "aXbYbZc"));
+        assert!(!glob_match("a*b*c", "aXbYcZ"));
+        assert!(glob_match("coin?", "coin2"));
+        assert!(!glob_match("coin?", "coin"));
+    }
+
+    #[test]
+    fn test_selects_module() {
+        let no_filter = PackageFilter::default();
+        assert!(no_filter.selects_module("coin"));
+
+        let f = filter(&["coin", "balance*"], &["balance_test"], &[]);
+        assert!(f.selects_module("coin"));
+        assert!(f.selects_module("balance"));
+        assert!(!f.selects_module("balance_test"));
+        assert!(!f.selects_module("object"));
+
+        let f = filter(&[], &["test_*"], &[]);
+        assert!(f.selects_module("coin"));
+        assert!(!f.selects_module("test_scenario"));
+    }
+
+    #[test]
+    fn test_selects_item() {
+        // No patterns: everything
+        assert!(PackageFilter::default().selects_item("pool", "Pool"));
+
+        // Only negated patterns: everything but the matches
+        let f = filter(&[], &[], &["!*::test_*"]);
+        assert!(f.selects_item("pool", "Pool"));
+        assert!(!f.selects_item("pool", "test_helper"));
+
+        // Positive patterns: only the matches, the last matching pattern wins
+        let f = filter(&[], &[], &["pool::*", "!*::test_*", "pool::test_keep"]);
+        assert!(f.selects_item("pool", "Pool"));
+        assert!(!f.selects_item("pool", "test_helper"));
+        assert!(f.selects_item("pool", "test_keep"));
+        assert!(!f.selects_item("admin", "AdminCap"));
+    }
+
+    #[test]
+    fn test_dep_functions_selects() {
+        let sui = AccountAddress::TWO;
+        let std = AccountAddress::ONE;
+        let deepbook = AccountAddress::from_hex_literal("0xdee9").unwrap();
+        let other = AccountAddress::from_hex_literal("0x3").unwrap();
+        let sui_patterns = vec!["coin".to_string(), "pay*".to_string()];
+        let deepbook_patterns = vec!["*".to_string()];
+        let dep_modules: BTreeMap<AccountAddress, &[String]> = [
+            (sui, sui_patterns.as_slice()),
+            (deepbook, deepbook_patterns.as_slice()),
+            (std, &[][..]),
+        ]
+        .into_iter()
+        .collect();
+
+        assert!(dep_functions_selects(&dep_modules, sui, "coin"));
+        assert!(dep_functions_selects(&dep_modules, sui, "pay"));
+        assert!(!dep_functions_selects(&dep_modules, sui, "balance"));
+        assert!(dep_functions_selects(&dep_modules, deepbook, "pool"));
+        assert!(!dep_functions_selects(&dep_modules, std, "string"));
+        assert!(!dep_functions_selects(&dep_modules, other, "coin"));
+    }
+
+    #[test]
+    fn test_transitive_closure() {
+        let addr = AccountAddress::TWO;
+        let id = |module: &str, name: &str| (addr, Symbol::from(module), Symbol::from(name));
+        let graph: BTreeMap<DatatypeId, Vec<DatatypeId>> = [
+            (
+                id("coin", "Coin"),
+                vec![id("object", "UID"), id("balance", "Balance")],
+            ),
+            (id("object", "UID"), vec![id("object", "ID")]),
+            (id("balance", "Balance"), vec![]),
+            (id("object", "ID"), vec![]),
+            // Cycles (through vectors or options) must terminate
+            (id("tree", "Node"), vec![id("tree", "Node")]),
+            (id("bag", "Bag"), vec![id("object", "UID")]),
+        ]
+        .into_iter()
+        .collect();
+
+        let kept = transitive_closure(vec![id("coin", "Coin"), id("tree", "Node")], |id| {
+            graph.get(id).cloned().unwrap_or_default()
+        });
+        assert_eq!(
+            kept,
+            [
+                id("balance", "Balance"),
+                id("coin", "Coin"),
+                id("object", "ID"),
+                id("object", "UID"),
+                id("tree", "Node"),
+            ]
+            .into_iter()
+            .collect()
+        );
+    }
+}
@@ -18,3 +18,17 @@ pub static VECTOR: &str = include_str!("../framework/vector.ts");
 /// Environment management runtime - provides setActiveEnv, getPublishedAt, getTypeOrigin, etc.
 /// Also contains the EnvConfig and PackageConfig type definitions.
 pub static ENV: &str = include_str!("../framework/env.ts");
+
+/// Abort code decoding - maps abort codes of generated modules back to error constants.
+pub static ERRORS: &str = include_str!("../framework/errors.ts");
+
+/// View calls - simulates a Move call and decodes its return values, used by `<function>View` wrappers.
+pub static VIEW: &str = include_str!("../framework/view.ts");
+
+/// Dynamic fields - typed lookups of dynamic (object) fields and of `Table`, `Bag`, `ObjectTable`,
+/// `ObjectBag` and `LinkedTable` entries.
+pub static DYNAMIC_FIELDS: &str = include_str!("../framework/dynamic-fields.ts");
+
+/// JSON validation - checks JSON representations against their types, used by the
+/// `validate<Name>JSONField` functions generated with `json-validators = true`.
+pub static VALIDATE: &str = include_str!("../framework/validate.ts");
@@ -1,32 +1,50 @@
 //! Caching layer for GraphQL queries.
 //!
 //! Provides per-chain-id caching to avoid duplicate queries when building
-//! models for multiple environments that share the same chain.
+//! models for multiple environments that share the same chain. Queries still
+//! go to each environment's own endpoint.
+//!
+//! Optionally backed by a [`DiskCache`] so results survive across runs, and
+//! can run in offline mode where results are served exclusively from disk.
 
+use std::collections::btree_map::Entry;
 use std::collections::BTreeMap;
+use std::path::PathBuf;
 
 use anyhow::Result;
 use move_core_types::account_address::AccountAddress;
 
-use super::client::GraphQLClient;
-use super::types::{TypeOrigin, TypeOriginMap};
+use super::client::{ClientOptions, GraphQLClient, GraphQLHeaders};
+use super::disk_cache::DiskCache;
+use super::types::{PackageVersionMap, TypeOrigin, TypeOriginMap};
 
 /// Cache for GraphQL query results, keyed by chain ID.
 ///
 /// Multiple environments may share the same chain (e.g., both environments
 /// point to mainnet). This cache avoids re-querying the same packages when
-/// building models for multiple environments on the same chain.
+/// building models for multiple environments on the same chain. Clients are
+/// kept per endpoint though, so each environment's `graphql` URL and headers
+/// are used for the packages it queries.
 pub struct GraphQLCache {
     /// Map from chain_id -> per-chain cache
     chains: BTreeMap<String, ChainCache>,
+    /// Map from (graphql_url, headers) -> client for that endpoint
+    clients: BTreeMap<(String, GraphQLHeaders), GraphQLClient>,
+    /// Persistent cache shared across runs
+    disk: Option<DiskCache>,
+    /// Serve results exclusively from the disk cache, never hitting the network
+    offline: bool,
+    /// Request and retry options for newly created clients
+    client_options: ClientOptions,
 }
 
 /// Per-chain cache for GraphQL results.
+#[derive(Default)]
 struct ChainCache {
-    /// GraphQL client for this chain
-    client: GraphQLClient,
     /// Cached type origins: package_addr -> type origins
     type_origins: TypeOriginMap,
+    /// Cached package versions: package_addr -> version
+    package_versions: PackageVersionMap,
 }
 
 impl GraphQLCache {
@@ -34,56 +52,160 @@ impl GraphQLCache {
     pub fn new() -> Self {
         Self {
             chains: BTreeMap::new(),
+            clients: BTreeMap::new(),
+            disk: None,
+            offline: false,
+            client_options: ClientOptions::default(),
         }
     }
 
-    /// Get or create the chain cache for a given chain ID and GraphQL endpoint.
-    fn get_or_create_chain(&mut self, chain_id: &str, graphql_url: &str) -> &mut ChainCache {
-        self.chains.entry(chain_id.to_string()).or_insert_with(|| {
-            ChainCache {
-                client: GraphQLClient::new(graphql_url),
-                type_origins: BTreeMap::new(),
-            }
+    /// Set the request and retry options used by all GraphQL clients.
+    pub fn with_client_options(mut self, client_options: ClientOptions) -> Self {
+        self.client_options = client_options;
+        self
+    }
+
+    /// Back the cache with a persistent on-disk cache rooted at `dir`.
+    pub fn with_disk_cache(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.disk = Some(DiskCache::new(dir));
+        self
+    }
+
+    /// Serve chain identifiers and type origins exclusively from the disk cache.
+    ///
+    /// Any cache miss results in an error instead of a network request.
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Get or create the chain cache for a given chain ID, and the client for a
+    /// GraphQL endpoint of that chain.
+    fn get_or_create_chain(
+        &mut self,
+        chain_id: &str,
+        graphql_url: &str,
+        headers: &GraphQLHeaders,
+    ) -> Result<(&mut ChainCache, &GraphQLClient)> {
+        get_or_create_chain(
+            &mut self.chains,
+            &mut self.clients,
+            chain_id,
+            graphql_url,
+            headers,
+            &self.client_options,
+        )
+    }
+
+    /// Get the disk cache, failing if it's required (offline mode) but not configured.
+    fn offline_disk_cache(&self) -> Result<&DiskCache> {
+        self.disk.as_ref().ok_or_else(|| {
+            anyhow::anyhow!("Offline mode requires a cache directory, but none is configured")
         })
     }
 
     /// Query the chain identifier from a GraphQL endpoint.
     ///
-    /// This is not cached since it's typically only called once per environment
-    /// during validation.
-    pub async fn query_chain_identifier(&self, graphql_url: &str) -> Result<String> {
-        let client = GraphQLClient::new(graphql_url);
-        client.query_chain_identifier().await
+    /// Not cached in memory since it's typically only called once per environment
+    /// during validation. When a disk cache is configured the result is recorded
+    /// there; in offline mode it is served exclusively from the disk cache.
+    pub async fn query_chain_identifier(
+        &self,
+        graphql_url: &str,
+        headers: &GraphQLHeaders,
+    ) -> Result<String> {
+        if self.offline {
+            let disk = self.offline_disk_cache()?;
+            return disk.get_chain_id(graphql_url)?.ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Offline mode: chain identifier for {} is not in the cache at {}. \
+                     Run once without --offline to populate the cache.",
+                    graphql_url,
+                    disk.dir().display()
+                )
+            });
+        }
+
+        let client =
+            GraphQLClient::with_options(graphql_url, headers, self.client_options.clone())?;
+        let chain_id = client.query_chain_identifier().await?;
+
+        if let Some(disk) = &self.disk {
+            if let Err(e) = disk.put_chain_id(graphql_url, &chain_id) {
+                eprintln!("Warning: Failed to write GraphQL cache: {:#}", e);
+            }
+        }
+
+        Ok(chain_id)
     }
 
     /// Query type origins for multiple packages, using cache when available.
     ///
-    /// Only queries packages that are not already in the cache.
-    /// Returns the combined results (cached + newly queried).
+    /// Looks up packages in memory first, then in the disk cache (if configured),
+    /// and only queries the remaining ones. Returns the combined results
+    /// (cached + newly queried).
+    ///
+    /// In offline mode, packages missing from the disk cache result in an error.
     pub async fn query_type_origins(
         &mut self,
         chain_id: &str,
         graphql_url: &str,
+        headers: &GraphQLHeaders,
         package_addrs: Vec<AccountAddress>,
     ) -> Result<TypeOriginMap> {
-        let chain_cache = self.get_or_create_chain(chain_id, graphql_url);
+        let (chain_cache, client) = get_or_create_chain(
+            &mut self.chains,
+            &mut self.clients,
+            chain_id,
+            graphql_url,
+            headers,
+            &self.client_options,
+        )?;
 
-        // Find packages not in cache
-        let uncached_addrs: Vec<AccountAddress> = package_addrs
-            .iter()
-            .filter(|addr| !chain_cache.type_origins.contains_key(addr))
-            .copied()
-            .collect();
+        // Find packages not in memory, loading what we can from disk
+        let mut uncached_addrs: Vec<AccountAddress> = vec![];
+        for addr in package_addrs.iter() {
+            if chain_cache.type_origins.contains_key(addr) {
+                continue;
+            }
+            let from_disk = match &self.disk {
+                Some(disk) => disk.get_type_origins(chain_id, addr)?,
+                None => None,
+            };
+            match from_disk {
+                Some(origins) => {
+                    chain_cache.type_origins.insert(*addr, origins);
+                }
+                None => uncached_addrs.push(*addr),
+            }
+        }
 
         // Query uncached packages
         if !uncached_addrs.is_empty() {
-            let new_results = chain_cache
-                .client
+            if self.offline {
+                let disk = self.offline_disk_cache()?;
+                let missing: Vec<String> =
+                    uncached_addrs.iter().map(|a| a.to_hex_literal()).collect();
+                return Err(anyhow::anyhow!(
+                    "Offline mode: type origins for packages [{}] on chain '{}' are not in the \
+                     cache at {}. Run once without --offline to populate the cache.",
+                    missing.join(", "),
+                    chain_id,
+                    disk.dir().display()
+                ));
+            }
+
+            let new_results = client
                 .query_multiple_packages_type_origins(uncached_addrs)
                 .await?;
 
             // Add to cache
             for (addr, origins) in new_results {
+                if let Some(disk) = &self.disk {
+                    if let Err(e) = disk.put_type_origins(chain_id, &addr, &origins) {
+                        eprintln!("Warning: Failed to write GraphQL cache: {:#}", e);
+                    }
+                }
                 chain_cache.type_origins.insert(addr, origins);
             }
         }
@@ -99,12 +221,94 @@ impl GraphQLCache {
         Ok(result)
     }
 
-    /// Get a reference to the GraphQL client for a chain.
+    /// Query on-chain versions of multiple packages, using cache when available.
+    ///
+    /// Same lookup order as [`Self::query_type_origins`]. Every requested package
+    /// must exist on-chain.
+    pub async fn query_package_versions(
+        &mut self,
+        chain_id: &str,
+        graphql_url: &str,
+        headers: &GraphQLHeaders,
+        package_addrs: Vec<AccountAddress>,
+    ) -> Result<PackageVersionMap> {
+        let (chain_cache, client) = get_or_create_chain(
+            &mut self.chains,
+            &mut self.clients,
+            chain_id,
+            graphql_url,
+            headers,
+            &self.client_options,
+        )?;
+
+        // Find packages not in memory, loading what we can from disk
+        let mut uncached_addrs: Vec<AccountAddress> = vec![];
+        for addr in package_addrs.iter() {
+            if chain_cache.package_versions.contains_key(addr) {
+                continue;
+            }
+            let from_disk = match &self.disk {
+                Some(disk) => disk.get_package_version(chain_id, addr)?,
+                None => None,
+            };
+            match from_disk {
+                Some(version) => {
+                    chain_cache.package_versions.insert(*addr, version);
+                }
+                None => uncached_addrs.push(*addr),
+            }
+        }
+
+        // Query uncached packages
+        if !uncached_addrs.is_empty() {
+            if self.offline {
+                let disk = self.offline_disk_cache()?;
+                let missing: Vec<String> =
+                    uncached_addrs.iter().map(|a| a.to_hex_literal()).collect();
+                return Err(anyhow::anyhow!(
+                    "Offline mode: versions of packages [{}] on chain '{}' are not in the \
+                     cache at {}. Run once without --offline to populate the cache.",
+                    missing.join(", "),
+                    chain_id,
+                    disk.dir().display()
+                ));
+            }
+
+            let new_results = client.query_package_versions(&uncached_addrs).await?;
+
+            // Add to cache
+            for (addr, version) in new_results {
+                if let Some(disk) = &self.disk {
+                    if let Err(e) = disk.put_package_version(chain_id, &addr, version) {
+                        eprintln!("Warning: Failed to write GraphQL cache: {:#}", e);
+                    }
+                }
+                chain_cache.package_versions.insert(addr, version);
+            }
+        }
+
+        // Return subset matching requested addresses
+        let mut result = PackageVersionMap::new();
+        for addr in package_addrs {
+            if let Some(version) = chain_cache.package_versions.get(&addr) {
+                result.insert(addr, *version);
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Get a reference to the GraphQL client for an endpoint of a chain.
     ///
-    /// Creates a new client if one doesn't exist for the chain.
-    pub fn get_client(&mut self, chain_id: &str, graphql_url: &str) -> &GraphQLClient {
-        let chain_cache = self.get_or_create_chain(chain_id, graphql_url);
-        &chain_cache.client
+    /// Creates a new client if one doesn't exist for the endpoint and headers.
+    pub fn get_client(
+        &mut self,
+        chain_id: &str,
+        graphql_url: &str,
+        headers: &GraphQLHeaders,
+    ) -> Result<&GraphQLClient> {
+        let (_, client) = self.get_or_create_chain(chain_id, graphql_url, headers)?;
+        Ok(client)
     }
 
     /// Get cached type origins for a package if available.
@@ -119,6 +323,26 @@ impl GraphQLCache {
     }
 }
 
+fn get_or_create_chain<'a>(
+    chains: &'a mut BTreeMap<String, ChainCache>,
+    clients: &'a mut BTreeMap<(String, GraphQLHeaders), GraphQLClient>,
+    chain_id: &str,
+    graphql_url: &str,
+    headers: &GraphQLHeaders,
+    client_options: &ClientOptions,
+) -> Result<(&'a mut ChainCache, &'a GraphQLClient)> {
+    let client = match clients.entry((graphql_url.to_string(), headers.clone())) {
+        Entry::Occupied(entry) => entry.into_mut(),
+        Entry::Vacant(entry) => entry.insert(GraphQLClient::with_options(
+            graphql_url,
+            headers,
+            client_options.clone(),
+        )?),
+    };
+    let chain_cache = chains.entry(chain_id.to_string()).or_default();
+    Ok((chain_cache, client))
+}
+
 impl Default for GraphQLCache {
     fn default() -> Self {
         Self::new()
@@ -140,15 +364,147 @@ mod tests {
         let mut cache = GraphQLCache::new();
 
         // First access creates the chain
-        let _chain = cache.get_or_create_chain("4c78adac", "https://example.com/graphql");
+        let _chain = cache
+            .get_or_create_chain("4c78adac", "https://example.com/graphql", &GraphQLHeaders::new())
+            .unwrap();
         assert_eq!(cache.chains.len(), 1);
 
         // Second access with same chain_id reuses existing
-        let _chain = cache.get_or_create_chain("4c78adac", "https://example.com/graphql");
+        let _chain = cache
+            .get_or_create_chain("4c78adac", "https://example.com/graphql", &GraphQLHeaders::new())
+            .unwrap();
         assert_eq!(cache.chains.len(), 1);
 
         // Different chain_id creates new entry
-        let _chain = cache.get_or_create_chain("different", "https://other.com/graphql");
+        let _chain = cache
+            .get_or_create_chain("different", "https://other.com/graphql", &GraphQLHeaders::new())
+            .unwrap();
+        assert_eq!(cache.chains.len(), 2);
+        assert_eq!(cache.clients.len(), 2);
+
+        // Another endpoint of the same chain shares the chain's cache but gets its own client
+        let _chain = cache
+            .get_or_create_chain(
+                "4c78adac",
+                "https://alt.example.com/graphql",
+                &GraphQLHeaders::new(),
+            )
+            .unwrap();
+        assert_eq!(cache.chains.len(), 2);
+        assert_eq!(cache.clients.len(), 3);
+
+        // ...and so does the same endpoint with other headers
+        let headers = GraphQLHeaders::from([("x-api-key".to_string(), "secret".to_string())]);
+        let _chain = cache
+            .get_or_create_chain("4c78adac", "https://example.com/graphql", &headers)
+            .unwrap();
         assert_eq!(cache.chains.len(), 2);
+        assert_eq!(cache.clients.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_type_origins_served_from_disk_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let addr = AccountAddress::from_hex_literal("0x123").unwrap();
+        DiskCache::new(dir.path())
+            .put_type_origins(
+                "4c78adac",
+                &addr,
+                &[TypeOrigin {
+                    module: "pool".to_string(),
+                    struct_name: "Pool".to_string(),
+                    defining_id: "0x123".to_string(),
+                }],
+            )
+            .unwrap();
+
+        // Unreachable endpoint: offline mode must never hit the network
+        let mut cache = GraphQLCache::new()
+            .with_disk_cache(dir.path())
+            .with_offline(true);
+        let origins = cache
+            .query_type_origins(
+                "4c78adac",
+                "http://127.0.0.1:0/graphql",
+                &GraphQLHeaders::new(),
+                vec![addr],
+            )
+            .await
+            .unwrap();
+        assert_eq!(origins[&addr][0].struct_name, "Pool");
+        assert!(cache.get_cached_type_origins("4c78adac", &addr).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_offline_cache_miss_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let addr = AccountAddress::from_hex_literal("0x456").unwrap();
+
+        let mut cache = GraphQLCache::new()
+            .with_disk_cache(dir.path())
+            .with_offline(true);
+
+        let err = cache
+            .query_type_origins(
+                "4c78adac",
+                "http://127.0.0.1:0/graphql",
+                &GraphQLHeaders::new(),
+                vec![addr],
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Offline mode"));
+        assert!(err.to_string().contains(&addr.to_hex_literal()));
+
+        let err = cache
+            .query_chain_identifier("http://127.0.0.1:0/graphql", &GraphQLHeaders::new())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Offline mode"));
+    }
+
+    #[tokio::test]
+    async fn test_package_versions_served_from_disk_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let cached = AccountAddress::from_hex_literal("0x123").unwrap();
+        let missing = AccountAddress::from_hex_literal("0x456").unwrap();
+        DiskCache::new(dir.path())
+            .put_package_version("4c78adac", &cached, 2)
+            .unwrap();
+
+        let mut cache = GraphQLCache::new()
+            .with_disk_cache(dir.path())
+            .with_offline(true);
+        let versions = cache
+            .query_package_versions(
+                "4c78adac",
+                "http://127.0.0.1:0/graphql",
+                &GraphQLHeaders::new(),
+                vec![cached],
+            )
+            .await
+            .unwrap();
+        assert_eq!(versions[&cached], 2);
+
+        let err = cache
+            .query_package_versions(
+                "4c78adac",
+                "http://127.0.0.1:0/graphql",
+                &GraphQLHeaders::new(),
+                vec![cached, missing],
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains(&missing.to_hex_literal()));
+        assert!(!err.to_string().contains(&cached.to_hex_literal()));
+    }
+
+    #[tokio::test]
+    async fn test_offline_without_disk_cache_errors() {
+        let cache = GraphQLCache::new().with_offline(true);
+        assert!(cache
+            .query_chain_identifier("http://127.0.0.1:0/graphql", &GraphQLHeaders::new())
+            .await
+            .is_err());
     }
 }
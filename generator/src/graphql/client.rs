@@ -1,13 +1,99 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
 use anyhow::{Context, Result};
 use move_core_types::account_address::AccountAddress;
 use serde::de::DeserializeOwned;
 use serde_json::json;
 use sui_sdk::types::digests::{ChainIdentifier, CheckpointDigest};
 
-use super::types::{ChainIdentifierResponse, GraphQLResponse, TypeOrigin, TypeOriginMap};
+use super::types::{
+    ChainIdentifierResponse, GraphQLResponse, PackageData, PackageVersionData, PackageVersionMap,
+    TypeOrigin, TypeOriginMap,
+};
+
+/// Extra HTTP headers sent with every request to a GraphQL endpoint (e.g. API keys).
+pub type GraphQLHeaders = BTreeMap<String, String>;
+
+/// Default number of packages queried in a single batched GraphQL request.
+pub const DEFAULT_BATCH_SIZE: usize = 20;
+
+/// Default timeout of a single GraphQL request.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Default number of retries of a failed GraphQL request.
+pub const DEFAULT_MAX_RETRIES: u32 = 4;
+
+/// Request and retry settings for [`GraphQLClient`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientOptions {
+    /// Max number of packages per batched type origins query (1 disables batching)
+    pub batch_size: usize,
+    /// Timeout of a single HTTP request
+    pub timeout: Duration,
+    /// Number of retries on timeouts, connection errors, 429 and 5xx responses
+    pub max_retries: u32,
+    /// Delay before the first retry; doubled on every subsequent retry
+    pub initial_backoff: Duration,
+    /// Upper bound for the delay between retries
+    pub max_backoff: Duration,
+}
+
+impl Default for ClientOptions {
+    fn default() -> Self {
+        Self {
+            batch_size: DEFAULT_BATCH_SIZE,
+            timeout: DEFAULT_TIMEOUT,
+            max_retries: DEFAULT_MAX_RETRIES,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(10),
+        }
+    }
+}
+
+impl ClientOptions {
+    /// Delay before retry number `attempt` (0-based), capped at `max_backoff`.
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_backoff)
+    }
+}
+
+/// A failed HTTP request, and whether it's worth retrying.
+struct RequestError {
+    error: anyhow::Error,
+    retryable: bool,
+    /// Delay requested by the server via `Retry-After`
+    retry_after: Option<Duration>,
+}
+
+impl RequestError {
+    fn fatal(error: anyhow::Error) -> Self {
+        Self {
+            error,
+            retryable: false,
+            retry_after: None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for RequestError {
+    fn from(e: reqwest::Error) -> Self {
+        // Builder, body and decode errors won't go away on a retry
+        let retryable = e.is_timeout() || e.is_connect();
+        Self {
+            error: e.into(),
+            retryable,
+            retry_after: None,
+        }
+    }
+}
 
 /// GraphQL query strings
 mod queries {
+    use indoc::formatdoc;
+
     pub const CHAIN_IDENTIFIER: &str = r#"
         query {
             chainIdentifier
@@ -25,42 +111,129 @@ mod queries {
             }
         }
     "#;
+
+    /// Build a query fetching type origins of `count` packages in one request.
+    ///
+    /// Each package is aliased as `pkg{i}` and bound to the variable `$addr{i}`.
+    pub fn batched_package_type_origins(count: usize) -> String {
+        let params: Vec<String> = (0..count)
+            .map(|i| format!("$addr{}: SuiAddress!", i))
+            .collect();
+        let fields: Vec<String> = (0..count)
+            .map(|i| {
+                formatdoc! {"
+                    pkg{i}: package(address: $addr{i}) {{
+                        typeOrigins {{
+                            module
+                            struct
+                            definingId
+                        }}
+                    }}",
+                    i = i,
+                }
+            })
+            .collect();
+        format!(
+            "query GetBatchedPackageTypeOrigins({}) {{\n{}\n}}",
+            params.join(", "),
+            fields.join("\n")
+        )
+    }
+
+    /// Build a query fetching the versions of `count` packages in one request.
+    ///
+    /// Aliased the same way as [`batched_package_type_origins`].
+    pub fn batched_package_versions(count: usize) -> String {
+        let params: Vec<String> = (0..count)
+            .map(|i| format!("$addr{}: SuiAddress!", i))
+            .collect();
+        let fields: Vec<String> = (0..count)
+            .map(|i| format!("pkg{i}: package(address: $addr{i}) {{ version }}", i = i))
+            .collect();
+        format!(
+            "query GetBatchedPackageVersions({}) {{\n{}\n}}",
+            params.join(", "),
+            fields.join("\n")
+        )
+    }
 }
 
 /// GraphQL client for querying Sui packages
 pub struct GraphQLClient {
     client: reqwest::Client,
     endpoint: String,
+    options: ClientOptions,
 }
 
 impl GraphQLClient {
-    /// Create a new GraphQL client
-    pub fn new(endpoint: impl Into<String>) -> Self {
-        Self {
-            client: reqwest::Client::new(),
+    /// Create a new GraphQL client with default options
+    pub fn new(endpoint: impl Into<String>, headers: &GraphQLHeaders) -> Result<Self> {
+        Self::with_options(endpoint, headers, ClientOptions::default())
+    }
+
+    /// Create a new GraphQL client with the given headers and request/retry options
+    pub fn with_options(
+        endpoint: impl Into<String>,
+        headers: &GraphQLHeaders,
+        mut options: ClientOptions,
+    ) -> Result<Self> {
+        options.batch_size = options.batch_size.max(1);
+        let client = reqwest::Client::builder()
+            .timeout(options.timeout)
+            .default_headers(header_map(headers)?)
+            .build()
+            .context("Failed to build HTTP client")?;
+        Ok(Self {
+            client,
             endpoint: endpoint.into(),
-        }
+            options,
+        })
     }
 
-    /// Execute a GraphQL query and return raw JSON response
+    /// Execute a GraphQL query and return raw JSON response.
+    ///
+    /// Timeouts, connection errors, 429 and 5xx responses are retried with
+    /// exponential backoff (honoring `Retry-After`) up to `max_retries` times.
     async fn execute_raw_query(
         &self,
         query: &str,
         variables: serde_json::Value,
+        max_retries: u32,
     ) -> Result<serde_json::Value> {
         let request = json!({
             "query": query,
             "variables": variables
         });
 
-        let response = self
-            .client
-            .post(&self.endpoint)
-            .json(&request)
-            .send()
-            .await?;
-
-        let json_response: serde_json::Value = response.json().await?;
+        let mut attempt = 0;
+        let json_response = loop {
+            match self.send_request(&request).await {
+                Ok(json_response) => break json_response,
+                Err(e) if e.retryable && attempt < max_retries => {
+                    let delay = e
+                        .retry_after
+                        .unwrap_or_else(|| self.options.backoff(attempt))
+                        .min(self.options.max_backoff);
+                    eprintln!(
+                        "Warning: GraphQL request to {} failed ({}), retrying in {:?} ({}/{})",
+                        self.endpoint,
+                        e.error,
+                        delay,
+                        attempt + 1,
+                        max_retries
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                Err(e) if attempt > 0 => {
+                    return Err(e.error.context(format!(
+                        "GraphQL request to {} failed after {} retries",
+                        self.endpoint, attempt
+                    )));
+                }
+                Err(e) => return Err(e.error),
+            }
+        };
 
         // Check for GraphQL errors
         if let Some(errors) = json_response.get("errors") {
@@ -79,13 +252,48 @@ impl GraphQLClient {
         Ok(json_response)
     }
 
+    /// Send a single HTTP request, classifying failures as retryable or not.
+    async fn send_request(
+        &self,
+        request: &serde_json::Value,
+    ) -> std::result::Result<serde_json::Value, RequestError> {
+        let response = self.client.post(&self.endpoint).json(request).send().await?;
+
+        let status = response.status();
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
+            let retry_after = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .map(Duration::from_secs);
+            return Err(RequestError {
+                error: anyhow::anyhow!("HTTP {}", status),
+                retryable: true,
+                retry_after,
+            });
+        }
+        if !status.is_success() {
+            // GraphQL servers may report query errors with a 4xx status and an `errors` body
+            return match response.json::<serde_json::Value>().await {
+                Ok(json) if json.get("errors").is_some() => Ok(json),
+                _ => Err(RequestError::fatal(anyhow::anyhow!("HTTP {}", status))),
+            };
+        }
+
+        Ok(response.json().await?)
+    }
+
     /// Execute a GraphQL query with typed response
     async fn execute_query<T: DeserializeOwned>(
         &self,
         query: &str,
         variables: serde_json::Value,
+        max_retries: u32,
     ) -> Result<T> {
-        let json_response = self.execute_raw_query(query, variables).await?;
+        let json_response = self
+            .execute_raw_query(query, variables, max_retries)
+            .await?;
         let typed: T = serde_json::from_value(json_response)?;
         Ok(typed)
     }
@@ -100,7 +308,11 @@ impl GraphQLClient {
     /// `sui-package-alt` environments and `gen.toml`.
     pub async fn query_chain_identifier(&self) -> Result<String> {
         let response: ChainIdentifierResponse = self
-            .execute_query(queries::CHAIN_IDENTIFIER, json!({}))
+            .execute_query(
+                queries::CHAIN_IDENTIFIER,
+                json!({}),
+                self.options.max_retries,
+            )
             .await?;
 
         let raw = response
@@ -119,31 +331,261 @@ impl GraphQLClient {
         &self,
         package_addr: AccountAddress,
     ) -> Result<Vec<TypeOrigin>> {
+        self.fetch_package_type_origins(package_addr, self.options.max_retries)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Package not found at address 0x{:x}", package_addr))
+    }
+
+    /// Query a single package's type origins, returning `None` if the package
+    /// doesn't exist on-chain.
+    async fn fetch_package_type_origins(
+        &self,
+        package_addr: AccountAddress,
+        max_retries: u32,
+    ) -> Result<Option<Vec<TypeOrigin>>> {
         let addr_str = format!("0x{:x}", package_addr);
         let variables = json!({
             "packageAddr": &addr_str
         });
 
         let response: GraphQLResponse = self
-            .execute_query(queries::PACKAGE_TYPE_ORIGINS, variables)
+            .execute_query(queries::PACKAGE_TYPE_ORIGINS, variables, max_retries)
             .await?;
 
         match response.data {
-            Some(data) => match data.package {
-                Some(pkg) => Ok(pkg.type_origins),
-                None => Err(anyhow::anyhow!("Package not found at address {}", addr_str)),
-            },
+            Some(data) => Ok(data.package.map(|pkg| pkg.type_origins)),
             None => Err(anyhow::anyhow!("No data returned for package {}", addr_str)),
         }
     }
 
-    /// Query multiple packages' type origins in parallel.
+    /// Query type origins of several packages in a single request using field aliases.
     ///
-    /// Packages that are not found on-chain are skipped (logged as warning).
-    /// Only successfully queried packages are included in the result.
+    /// Packages that are not found on-chain are omitted from the result.
+    pub async fn query_packages_type_origins_batch(
+        &self,
+        package_addrs: &[AccountAddress],
+    ) -> Result<TypeOriginMap> {
+        let mut variables = serde_json::Map::new();
+        for (i, addr) in package_addrs.iter().enumerate() {
+            variables.insert(format!("addr{}", i), json!(format!("0x{:x}", addr)));
+        }
+
+        let query = queries::batched_package_type_origins(package_addrs.len());
+        let response = self
+            .execute_raw_query(
+                &query,
+                serde_json::Value::Object(variables),
+                self.options.max_retries,
+            )
+            .await?;
+        let data = response
+            .get("data")
+            .ok_or_else(|| anyhow::anyhow!("No data returned for batched type origins query"))?;
+
+        let mut map = TypeOriginMap::new();
+        for (i, addr) in package_addrs.iter().enumerate() {
+            let pkg: Option<PackageData> = match data.get(format!("pkg{}", i)) {
+                Some(value) => serde_json::from_value(value.clone())?,
+                None => None,
+            };
+            if let Some(pkg) = pkg {
+                map.insert(*addr, pkg.type_origins);
+            }
+        }
+
+        Ok(map)
+    }
+
+    /// Query multiple packages' type origins.
+    ///
+    /// Packages are queried in batches of up to `batch_size` per request (batches
+    /// run in parallel). If a batched request fails, its packages are re-queried
+    /// individually (without further retries) so that one bad package doesn't
+    /// fail the whole batch.
+    ///
+    /// Packages that are not found on-chain or could not be queried at all (e.g.
+    /// the endpoint kept failing after retries) result in an error, since silently
+    /// falling back to self-origin would generate wrong type names for upgraded
+    /// packages.
     pub async fn query_multiple_packages_type_origins(
         &self,
         package_addrs: Vec<AccountAddress>,
+    ) -> Result<TypeOriginMap> {
+        let map = self.query_type_origins_batched(&package_addrs).await?;
+
+        let missing: Vec<String> = package_addrs
+            .iter()
+            .filter(|addr| !map.contains_key(*addr))
+            .map(|addr| addr.to_hex_literal())
+            .collect();
+        if !missing.is_empty() {
+            return Err(anyhow::anyhow!(
+                "Packages not found on-chain at {}: {}",
+                self.endpoint,
+                missing.join(", ")
+            ));
+        }
+
+        Ok(map)
+    }
+
+    /// Query type origins in batches, omitting packages not found on-chain.
+    async fn query_type_origins_batched(
+        &self,
+        package_addrs: &[AccountAddress],
+    ) -> Result<TypeOriginMap> {
+        use futures::future;
+
+        if self.options.batch_size <= 1 {
+            return self
+                .query_packages_type_origins_individually(package_addrs, self.options.max_retries)
+                .await;
+        }
+
+        let batch_results = future::join_all(
+            package_addrs
+                .chunks(self.options.batch_size)
+                .map(|batch| async move {
+                    let result = self.query_packages_type_origins_batch(batch).await;
+                    (batch, result)
+                })
+                .collect::<Vec<_>>(),
+        )
+        .await;
+
+        let mut map = TypeOriginMap::new();
+        for (batch, result) in batch_results {
+            match result {
+                Ok(batch_map) => map.extend(batch_map),
+                Err(e) => {
+                    eprintln!(
+                        "Warning: Batched type origins query failed ({}), falling back to per-package queries",
+                        e
+                    );
+                    // The batch already used up the retry budget, so only try each package once
+                    map.extend(
+                        self.query_packages_type_origins_individually(batch, 0)
+                            .await?,
+                    );
+                }
+            }
+        }
+
+        Ok(map)
+    }
+
+    /// Query the on-chain versions of multiple packages.
+    ///
+    /// Packages are queried in batches of up to `batch_size` per request (batches
+    /// run in parallel). If a batched request fails, its packages are re-queried
+    /// individually (without further retries), like for type origins. Unlike type
+    /// origins, every package must exist: the addresses come from type origins
+    /// reported by the chain itself.
+    pub async fn query_package_versions(
+        &self,
+        package_addrs: &[AccountAddress],
+    ) -> Result<PackageVersionMap> {
+        use futures::future;
+
+        let batch_results = future::join_all(
+            package_addrs
+                .chunks(self.options.batch_size)
+                .map(|batch| async move {
+                    let result = self
+                        .query_package_versions_batch(batch, self.options.max_retries)
+                        .await;
+                    (batch, result)
+                })
+                .collect::<Vec<_>>(),
+        )
+        .await;
+
+        let mut map = PackageVersionMap::new();
+        for (batch, result) in batch_results {
+            match result {
+                Ok(batch_map) => map.extend(batch_map),
+                Err(e) if batch.len() > 1 => {
+                    eprintln!(
+                        "Warning: Batched package versions query failed ({}), falling back to per-package queries",
+                        e
+                    );
+                    // The batch already used up the retry budget, so only try each package once
+                    map.extend(self.query_package_versions_individually(batch).await?);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(map)
+    }
+
+    /// Query packages' versions with one request per package, in parallel.
+    async fn query_package_versions_individually(
+        &self,
+        package_addrs: &[AccountAddress],
+    ) -> Result<PackageVersionMap> {
+        use futures::future;
+
+        let results = future::join_all(
+            package_addrs
+                .iter()
+                .map(|addr| self.query_package_versions_batch(std::slice::from_ref(addr), 0))
+                .collect::<Vec<_>>(),
+        )
+        .await;
+
+        let mut map = PackageVersionMap::new();
+        for result in results {
+            map.extend(result?);
+        }
+        Ok(map)
+    }
+
+    /// Query versions of several packages in a single request using field aliases.
+    async fn query_package_versions_batch(
+        &self,
+        package_addrs: &[AccountAddress],
+        max_retries: u32,
+    ) -> Result<PackageVersionMap> {
+        let mut variables = serde_json::Map::new();
+        for (i, addr) in package_addrs.iter().enumerate() {
+            variables.insert(format!("addr{}", i), json!(format!("0x{:x}", addr)));
+        }
+
+        let query = queries::batched_package_versions(package_addrs.len());
+        let response = self
+            .execute_raw_query(&query, serde_json::Value::Object(variables), max_retries)
+            .await?;
+        let data = response
+            .get("data")
+            .ok_or_else(|| anyhow::anyhow!("No data returned for package versions query"))?;
+
+        let mut map = PackageVersionMap::new();
+        for (i, addr) in package_addrs.iter().enumerate() {
+            let pkg: Option<PackageVersionData> = match data.get(format!("pkg{}", i)) {
+                Some(value) => serde_json::from_value(value.clone())?,
+                None => None,
+            };
+            let pkg = pkg.ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Package not found at address 0x{:x} while querying its version from {}",
+                    addr,
+                    self.endpoint
+                )
+            })?;
+            map.insert(*addr, pkg.version);
+        }
+
+        Ok(map)
+    }
+
+    /// Query packages' type origins with one request per package, in parallel.
+    ///
+    /// Fails if any package could not be queried; packages not found on-chain
+    /// are omitted from the result.
+    async fn query_packages_type_origins_individually(
+        &self,
+        package_addrs: &[AccountAddress],
+        max_retries: u32,
     ) -> Result<TypeOriginMap> {
         use futures::future;
 
@@ -152,7 +594,7 @@ impl GraphQLClient {
             package_addrs
                 .iter()
                 .map(|&addr| async move {
-                    let result = self.query_package_type_origins(addr).await;
+                    let result = self.fetch_package_type_origins(addr, max_retries).await;
                     (addr, result)
                 })
                 .collect::<Vec<_>>(),
@@ -160,22 +602,286 @@ impl GraphQLClient {
         .await;
 
         let mut map = TypeOriginMap::new();
+        let mut failures = vec![];
         for (addr, result) in results {
             match result {
-                Ok(origins) => {
+                Ok(Some(origins)) => {
                     map.insert(addr, origins);
                 }
-                Err(e) => {
-                    // Log warning but continue - package will use self-origin
-                    eprintln!(
-                        "Warning: Failed to fetch type origins for 0x{:x}: {}",
-                        addr,
-                        e
+                Ok(None) => {}
+                Err(e) => failures.push(format!("0x{:x}: {:#}", addr, e)),
+            }
+        }
+
+        if !failures.is_empty() {
+            return Err(anyhow::anyhow!(
+                "Failed to fetch type origins from {}:\n  {}",
+                self.endpoint,
+                failures.join("\n  ")
+            ));
+        }
+
+        Ok(map)
+    }
+}
+
+/// Convert configured headers into a `HeaderMap`.
+///
+/// Header values are not included in errors since they typically hold secrets.
+fn header_map(headers: &GraphQLHeaders) -> Result<reqwest::header::HeaderMap> {
+    use reqwest::header::{HeaderName, HeaderValue};
+
+    let mut map = reqwest::header::HeaderMap::new();
+    for (name, value) in headers {
+        let header_name = HeaderName::from_bytes(name.as_bytes())
+            .with_context(|| format!("Invalid GraphQL header name '{}'", name))?;
+        let mut header_value = HeaderValue::from_str(value)
+            .map_err(|_| anyhow::anyhow!("Invalid value for GraphQL header '{}'", name))?;
+        header_value.set_sensitive(true);
+        map.insert(header_name, header_value);
+    }
+    Ok(map)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    use super::*;
+
+    /// Serve a local endpoint answering every request with `status` and `body`.
+    ///
+    /// Returns the endpoint URL and a counter of the requests received.
+    async fn mock_endpoint(status: u16, body: &'static str) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/graphql", listener.local_addr().unwrap());
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = requests.clone();
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let counter = counter.clone();
+                tokio::spawn(async move {
+                    read_request(&mut socket).await;
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    let response = format!(
+                        "HTTP/1.1 {} Mock\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                        status,
+                        body.len(),
+                        body
                     );
+                    let _ = socket.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+        (url, requests)
+    }
+
+    /// Read a single HTTP request (headers and body) from the socket.
+    async fn read_request(socket: &mut TcpStream) {
+        let mut buf = vec![];
+        let mut chunk = [0u8; 4096];
+        loop {
+            let n = socket.read(&mut chunk).await.unwrap();
+            if n == 0 {
+                return;
+            }
+            buf.extend_from_slice(&chunk[..n]);
+            let text = String::from_utf8_lossy(&buf);
+            if let Some(end) = text.find("\r\n\r\n") {
+                let content_length = text[..end]
+                    .lines()
+                    .find_map(|line| {
+                        line.to_ascii_lowercase()
+                            .strip_prefix("content-length:")
+                            .map(|v| v.trim().parse::<usize>().unwrap())
+                    })
+                    .unwrap_or(0);
+                if buf.len() >= end + 4 + content_length {
+                    return;
                 }
             }
         }
+    }
 
-        Ok(map)
+    fn test_options(max_retries: u32) -> ClientOptions {
+        ClientOptions {
+            max_retries,
+            initial_backoff: Duration::from_millis(1),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_batched_query() {
+        let query = queries::batched_package_type_origins(2);
+        assert!(query.starts_with(
+            "query GetBatchedPackageTypeOrigins($addr0: SuiAddress!, $addr1: SuiAddress!) {"
+        ));
+        assert!(query.contains("pkg0: package(address: $addr0) {"));
+        assert!(query.contains("pkg1: package(address: $addr1) {"));
+        assert!(!query.contains("pkg2"));
+        assert_eq!(query.matches("definingId").count(), 2);
+    }
+
+    #[test]
+    fn test_batched_versions_query() {
+        let query = queries::batched_package_versions(2);
+        assert!(query.starts_with(
+            "query GetBatchedPackageVersions($addr0: SuiAddress!, $addr1: SuiAddress!) {"
+        ));
+        assert!(query.contains("pkg0: package(address: $addr0) { version }"));
+        assert!(query.contains("pkg1: package(address: $addr1) { version }"));
+        assert!(!query.contains("pkg2"));
+    }
+
+    #[test]
+    fn test_batch_size_min() {
+        let options = ClientOptions {
+            batch_size: 0,
+            ..Default::default()
+        };
+        let client =
+            GraphQLClient::with_options("https://example.com/graphql", &GraphQLHeaders::new(), options)
+                .unwrap();
+        assert_eq!(client.options.batch_size, 1);
+    }
+
+    #[test]
+    fn test_header_map() {
+        let headers = GraphQLHeaders::from([("X-Api-Key".to_string(), "secret".to_string())]);
+        let map = header_map(&headers).unwrap();
+        assert_eq!(map["x-api-key"], "secret");
+        assert!(map["x-api-key"].is_sensitive());
+
+        let headers = GraphQLHeaders::from([("Bad Name".to_string(), "v".to_string())]);
+        assert!(header_map(&headers).is_err());
+
+        let headers = GraphQLHeaders::from([("X-Api-Key".to_string(), "se\ncret".to_string())]);
+        let err = header_map(&headers).unwrap_err();
+        assert!(err.to_string().contains("X-Api-Key"));
+        assert!(!err.to_string().contains("cret"), "header values must not leak into errors");
+    }
+
+    #[test]
+    fn test_backoff() {
+        let options = ClientOptions {
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(3),
+            ..Default::default()
+        };
+        assert_eq!(options.backoff(0), Duration::from_millis(500));
+        assert_eq!(options.backoff(1), Duration::from_secs(1));
+        assert_eq!(options.backoff(2), Duration::from_secs(2));
+        assert_eq!(options.backoff(3), Duration::from_secs(3));
+        assert_eq!(options.backoff(40), Duration::from_secs(3));
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_endpoint_is_an_error() {
+        let options = ClientOptions {
+            max_retries: 1,
+            initial_backoff: Duration::from_millis(1),
+            ..Default::default()
+        };
+        let client =
+            GraphQLClient::with_options("http://127.0.0.1:1/graphql", &GraphQLHeaders::new(), options)
+                .unwrap();
+        let addr = AccountAddress::from_hex_literal("0x2").unwrap();
+
+        let err = client
+            .query_multiple_packages_type_origins(vec![addr])
+            .await
+            .unwrap_err();
+        assert!(format!("{:#}", err).contains("127.0.0.1:1"));
+    }
+
+    #[tokio::test]
+    async fn test_missing_packages_are_an_error() {
+        let (url, _) = mock_endpoint(
+            200,
+            r#"{"data":{"pkg0":{"typeOrigins":[]},"pkg1":null,"pkg2":null}}"#,
+        )
+        .await;
+        let client =
+            GraphQLClient::with_options(url, &GraphQLHeaders::new(), test_options(0)).unwrap();
+        let addrs: Vec<AccountAddress> = ["0x2", "0x3", "0x4"]
+            .iter()
+            .map(|a| AccountAddress::from_hex_literal(a).unwrap())
+            .collect();
+
+        let err = client
+            .query_multiple_packages_type_origins(addrs.clone())
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Packages not found on-chain"), "{}", err);
+        assert!(!err.contains(&addrs[0].to_hex_literal()), "{}", err);
+        assert!(err.contains(&addrs[1].to_hex_literal()), "{}", err);
+        assert!(err.contains(&addrs[2].to_hex_literal()), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_batch_fallback_does_not_retry() {
+        let (url, requests) = mock_endpoint(503, "{}").await;
+        let client =
+            GraphQLClient::with_options(url, &GraphQLHeaders::new(), test_options(2)).unwrap();
+        let addrs: Vec<AccountAddress> = ["0x2", "0x3"]
+            .iter()
+            .map(|a| AccountAddress::from_hex_literal(a).unwrap())
+            .collect();
+
+        assert!(client
+            .query_multiple_packages_type_origins(addrs)
+            .await
+            .is_err());
+        // 1 batched request + 2 retries, then a single attempt per package
+        assert_eq!(requests.load(Ordering::SeqCst), 3 + 2);
+    }
+
+    #[tokio::test]
+    async fn test_versions_batch_fallback_does_not_retry() {
+        let (url, requests) = mock_endpoint(503, "{}").await;
+        let client =
+            GraphQLClient::with_options(url, &GraphQLHeaders::new(), test_options(2)).unwrap();
+        let addrs: Vec<AccountAddress> = ["0x2", "0x3"]
+            .iter()
+            .map(|a| AccountAddress::from_hex_literal(a).unwrap())
+            .collect();
+
+        assert!(client.query_package_versions(&addrs).await.is_err());
+        // 1 batched request + 2 retries, then a single attempt per package
+        assert_eq!(requests.load(Ordering::SeqCst), 3 + 2);
+    }
+
+    #[tokio::test]
+    async fn test_versions_batch_fallback_recovers() {
+        // Every request gets a single package back, so only per-package queries succeed
+        let (url, requests) = mock_endpoint(200, r#"{"data":{"pkg0":{"version":3}}}"#).await;
+        let client =
+            GraphQLClient::with_options(url, &GraphQLHeaders::new(), test_options(2)).unwrap();
+        let addrs: Vec<AccountAddress> = ["0x2", "0x3"]
+            .iter()
+            .map(|a| AccountAddress::from_hex_literal(a).unwrap())
+            .collect();
+
+        let versions = client.query_package_versions(&addrs).await.unwrap();
+        assert_eq!(versions.len(), 2);
+        assert!(versions.values().all(|v| *v == 3));
+        assert_eq!(requests.load(Ordering::SeqCst), 1 + 2);
+    }
+
+    #[tokio::test]
+    async fn test_client_errors_are_not_retried() {
+        let (url, requests) = mock_endpoint(401, "{}").await;
+        let client =
+            GraphQLClient::with_options(url, &GraphQLHeaders::new(), test_options(3)).unwrap();
+
+        assert!(client.query_chain_identifier().await.is_err());
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
     }
 }
@@ -0,0 +1,250 @@
+//! Persistent on-disk cache for GraphQL query results.
+//!
+//! Published packages are immutable, so type origins and versions of a package
+//! at a given address on a given chain never change and can be cached forever. Chain
+//! identifiers are cached per GraphQL endpoint so that offline runs can still
+//! validate the environment configuration.
+//!
+//! Layout:
+//! ```text
+//! <dir>/
+//!   chain-ids.json                          (endpoint URL -> chain id)
+//!   <chain-id>/type-origins/<0xaddr>.json   (Vec<TypeOrigin>)
+//!   <chain-id>/versions/<0xaddr>.json       (u64)
+//! ```
+
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use move_core_types::account_address::AccountAddress;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use super::types::TypeOrigin;
+
+const CHAIN_IDS_FILE: &str = "chain-ids.json";
+const TYPE_ORIGINS_DIR: &str = "type-origins";
+const VERSIONS_DIR: &str = "versions";
+
+/// Disk-backed cache of GraphQL results rooted at a directory.
+#[derive(Debug, Clone)]
+pub struct DiskCache {
+    dir: PathBuf,
+}
+
+impl DiskCache {
+    /// Create a cache rooted at `dir`. The directory is created lazily on first write.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The default cache directory for the current user, if one can be determined.
+    ///
+    /// Uses `$XDG_CACHE_HOME/sui-client-gen`, falling back to
+    /// `$HOME/.cache/sui-client-gen` (or `%LOCALAPPDATA%\sui-client-gen` on Windows).
+    pub fn default_dir() -> Option<PathBuf> {
+        let non_empty = |key: &str| std::env::var_os(key).filter(|v| !v.is_empty());
+
+        let base = non_empty("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .or_else(|| non_empty("HOME").map(|home| PathBuf::from(home).join(".cache")))
+            .or_else(|| non_empty("LOCALAPPDATA").map(PathBuf::from))?;
+        Some(base.join("sui-client-gen"))
+    }
+
+    /// The root directory of the cache.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Get the cached chain identifier for a GraphQL endpoint.
+    pub fn get_chain_id(&self, graphql_url: &str) -> Result<Option<String>> {
+        let chain_ids: BTreeMap<String, String> =
+            read_json(&self.dir.join(CHAIN_IDS_FILE))?.unwrap_or_default();
+        Ok(chain_ids.get(graphql_url).cloned())
+    }
+
+    /// Record the chain identifier for a GraphQL endpoint.
+    pub fn put_chain_id(&self, graphql_url: &str, chain_id: &str) -> Result<()> {
+        let path = self.dir.join(CHAIN_IDS_FILE);
+        let mut chain_ids: BTreeMap<String, String> = read_json(&path)?.unwrap_or_default();
+        if chain_ids.get(graphql_url).map(String::as_str) == Some(chain_id) {
+            return Ok(());
+        }
+        chain_ids.insert(graphql_url.to_string(), chain_id.to_string());
+        write_json(&path, &chain_ids)
+    }
+
+    /// Get the cached type origins of a package on a chain.
+    pub fn get_type_origins(
+        &self,
+        chain_id: &str,
+        package_addr: &AccountAddress,
+    ) -> Result<Option<Vec<TypeOrigin>>> {
+        read_json(&self.package_path(chain_id, TYPE_ORIGINS_DIR, package_addr)?)
+    }
+
+    /// Record the type origins of a package on a chain.
+    pub fn put_type_origins(
+        &self,
+        chain_id: &str,
+        package_addr: &AccountAddress,
+        origins: &[TypeOrigin],
+    ) -> Result<()> {
+        write_json(
+            &self.package_path(chain_id, TYPE_ORIGINS_DIR, package_addr)?,
+            &origins,
+        )
+    }
+
+    /// Get the cached on-chain version of a package.
+    pub fn get_package_version(
+        &self,
+        chain_id: &str,
+        package_addr: &AccountAddress,
+    ) -> Result<Option<u64>> {
+        read_json(&self.package_path(chain_id, VERSIONS_DIR, package_addr)?)
+    }
+
+    /// Record the on-chain version of a package.
+    pub fn put_package_version(
+        &self,
+        chain_id: &str,
+        package_addr: &AccountAddress,
+        version: u64,
+    ) -> Result<()> {
+        write_json(
+            &self.package_path(chain_id, VERSIONS_DIR, package_addr)?,
+            &version,
+        )
+    }
+
+    fn package_path(
+        &self,
+        chain_id: &str,
+        kind: &str,
+        package_addr: &AccountAddress,
+    ) -> Result<PathBuf> {
+        // Chain ids are used as directory names, so only allow safe characters
+        if chain_id.is_empty()
+            || !chain_id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            anyhow::bail!("Invalid chain id for disk cache: '{}'", chain_id);
+        }
+        Ok(self
+            .dir
+            .join(chain_id)
+            .join(kind)
+            .join(format!("{}.json", package_addr.to_hex_literal())))
+    }
+}
+
+fn read_json<T: DeserializeOwned>(path: &Path) -> Result<Option<T>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content =
+        std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let value = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse cache file {}", path.display()))?;
+    Ok(Some(value))
+}
+
+/// Write JSON atomically (write to a temp file in the same directory, then rename),
+/// so concurrent or interrupted runs never leave a partially written cache entry.
+fn write_json<T: Serialize + ?Sized>(path: &Path, value: &T) -> Result<()> {
+    let parent = path
+        .parent()
+        .ok_or_else(|| anyhow::anyhow!("Invalid cache path {}", path.display()))?;
+    std::fs::create_dir_all(parent)
+        .with_context(|| format!("Failed to create cache directory {}", parent.display()))?;
+
+    let mut tmp = tempfile::NamedTempFile::new_in(parent)?;
+    tmp.write_all(serde_json::to_string_pretty(value)?.as_bytes())?;
+    tmp.persist(path)
+        .with_context(|| format!("Failed to write cache file {}", path.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn origin(module: &str, name: &str, defining_id: &str) -> TypeOrigin {
+        TypeOrigin {
+            module: module.to_string(),
+            struct_name: name.to_string(),
+            defining_id: defining_id.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_chain_id_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = DiskCache::new(dir.path());
+
+        assert_eq!(cache.get_chain_id("https://a.example/graphql").unwrap(), None);
+
+        cache.put_chain_id("https://a.example/graphql", "4c78adac").unwrap();
+        cache.put_chain_id("https://b.example/graphql", "35834a8a").unwrap();
+
+        assert_eq!(
+            cache.get_chain_id("https://a.example/graphql").unwrap(),
+            Some("4c78adac".to_string())
+        );
+        assert_eq!(
+            cache.get_chain_id("https://b.example/graphql").unwrap(),
+            Some("35834a8a".to_string())
+        );
+    }
+
+    #[test]
+    fn test_type_origins_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = DiskCache::new(dir.path());
+        let addr = AccountAddress::from_hex_literal("0x123").unwrap();
+
+        assert!(cache.get_type_origins("4c78adac", &addr).unwrap().is_none());
+
+        cache
+            .put_type_origins("4c78adac", &addr, &[origin("pool", "Pool", "0x123")])
+            .unwrap();
+
+        let origins = cache.get_type_origins("4c78adac", &addr).unwrap().unwrap();
+        assert_eq!(origins.len(), 1);
+        assert_eq!(origins[0].module, "pool");
+        assert_eq!(origins[0].struct_name, "Pool");
+        assert_eq!(origins[0].defining_id, "0x123");
+
+        // Keyed by chain id
+        assert!(cache.get_type_origins("35834a8a", &addr).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_package_version_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = DiskCache::new(dir.path());
+        let addr = AccountAddress::from_hex_literal("0x123").unwrap();
+
+        assert_eq!(cache.get_package_version("4c78adac", &addr).unwrap(), None);
+        cache.put_package_version("4c78adac", &addr, 3).unwrap();
+        assert_eq!(cache.get_package_version("4c78adac", &addr).unwrap(), Some(3));
+
+        // Stored separately from type origins
+        assert!(cache.get_type_origins("4c78adac", &addr).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_rejects_unsafe_chain_id() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = DiskCache::new(dir.path());
+        let addr = AccountAddress::from_hex_literal("0x1").unwrap();
+
+        assert!(cache.get_type_origins("../escape", &addr).is_err());
+        assert!(cache.put_type_origins("", &addr, &[]).is_err());
+    }
+}
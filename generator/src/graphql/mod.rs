@@ -1,7 +1,9 @@
 pub mod cache;
 pub mod client;
+pub mod disk_cache;
 pub mod types;
 
 pub use cache::GraphQLCache;
-pub use client::GraphQLClient;
-pub use types::{TypeOrigin, TypeOriginMap};
+pub use client::{ClientOptions, GraphQLClient, GraphQLHeaders};
+pub use disk_cache::DiskCache;
+pub use types::{PackageVersionMap, TypeOrigin, TypeOriginMap};
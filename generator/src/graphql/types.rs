@@ -1,5 +1,5 @@
 use move_core_types::account_address::AccountAddress;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
 /// GraphQL response wrapper
@@ -41,7 +41,7 @@ pub(super) struct PackageData {
 }
 
 /// Type origin information from GraphQL
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct TypeOrigin {
     pub module: String,
     #[serde(rename = "struct")]
@@ -53,6 +53,15 @@ pub struct TypeOrigin {
 /// Map of package addresses to their type origins
 pub type TypeOriginMap = BTreeMap<AccountAddress, Vec<TypeOrigin>>;
 
+/// Package version data from GraphQL
+#[derive(Debug, Deserialize)]
+pub(super) struct PackageVersionData {
+    pub version: u64,
+}
+
+/// Map of package addresses to their on-chain versions
+pub type PackageVersionMap = BTreeMap<AccountAddress, u64>;
+
 /// GraphQL response for chain identifier query
 #[derive(Debug, Deserialize)]
 pub(super) struct ChainIdentifierResponse {
@@ -1,45 +1,352 @@
 //! File I/O utilities for code generation.
 //!
 //! Provides safe, reusable file operations for the generator.
+//!
+//! Generated files are first collected in memory via [`RenderedFiles`] and only
+//! then written to disk (or compared against the existing output in check mode).
+//!
+//! Every run records the files it emitted in [`GENERATED_MANIFEST_NAME`] so that
+//! files generated by a previous run but no longer emitted can be pruned without
+//! touching files added by the user.
+//!
+//! Files are only written when their content hash differs from the file on disk,
+//! so unchanged files keep their modification times (and downstream TS build
+//! caches stay valid).
 
-use std::path::Path;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Component, Path, PathBuf};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use similar::{ChangeTag, TextDiff};
 
+use crate::lockfile::LOCKFILE_NAME;
 use crate::ts_gen::TsFormatter;
 
-/// Write a string to a file, creating parent directories if needed.
+/// Name of the file (in the output root) listing all files emitted by the last run.
+pub const GENERATED_MANIFEST_NAME: &str = ".sui-client-gen-manifest.json";
+
+/// Hex-encoded SHA-256 hash of the given bytes.
+pub fn content_hash(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+/// The set of files emitted by a generator run, relative to the output root.
 ///
-/// Does nothing if the string is empty.
-pub fn write_str_to_file(s: &str, path: &Path) -> Result<()> {
-    if s.is_empty() {
-        return Ok(());
+/// Paths are stored with `/` separators so the manifest is portable across platforms.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GeneratedManifest {
+    pub files: BTreeSet<String>,
+    /// Input hash (compiled bytecode + generation context) of each module,
+    /// keyed by the module's output directory relative to the output root.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub modules: BTreeMap<String, String>,
+}
+
+impl GeneratedManifest {
+    /// Load the manifest from the output root. Returns `None` if there is none
+    /// (e.g. first run, or output generated by an older version).
+    pub fn load(out_root: &Path) -> Result<Option<Self>> {
+        let path = out_root.join(GENERATED_MANIFEST_NAME);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let manifest = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        Ok(Some(manifest))
     }
 
-    std::fs::write(path, s)?;
-    Ok(())
+    pub fn to_json(&self) -> Result<String> {
+        let mut json = serde_json::to_string_pretty(self)?;
+        json.push('\n');
+        Ok(json)
+    }
+
+    /// Resolve a manifest entry to a path under `out_root`.
+    ///
+    /// Returns `None` for entries that would escape the output root (absolute
+    /// paths or `..` components) or that point at `gen.toml`/`gen.lock`, so a tampered
+    /// manifest can never cause files outside the generated tree to be deleted.
+    fn resolve(out_root: &Path, rel: &str) -> Option<PathBuf> {
+        let rel_path = Path::new(rel);
+        let is_safe = rel_path
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)));
+        if !is_safe || rel == "gen.toml" || rel == LOCKFILE_NAME {
+            return None;
+        }
+        Some(out_root.join(rel_path))
+    }
 }
 
-/// Write TypeScript content to a file, formatting it first.
+/// Convert a path under `out_root` to a `/`-separated relative path.
 ///
-/// Does nothing if the string is empty.
-pub fn write_ts_file(formatter: &TsFormatter, content: &str, path: &Path) -> Result<()> {
-    if content.is_empty() {
-        return Ok(());
+/// Returns `None` if `path` is not under `out_root`.
+pub fn relative_path(out_root: &Path, path: &Path) -> Option<String> {
+    let rel = path.strip_prefix(out_root).ok()?;
+    Some(
+        rel.components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/"),
+    )
+}
+
+/// All files rendered during a generator run, keyed by output path.
+///
+/// Uses a `BTreeMap` so that writes and drift reports are deterministic.
+#[derive(Debug, Default)]
+pub struct RenderedFiles {
+    files: BTreeMap<PathBuf, String>,
+    module_hashes: BTreeMap<String, String>,
+}
+
+impl RenderedFiles {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a file with the given content verbatim.
+    ///
+    /// Does nothing if the string is empty.
+    pub fn add_str(&mut self, s: &str, path: &Path) {
+        if s.is_empty() {
+            return;
+        }
+        self.files.insert(path.to_path_buf(), s.to_string());
     }
 
-    let formatted = formatter.format(path, content)?;
-    std::fs::write(path, formatted)?;
+    /// Add a TypeScript file, formatting its content first.
+    ///
+    /// Does nothing if the string is empty.
+    pub fn add_ts(&mut self, formatter: &TsFormatter, content: &str, path: &Path) -> Result<()> {
+        if content.is_empty() {
+            return Ok(());
+        }
+
+        let formatted = formatter.format(path, content)?;
+        self.files.insert(path.to_path_buf(), formatted);
+        Ok(())
+    }
+
+    /// Iterate over all rendered files in path order.
+    pub fn iter(&self) -> impl Iterator<Item = (&PathBuf, &String)> {
+        self.files.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Record the input hash of a module so the next run can skip it if unchanged.
+    pub fn record_module_hash(&mut self, module_rel_dir: String, hash: String) {
+        self.module_hashes.insert(module_rel_dir, hash);
+    }
+
+    /// Re-add the files generated for a module by the previous run, reading them
+    /// back from disk instead of rendering them again.
+    ///
+    /// Returns `false` (and adds nothing) if the previous manifest lists no files
+    /// for the module or any of them is missing on disk.
+    pub fn reuse_previous_module(
+        &mut self,
+        out_root: &Path,
+        previous: &GeneratedManifest,
+        module_rel_dir: &str,
+    ) -> Result<bool> {
+        let prefix = format!("{}/", module_rel_dir);
+        let mut reused = vec![];
+        for rel in previous.files.iter().filter(|f| f.starts_with(&prefix)) {
+            let Some(path) = GeneratedManifest::resolve(out_root, rel) else {
+                return Ok(false);
+            };
+            if !path.is_file() {
+                return Ok(false);
+            }
+            let content = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            reused.push((path, content));
+        }
+        if reused.is_empty() {
+            return Ok(false);
+        }
+        self.files.extend(reused);
+        Ok(true)
+    }
+
+    /// Build the manifest of rendered files relative to `out_root`.
+    ///
+    /// Files outside of `out_root` are not recorded.
+    pub fn generated_manifest(&self, out_root: &Path) -> GeneratedManifest {
+        let files = self
+            .files
+            .keys()
+            .filter_map(|path| relative_path(out_root, path))
+            .collect();
+        GeneratedManifest {
+            files,
+            modules: self.module_hashes.clone(),
+        }
+    }
+
+    /// Files recorded in the `previous` manifest that still exist on disk but
+    /// were not rendered in this run.
+    pub fn stale_files(&self, out_root: &Path, previous: &GeneratedManifest) -> Vec<PathBuf> {
+        previous
+            .files
+            .iter()
+            .filter_map(|rel| GeneratedManifest::resolve(out_root, rel))
+            .filter(|path| !self.files.contains_key(path) && path.is_file())
+            .collect()
+    }
+
+    /// Write all rendered files to disk, creating parent directories if needed.
+    ///
+    /// Files whose bytes are identical to the file already on disk are left
+    /// untouched. Returns the number of files actually written.
+    pub fn write_all(&self) -> Result<usize> {
+        let mut written = 0;
+        for (path, content) in &self.files {
+            if path.is_file() {
+                let existing = std::fs::read(path)
+                    .with_context(|| format!("Failed to read {}", path.display()))?;
+                if existing == content.as_bytes() {
+                    continue;
+                }
+            }
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(path, content)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            written += 1;
+        }
+        Ok(written)
+    }
+
+    /// Compare all rendered files against what is currently on disk.
+    ///
+    /// Returns one entry per file that is missing or differs, plus one per
+    /// `stale` file (see [`RenderedFiles::stale_files`]). An empty result means
+    /// the output directory is up to date.
+    pub fn check_drift(&self, stale: &[PathBuf]) -> Result<Vec<FileDrift>> {
+        let mut drifts = vec![];
+        for (path, content) in &self.files {
+            if !path.exists() {
+                drifts.push(FileDrift::Missing { path: path.clone() });
+                continue;
+            }
+            let existing = std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            if &existing != content {
+                drifts.push(FileDrift::changed(path, &existing, content));
+            }
+        }
+        for path in stale {
+            drifts.push(FileDrift::Stale { path: path.clone() });
+        }
+        Ok(drifts)
+    }
+}
+
+/// A difference between a rendered file and the file on disk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileDrift {
+    /// The file would be generated but does not exist on disk.
+    Missing { path: PathBuf },
+    /// The file exists on disk but its content differs.
+    Changed {
+        path: PathBuf,
+        insertions: usize,
+        deletions: usize,
+        /// Unified diff from the on-disk content to the rendered content.
+        diff: String,
+    },
+    /// The file was generated by a previous run but is no longer emitted.
+    Stale { path: PathBuf },
+}
+
+impl FileDrift {
+    fn changed(path: &Path, existing: &str, rendered: &str) -> Self {
+        let text_diff = TextDiff::from_lines(existing, rendered);
+
+        let mut insertions = 0;
+        let mut deletions = 0;
+        for change in text_diff.iter_all_changes() {
+            match change.tag() {
+                ChangeTag::Insert => insertions += 1,
+                ChangeTag::Delete => deletions += 1,
+                ChangeTag::Equal => (),
+            }
+        }
+
+        let display_path = path.display().to_string();
+        let diff = text_diff
+            .unified_diff()
+            .context_radius(3)
+            .header(
+                &format!("a/{}", display_path),
+                &format!("b/{}", display_path),
+            )
+            .to_string();
+
+        FileDrift::Changed {
+            path: path.to_path_buf(),
+            insertions,
+            deletions,
+            diff,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        match self {
+            FileDrift::Missing { path }
+            | FileDrift::Changed { path, .. }
+            | FileDrift::Stale { path } => path,
+        }
+    }
+}
+
+/// Delete stale generated files and any directories left empty by their removal.
+///
+/// Directories are only removed up to (but excluding) `out_root`, and only if
+/// they are empty, so user-added files are never touched.
+pub fn prune_stale_files(out_root: &Path, stale: &[PathBuf]) -> Result<()> {
+    for path in stale {
+        std::fs::remove_file(path)
+            .with_context(|| format!("Failed to remove stale file {}", path.display()))?;
+
+        let mut dir = path.parent();
+        while let Some(d) = dir {
+            if d == out_root || !d.starts_with(out_root) {
+                break;
+            }
+            let is_empty = std::fs::read_dir(d)?.next().is_none();
+            if !is_empty {
+                break;
+            }
+            std::fs::remove_dir(d)?;
+            dir = d.parent();
+        }
+    }
     Ok(())
 }
 
-/// Clean the output directory, removing all contents except gen.toml.
+/// Clean the output directory, removing all contents except gen.toml and gen.lock.
 pub fn clean_output(out_root: &Path) -> Result<()> {
     let mut paths_to_remove = vec![];
     for entry in std::fs::read_dir(out_root)? {
         let entry = entry?;
         let path = entry.path();
-        if path.is_file() && path.file_name().unwrap() == "gen.toml" {
+        let file_name = path.file_name().unwrap();
+        if path.is_file() && (file_name == "gen.toml" || file_name == LOCKFILE_NAME) {
             continue;
         }
         paths_to_remove.push(path);
@@ -55,3 +362,196 @@ pub fn clean_output(out_root: &Path) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_str_skips_empty() {
+        let mut files = RenderedFiles::new();
+        files.add_str("", Path::new("empty.ts"));
+        assert!(files.is_empty());
+    }
+
+    #[test]
+    fn test_write_all_creates_parent_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("a").join("b").join("structs.ts");
+
+        let mut files = RenderedFiles::new();
+        files.add_str("export {}\n", &path);
+        assert_eq!(files.write_all().unwrap(), 1);
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "export {}\n");
+    }
+
+    #[test]
+    fn test_write_all_skips_unchanged() {
+        let dir = tempfile::tempdir().unwrap();
+        let unchanged = dir.path().join("unchanged.ts");
+        let changed = dir.path().join("changed.ts");
+        std::fs::write(&unchanged, "const a = 1\n").unwrap();
+        std::fs::write(&changed, "const b = 1\n").unwrap();
+
+        let mut files = RenderedFiles::new();
+        files.add_str("const a = 1\n", &unchanged);
+        files.add_str("const b = 2\n", &changed);
+
+        assert_eq!(files.write_all().unwrap(), 1);
+        assert_eq!(std::fs::read_to_string(&changed).unwrap(), "const b = 2\n");
+    }
+
+    #[test]
+    fn test_content_hash() {
+        assert_eq!(
+            content_hash(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_ne!(content_hash(b"a"), content_hash(b"b"));
+    }
+
+    #[test]
+    fn test_reuse_previous_module() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let structs = root.join("pkg").join("mod").join("structs.ts");
+        std::fs::create_dir_all(structs.parent().unwrap()).unwrap();
+        std::fs::write(&structs, "export class A {}\n").unwrap();
+
+        let previous = GeneratedManifest {
+            files: ["pkg/mod/structs.ts", "pkg/mod-other/structs.ts"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            modules: BTreeMap::new(),
+        };
+
+        let mut files = RenderedFiles::new();
+        assert!(files.reuse_previous_module(root, &previous, "pkg/mod").unwrap());
+        assert_eq!(files.len(), 1);
+        assert_eq!(files.files[&structs], "export class A {}\n");
+
+        // Files of the other module are listed but missing on disk
+        let mut files = RenderedFiles::new();
+        assert!(!files.reuse_previous_module(root, &previous, "pkg/mod-other").unwrap());
+        assert!(files.is_empty());
+    }
+
+    #[test]
+    fn test_check_drift() {
+        let dir = tempfile::tempdir().unwrap();
+        let unchanged = dir.path().join("unchanged.ts");
+        let changed = dir.path().join("changed.ts");
+        let missing = dir.path().join("missing.ts");
+        std::fs::write(&unchanged, "const a = 1\n").unwrap();
+        std::fs::write(&changed, "const b = 1\nconst c = 2\n").unwrap();
+
+        let mut files = RenderedFiles::new();
+        files.add_str("const a = 1\n", &unchanged);
+        files.add_str("const b = 2\nconst c = 2\n", &changed);
+        files.add_str("const d = 1\n", &missing);
+
+        let drifts = files.check_drift(&[]).unwrap();
+        assert_eq!(drifts.len(), 2);
+
+        match &drifts[0] {
+            FileDrift::Changed {
+                path,
+                insertions,
+                deletions,
+                diff,
+            } => {
+                assert_eq!(path, &changed);
+                assert_eq!(*insertions, 1);
+                assert_eq!(*deletions, 1);
+                assert!(diff.contains("-const b = 1"));
+                assert!(diff.contains("+const b = 2"));
+            }
+            other => panic!("expected Changed, got {:?}", other),
+        }
+        assert_eq!(drifts[1], FileDrift::Missing { path: missing });
+    }
+
+    #[test]
+    fn test_check_drift_up_to_date() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("index.ts");
+        std::fs::write(&path, "export {}\n").unwrap();
+
+        let mut files = RenderedFiles::new();
+        files.add_str("export {}\n", &path);
+
+        assert!(files.check_drift(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_generated_manifest_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+
+        let mut files = RenderedFiles::new();
+        files.add_str("a", &root.join("_framework").join("util.ts"));
+        files.add_str("b", &root.join("pkg").join("mod").join("structs.ts"));
+        files.record_module_hash("pkg/mod".to_string(), "abc".to_string());
+
+        let manifest = files.generated_manifest(root);
+        assert_eq!(
+            manifest.files.iter().collect::<Vec<_>>(),
+            vec!["_framework/util.ts", "pkg/mod/structs.ts"]
+        );
+        assert_eq!(manifest.modules["pkg/mod"], "abc");
+
+        std::fs::write(root.join(GENERATED_MANIFEST_NAME), manifest.to_json().unwrap()).unwrap();
+        assert_eq!(GeneratedManifest::load(root).unwrap(), Some(manifest));
+    }
+
+    #[test]
+    fn test_load_missing_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(GeneratedManifest::load(dir.path()).unwrap(), None);
+    }
+
+    #[test]
+    fn test_prune_stale_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+
+        let kept = root.join("pkg").join("kept").join("structs.ts");
+        let removed = root.join("pkg").join("removed").join("structs.ts");
+        let user_file = root.join("pkg").join("user.ts");
+        for path in [&kept, &removed, &user_file] {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "x").unwrap();
+        }
+
+        let previous = GeneratedManifest {
+            files: [
+                "pkg/kept/structs.ts",
+                "pkg/removed/structs.ts",
+                "pkg/already-gone/structs.ts",
+                "../outside.ts",
+                "gen.toml",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+            modules: BTreeMap::new(),
+        };
+
+        let mut files = RenderedFiles::new();
+        files.add_str("x", &kept);
+
+        let stale = files.stale_files(root, &previous);
+        assert_eq!(stale, vec![removed.clone()]);
+
+        let drifts = files.check_drift(&stale).unwrap();
+        assert_eq!(drifts, vec![FileDrift::Stale { path: removed.clone() }]);
+
+        prune_stale_files(root, &stale).unwrap();
+        assert!(kept.exists());
+        assert!(user_file.exists());
+        assert!(!removed.exists());
+        assert!(!removed.parent().unwrap().exists());
+    }
+}
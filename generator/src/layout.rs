@@ -3,35 +3,140 @@
 //! This module provides structured representations for the output directory hierarchy,
 //! centralizing path calculation logic.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::PathBuf;
 
+use anyhow::{bail, Context, Result};
 use move_core_types::account_address::AccountAddress;
 use move_package_alt::schema::PackageName;
 use move_symbol_pool::Symbol;
 
 use crate::ts_gen::{module_import_name, package_import_name};
 
+// ============================================================================
+// Package Keys
+// ============================================================================
+
+/// Resolve a package key of gen.toml (`[naming]`, `[filters]` and `[dep-functions]`) to the
+/// package's address.
+///
+/// A key is either an address (e.g. `0x2`) or a package name: the name in `[packages]` for
+/// top-level packages, the dependency's own package name otherwise. Names are compared in
+/// kebab-case, like the folder names derived from them, so `deepbook_v3` and `deepbook-v3`
+/// are the same key. Returns `None` for unknown packages, and an error if a name matches
+/// more than one package.
+pub fn resolve_package_key(
+    key: &str,
+    id_map: &BTreeMap<AccountAddress, PackageName>,
+    top_level_pkg_names: &BTreeMap<AccountAddress, Symbol>,
+) -> Result<Option<AccountAddress>> {
+    let addrs: BTreeSet<AccountAddress> = id_map
+        .keys()
+        .chain(top_level_pkg_names.keys())
+        .copied()
+        .collect();
+
+    if key.starts_with("0x") {
+        let addr = AccountAddress::from_hex_literal(key)
+            .with_context(|| format!("Invalid package address '{}'", key))?;
+        return Ok(addrs.contains(&addr).then_some(addr));
+    }
+
+    let name = package_import_name(Symbol::from(key));
+    let matches: Vec<AccountAddress> = addrs
+        .into_iter()
+        .filter(|addr| {
+            let top_level_name = top_level_pkg_names.get(addr).map(|n| n.as_str());
+            let pkg_name = id_map.get(addr).map(|n| n.as_str());
+            [top_level_name, pkg_name]
+                .into_iter()
+                .flatten()
+                .any(|n| package_import_name(Symbol::from(n)) == name)
+        })
+        .collect();
+    match matches[..] {
+        [] => Ok(None),
+        [addr] => Ok(Some(addr)),
+        _ => bail!(
+            "Package name '{}' matches several packages ({}), use the address of the one you mean",
+            key,
+            matches
+                .iter()
+                .map(|addr| addr.to_hex_literal())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
 // ============================================================================
 // Package Folder Name Resolution
 // ============================================================================
 
 /// Build a map from package address to output folder name.
 ///
+/// - Packages pinned in `[naming.folders]` (see [`resolve_package_key`]): the pinned name
 /// - Top-level packages: kebab-case of their name (no suffix)
 /// - Dependencies with unique names: kebab-case (no suffix)
-/// - Dependencies with colliding names: kebab-case with `-1`, `-2`, ... suffixes
+/// - Dependencies with colliding names (including with pinned dependencies): kebab-case
+///   with `-1`, `-2`, ... suffixes
 ///
 /// The suffix order is determined by BTreeMap ordering (by address) for determinism.
+///
+/// Returns an error if a pinned package doesn't exist, is pinned twice, or if a pinned
+/// top-level folder is the folder name of another top-level package.
 pub fn build_package_folder_names(
     id_map: &BTreeMap<AccountAddress, PackageName>,
     top_level_pkg_names: &BTreeMap<AccountAddress, Symbol>,
-) -> BTreeMap<AccountAddress, String> {
+    pinned: &BTreeMap<String, String>,
+) -> Result<BTreeMap<AccountAddress, String>> {
     let mut result: BTreeMap<AccountAddress, String> = BTreeMap::new();
 
-    // Step 1: Add top-level packages (no suffix needed, unique by definition)
+    // Step 0: Add pinned packages
+    let mut pinned_by: BTreeMap<AccountAddress, &str> = BTreeMap::new();
+    for (key, folder) in pinned {
+        let Some(addr) = resolve_package_key(key, id_map, top_level_pkg_names)
+            .context("Error in [naming.folders]")?
+        else {
+            bail!("[naming.folders] lists unknown package '{}'", key);
+        };
+        if let Some(other) = pinned_by.insert(addr, key) {
+            bail!(
+                "[naming.folders] lists package {} twice, as '{}' and '{}'",
+                addr.to_hex_literal(),
+                other,
+                key
+            );
+        }
+        result.insert(addr, folder.clone());
+    }
+    // Folder names of pinned dependencies, which other dependencies must not reuse
+    let taken: BTreeSet<String> = result
+        .iter()
+        .filter(|(addr, _)| !top_level_pkg_names.contains_key(*addr))
+        .map(|(_, folder)| folder.clone())
+        .collect();
+
+    // Step 1: Add top-level packages (no suffix, the names in [packages] are unique).
+    // They can't be renamed to make room for a pinned top-level folder.
     for (addr, name) in top_level_pkg_names {
-        result.insert(*addr, package_import_name(*name));
+        if result.contains_key(addr) {
+            continue;
+        }
+        let folder = package_import_name(*name);
+        if let Some((_, key)) = pinned_by
+            .iter()
+            .find(|(a, _)| top_level_pkg_names.contains_key(*a) && result[*a] == folder)
+        {
+            bail!(
+                "[naming.folders] pins package '{}' to '{}', which is the folder of top-level \
+                 package '{}'",
+                key,
+                folder,
+                name
+            );
+        }
+        result.insert(*addr, folder);
     }
 
     // Step 2: Collect dependency packages grouped by kebab-case name
@@ -39,8 +144,8 @@ pub fn build_package_folder_names(
     let mut deps_by_name: BTreeMap<String, Vec<AccountAddress>> = BTreeMap::new();
 
     for (addr, pkg_name) in id_map {
-        // Skip top-level packages (already handled)
-        if top_level_pkg_names.contains_key(addr) {
+        // Skip top-level and pinned packages (already handled)
+        if top_level_pkg_names.contains_key(addr) || result.contains_key(addr) {
             continue;
         }
 
@@ -50,19 +155,22 @@ pub fn build_package_folder_names(
 
     // Step 3: Assign folder names to dependencies
     for (kebab_name, addrs) in deps_by_name {
-        if addrs.len() == 1 {
+        if addrs.len() == 1 && !taken.contains(&kebab_name) {
             // Unique name - no suffix needed
             result.insert(addrs[0], kebab_name);
         } else {
-            // Collision - add suffixes starting from 1
+            // Collision - add suffixes starting from 1, skipping pinned names
             // addrs is already sorted by address (from BTreeMap iteration)
-            for (i, addr) in addrs.iter().enumerate() {
-                result.insert(*addr, format!("{}-{}", kebab_name, i + 1));
+            let mut suffixed = (1..)
+                .map(|i| format!("{}-{}", kebab_name, i))
+                .filter(|name| !taken.contains(name));
+            for addr in addrs {
+                result.insert(addr, suffixed.next().unwrap());
             }
         }
     }
 
-    result
+    Ok(result)
 }
 
 // ============================================================================
@@ -180,7 +288,7 @@ mod tests {
         let mut top_level: BTreeMap<AccountAddress, Symbol> = BTreeMap::new();
         top_level.insert(addr1, Symbol::from("my_top_level"));
 
-        let result = build_package_folder_names(&id_map, &top_level);
+        let result = build_package_folder_names(&id_map, &top_level, &BTreeMap::new()).unwrap();
 
         assert_eq!(result.get(&addr1), Some(&"my-top-level".to_string()));
         assert_eq!(result.get(&addr2), Some(&"move-stdlib".to_string()));
@@ -202,7 +310,7 @@ mod tests {
         let mut top_level: BTreeMap<AccountAddress, Symbol> = BTreeMap::new();
         top_level.insert(addr1, Symbol::from("my_package"));
 
-        let result = build_package_folder_names(&id_map, &top_level);
+        let result = build_package_folder_names(&id_map, &top_level, &BTreeMap::new()).unwrap();
 
         assert_eq!(result.get(&addr1), Some(&"my-package".to_string()));
         // addr2 < addr3 in BTreeMap order, so addr2 gets -1, addr3 gets -2
@@ -223,7 +331,7 @@ mod tests {
         let mut top_level: BTreeMap<AccountAddress, Symbol> = BTreeMap::new();
         top_level.insert(addr1, Symbol::from("my_package"));
 
-        let result = build_package_folder_names(&id_map, &top_level);
+        let result = build_package_folder_names(&id_map, &top_level, &BTreeMap::new()).unwrap();
 
         // Top-level never gets a suffix
         assert_eq!(result.get(&addr1), Some(&"my-package".to_string()));
@@ -231,6 +339,43 @@ mod tests {
         assert_eq!(result.get(&addr2), Some(&"my-package".to_string()));
     }
 
+    #[test]
+    fn test_pinned_folder_names() {
+        let addr1 = addr("0x1");
+        let addr2 = addr("0x2");
+        let addr3 = addr("0x3");
+        let addr4 = addr("0x4");
+        let addr5 = addr("0x5");
+
+        let mut id_map: BTreeMap<AccountAddress, PackageName> = BTreeMap::new();
+        id_map.insert(addr1, pkg_name("my_package"));
+        id_map.insert(addr2, pkg_name("deepbook"));
+        id_map.insert(addr3, pkg_name("deepbook"));
+        id_map.insert(addr4, pkg_name("token"));
+        id_map.insert(addr5, pkg_name("token_v2"));
+
+        let mut top_level: BTreeMap<AccountAddress, Symbol> = BTreeMap::new();
+        top_level.insert(addr1, Symbol::from("my_package"));
+
+        let pinned: BTreeMap<String, String> = [
+            ("my_package", "app"),  // by name, top-level
+            ("0x3", "deepbook-v3"), // by address
+            ("token_v2", "token"),  // takes the name of an unpinned dependency
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+
+        let result = build_package_folder_names(&id_map, &top_level, &pinned).unwrap();
+
+        assert_eq!(result.get(&addr1), Some(&"app".to_string()));
+        // The other deepbook no longer collides
+        assert_eq!(result.get(&addr2), Some(&"deepbook".to_string()));
+        assert_eq!(result.get(&addr3), Some(&"deepbook-v3".to_string()));
+        assert_eq!(result.get(&addr4), Some(&"token-1".to_string()));
+        assert_eq!(result.get(&addr5), Some(&"token".to_string()));
+    }
+
     #[test]
     fn test_deterministic_ordering_by_address() {
         // Ensure address ordering is deterministic
@@ -246,11 +391,84 @@ mod tests {
 
         let top_level: BTreeMap<AccountAddress, Symbol> = BTreeMap::new();
 
-        let result = build_package_folder_names(&id_map, &top_level);
+        let result = build_package_folder_names(&id_map, &top_level, &BTreeMap::new()).unwrap();
 
         // BTreeMap sorts by address: addr_a < addr_b < addr_c
         assert_eq!(result.get(&addr_a), Some(&"same-name-1".to_string()));
         assert_eq!(result.get(&addr_b), Some(&"same-name-2".to_string()));
         assert_eq!(result.get(&addr_c), Some(&"same-name-3".to_string()));
     }
+
+    #[test]
+    fn test_resolve_package_key() {
+        let mut id_map: BTreeMap<AccountAddress, PackageName> = BTreeMap::new();
+        id_map.insert(addr("0x1"), pkg_name("move_stdlib"));
+        id_map.insert(addr("0x2"), pkg_name("Sui"));
+        id_map.insert(addr("0xa"), pkg_name("deepbook"));
+        id_map.insert(addr("0xb"), pkg_name("deepbook"));
+        id_map.insert(addr("0xc"), pkg_name("my_app"));
+
+        let mut top_level: BTreeMap<AccountAddress, Symbol> = BTreeMap::new();
+        top_level.insert(addr("0xc"), Symbol::from("app"));
+
+        let resolve = |key: &str| resolve_package_key(key, &id_map, &top_level);
+
+        // Names are compared in kebab-case, like folder names
+        assert_eq!(resolve("sui").unwrap(), Some(addr("0x2")));
+        assert_eq!(resolve("move-stdlib").unwrap(), Some(addr("0x1")));
+        assert_eq!(resolve("move_stdlib").unwrap(), Some(addr("0x1")));
+        // Top-level packages by their name in [packages] or their own name
+        assert_eq!(resolve("app").unwrap(), Some(addr("0xc")));
+        assert_eq!(resolve("my_app").unwrap(), Some(addr("0xc")));
+        // Addresses, in any form
+        assert_eq!(resolve("0xa").unwrap(), Some(addr("0xa")));
+        assert_eq!(resolve("0x000a").unwrap(), Some(addr("0xa")));
+
+        assert_eq!(resolve("unknown").unwrap(), None);
+        assert_eq!(resolve("0xd").unwrap(), None);
+        assert!(resolve("0xzz").is_err());
+
+        let err = resolve("deepbook").unwrap_err().to_string();
+        assert!(
+            err.contains("matches several packages (0xa, 0xb)"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_pinned_folder_names_errors() {
+        let addr1 = addr("0x1");
+        let addr2 = addr("0x2");
+        let addr3 = addr("0x3");
+        let addr4 = addr("0x4");
+
+        let mut id_map: BTreeMap<AccountAddress, PackageName> = BTreeMap::new();
+        id_map.insert(addr1, pkg_name("app"));
+        id_map.insert(addr2, pkg_name("admin"));
+        id_map.insert(addr3, pkg_name("deepbook"));
+        id_map.insert(addr4, pkg_name("deepbook"));
+
+        let mut top_level: BTreeMap<AccountAddress, Symbol> = BTreeMap::new();
+        top_level.insert(addr1, Symbol::from("app"));
+        top_level.insert(addr2, Symbol::from("admin"));
+
+        let build = |pins: &[(&str, &str)]| {
+            let pinned: BTreeMap<String, String> = pins
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect();
+            build_package_folder_names(&id_map, &top_level, &pinned)
+                .unwrap_err()
+                .to_string()
+        };
+
+        // Ambiguous names must be pinned by address
+        assert!(build(&[("deepbook", "db")]).contains("Error in [naming.folders]"));
+        assert!(build(&[("unknown", "x")]).contains("lists unknown package 'unknown'"));
+        assert!(build(&[("app", "x"), ("0x1", "y")]).contains("lists package 0x1 twice"));
+        // A pinned top-level folder can't take the folder of another top-level package
+        assert!(build(&[("app", "admin")])
+            .contains("pins package 'app' to 'admin', which is the folder of top-level package"));
+    }
 }
@@ -1,14 +1,19 @@
 pub mod driver;
+pub mod filter;
 pub mod framework_sources;
 pub mod graphql;
 pub mod io;
 pub mod layout;
+pub mod lockfile;
 pub mod manifest;
 pub mod model_builder;
 pub mod multi_env;
+pub mod schema;
 pub mod ts_gen;
 
-use manifest::Environments;
+use anyhow::{Context, Result};
+use graphql::GraphQLHeaders;
+use manifest::{interpolate_env_vars, Environments};
 use move_package_alt::MoveFlavor;
 use sui_package_alt::SuiFlavor;
 
@@ -63,3 +68,102 @@ pub fn resolve_graphql(
         _ => DEFAULT_GRAPHQL.to_string(), // mainnet or any other defaults to mainnet
     }
 }
+
+/// Resolve the extra HTTP headers for the GraphQL endpoint of a given environment.
+///
+/// Headers are only sent to the endpoint they were configured for, so credentials
+/// never reach a different provider: headers from `[config]` go to the `[config]`
+/// endpoint, and headers of the environment's `[environments]` entry to the
+/// environment's own endpoint (i.e. when `[config]` doesn't override it, and the
+/// environment sets one). Default endpoints never get headers.
+///
+/// `${VAR}` references in header values are expanded from the process environment.
+pub fn resolve_graphql_headers(
+    config_graphql: Option<&str>,
+    config_headers: &GraphQLHeaders,
+    environment: &str,
+    environments: &Environments,
+) -> Result<GraphQLHeaders> {
+    let configured = match (config_graphql, environments.get(environment)) {
+        (Some(_), _) => config_headers,
+        (None, Some(env)) if env.graphql.is_some() => &env.graphql_headers,
+        (None, _) => return Ok(GraphQLHeaders::new()),
+    };
+
+    // Header names are case-insensitive, so normalize them
+    let mut headers = GraphQLHeaders::new();
+    for (name, value) in configured {
+        let value = interpolate_env_vars(value).with_context(|| {
+            format!(
+                "Failed to resolve GraphQL header '{}' for environment '{}'",
+                name, environment
+            )
+        })?;
+        headers.insert(name.to_ascii_lowercase(), value);
+    }
+    Ok(headers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use manifest::Environment;
+
+    #[test]
+    fn test_graphql_headers_only_sent_to_their_endpoint() {
+        let config_headers = GraphQLHeaders::from([("User-Agent".to_string(), "app".to_string())]);
+        let environments = Environments::from([(
+            "staging".to_string(),
+            Environment {
+                chain_id: Some("12345678".to_string()),
+                graphql: Some("https://provider.example.com/graphql".to_string()),
+                graphql_headers: GraphQLHeaders::from([(
+                    "X-Api-Key".to_string(),
+                    "secret".to_string(),
+                )]),
+            },
+        )]);
+
+        // The environment's own endpoint gets only the environment's headers
+        let headers =
+            resolve_graphql_headers(None, &config_headers, "staging", &environments).unwrap();
+        assert_eq!(
+            headers,
+            GraphQLHeaders::from([("x-api-key".to_string(), "secret".to_string())])
+        );
+
+        // The [config] endpoint gets only the [config] headers
+        let headers = resolve_graphql_headers(
+            Some("https://other.example.com/graphql"),
+            &config_headers,
+            "staging",
+            &environments,
+        )
+        .unwrap();
+        assert_eq!(
+            headers,
+            GraphQLHeaders::from([("user-agent".to_string(), "app".to_string())])
+        );
+
+        // Default endpoints get nothing
+        let headers =
+            resolve_graphql_headers(None, &config_headers, "mainnet", &environments).unwrap();
+        assert!(headers.is_empty());
+
+        // ...even if the environment has headers but no endpoint of its own
+        let environments = Environments::from([(
+            "testnet".to_string(),
+            Environment {
+                chain_id: None,
+                graphql: None,
+                graphql_headers: GraphQLHeaders::from([(
+                    "X-Api-Key".to_string(),
+                    "secret".to_string(),
+                )]),
+            },
+        )]);
+        let headers =
+            resolve_graphql_headers(None, &config_headers, "testnet", &environments).unwrap();
+        assert!(headers.is_empty());
+    }
+}
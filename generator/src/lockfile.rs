@@ -0,0 +1,304 @@
+//! `gen.lock`: pins on-chain data resolved during model building.
+//!
+//! For each environment the lock records the chain id, the `published_at` map
+//! and the type origin / version tables fetched from GraphQL for published
+//! packages. Subsequent runs reuse the locked tables instead of querying the
+//! chain, so two runs a week apart produce byte-identical output.
+//!
+//! Only data fetched from the chain is locked. Self-origins of unpublished
+//! packages are derived from the compiled model on every run.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use move_core_types::account_address::AccountAddress;
+use serde::{Deserialize, Serialize};
+use sui_sdk::types::base_types::SequenceNumber;
+
+use crate::model_builder::{TypeOriginTable, VersionTable};
+
+/// File name of the lockfile, placed next to `gen.toml`.
+pub const LOCKFILE_NAME: &str = "gen.lock";
+
+/// Current lockfile format version.
+const LOCKFILE_VERSION: u32 = 1;
+
+const LOCKFILE_HEADER: &str = "# This file is automatically generated by sui-client-gen.\n\
+                               # It is not intended for manual editing.\n\n";
+
+/// Get the lockfile path for a manifest path (either `gen.toml` itself or its directory).
+pub fn lockfile_path(manifest_path: &Path) -> PathBuf {
+    if manifest_path.is_dir() {
+        manifest_path.join(LOCKFILE_NAME)
+    } else {
+        manifest_path
+            .parent()
+            .map(|p| p.join(LOCKFILE_NAME))
+            .unwrap_or_else(|| PathBuf::from(LOCKFILE_NAME))
+    }
+}
+
+/// Contents of `gen.lock`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenLock {
+    pub version: u32,
+    /// Locked data per environment name
+    #[serde(default)]
+    pub environments: BTreeMap<String, EnvLock>,
+}
+
+/// Locked on-chain data for a single environment.
+///
+/// Addresses are stored as hex literals so the file is readable and diffable.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct EnvLock {
+    pub chain_id: String,
+    /// Original package ID -> published-at ID
+    #[serde(default)]
+    pub published_at: BTreeMap<String, String>,
+    /// Original package ID -> (`module::Datatype` -> defining package ID)
+    #[serde(default)]
+    pub type_origins: BTreeMap<String, BTreeMap<String, String>>,
+    /// Original package ID -> (defining package ID -> version)
+    #[serde(default)]
+    pub versions: BTreeMap<String, BTreeMap<String, u64>>,
+}
+
+impl GenLock {
+    pub fn new(environments: BTreeMap<String, EnvLock>) -> Self {
+        Self {
+            version: LOCKFILE_VERSION,
+            environments,
+        }
+    }
+
+    /// Load the lockfile. Returns `None` if it doesn't exist.
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let lock = Self::parse(&content).with_context(|| format!("Invalid {}", path.display()))?;
+        Ok(Some(lock))
+    }
+
+    pub fn parse(content: &str) -> Result<Self> {
+        let lock: GenLock = toml::from_str(content)?;
+        if lock.version != LOCKFILE_VERSION {
+            bail!(
+                "Unsupported lockfile version {} (expected {}). Run with --update-lock to regenerate it.",
+                lock.version,
+                LOCKFILE_VERSION
+            );
+        }
+        Ok(lock)
+    }
+
+    pub fn to_toml(&self) -> Result<String> {
+        Ok(format!("{}{}", LOCKFILE_HEADER, toml::to_string_pretty(self)?))
+    }
+
+    /// Write the lockfile if its content changed. Returns whether it was written.
+    pub fn write_if_changed(&self, path: &Path) -> Result<bool> {
+        let content = self.to_toml()?;
+        if path.is_file() && std::fs::read_to_string(path)? == content {
+            return Ok(false);
+        }
+        std::fs::write(path, content)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(true)
+    }
+}
+
+impl EnvLock {
+    pub fn new(
+        chain_id: &str,
+        published_at: &BTreeMap<AccountAddress, AccountAddress>,
+        type_origin_table: &TypeOriginTable,
+        version_table: &VersionTable,
+    ) -> Self {
+        Self {
+            chain_id: chain_id.to_string(),
+            published_at: published_at
+                .iter()
+                .map(|(k, v)| (k.to_hex_literal(), v.to_hex_literal()))
+                .collect(),
+            type_origins: type_origin_table
+                .iter()
+                .map(|(pkg, origins)| {
+                    let origins = origins
+                        .iter()
+                        .map(|(ty, addr)| (ty.clone(), addr.to_hex_literal()))
+                        .collect();
+                    (pkg.to_hex_literal(), origins)
+                })
+                .collect(),
+            versions: version_table
+                .iter()
+                .map(|(pkg, versions)| {
+                    let versions = versions
+                        .iter()
+                        .map(|(addr, v)| (addr.to_hex_literal(), v.value()))
+                        .collect();
+                    (pkg.to_hex_literal(), versions)
+                })
+                .collect(),
+        }
+    }
+
+    /// Get the locked type origin and version tables for an environment.
+    ///
+    /// Fails if the lock doesn't match the freshly resolved chain id or
+    /// `published_at` map (e.g. after a package upgrade), since the locked
+    /// tables would then be stale.
+    pub fn tables_for(
+        &self,
+        env_name: &str,
+        chain_id: &str,
+        published_at: &BTreeMap<AccountAddress, AccountAddress>,
+    ) -> Result<(TypeOriginTable, VersionTable)> {
+        let out_of_date = |reason: String| {
+            anyhow::anyhow!(
+                "{} is out of date for environment '{}': {}. Run with --update-lock to refresh it.",
+                LOCKFILE_NAME,
+                env_name,
+                reason
+            )
+        };
+
+        if self.chain_id != chain_id {
+            return Err(out_of_date(format!(
+                "chain id changed from '{}' to '{}'",
+                self.chain_id, chain_id
+            )));
+        }
+
+        let locked_published_at = self
+            .published_at
+            .iter()
+            .map(|(k, v)| Ok((parse_address(k)?, parse_address(v)?)))
+            .collect::<Result<BTreeMap<_, _>>>()?;
+        if &locked_published_at != published_at {
+            let changed: Vec<String> = published_at
+                .keys()
+                .chain(locked_published_at.keys())
+                .filter(|k| published_at.get(k) != locked_published_at.get(k))
+                .map(|k| k.to_hex_literal())
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect();
+            return Err(out_of_date(format!(
+                "published addresses changed for packages [{}]",
+                changed.join(", ")
+            )));
+        }
+
+        let mut type_origin_table = TypeOriginTable::new();
+        for (pkg, origins) in &self.type_origins {
+            let origins = origins
+                .iter()
+                .map(|(ty, addr)| Ok((ty.clone(), parse_address(addr)?)))
+                .collect::<Result<BTreeMap<_, _>>>()?;
+            type_origin_table.insert(parse_address(pkg)?, origins);
+        }
+
+        let mut version_table = VersionTable::new();
+        for (pkg, versions) in &self.versions {
+            let versions = versions
+                .iter()
+                .map(|(addr, v)| Ok((parse_address(addr)?, SequenceNumber::from_u64(*v))))
+                .collect::<Result<BTreeMap<_, _>>>()?;
+            version_table.insert(parse_address(pkg)?, versions);
+        }
+
+        Ok((type_origin_table, version_table))
+    }
+}
+
+fn parse_address(s: &str) -> Result<AccountAddress> {
+    AccountAddress::from_hex_literal(s)
+        .with_context(|| format!("Invalid address '{}' in {}", s, LOCKFILE_NAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addr(s: &str) -> AccountAddress {
+        AccountAddress::from_hex_literal(s).unwrap()
+    }
+
+    fn sample_env_lock() -> EnvLock {
+        let published_at = BTreeMap::from([(addr("0x10"), addr("0x12"))]);
+        let type_origins = TypeOriginTable::from([(
+            addr("0x10"),
+            BTreeMap::from([
+                ("pool::Pool".to_string(), addr("0x10")),
+                ("pool::Receipt".to_string(), addr("0x12")),
+            ]),
+        )]);
+        let versions = VersionTable::from([(
+            addr("0x10"),
+            BTreeMap::from([
+                (addr("0x10"), SequenceNumber::from_u64(1)),
+                (addr("0x12"), SequenceNumber::from_u64(3)),
+            ]),
+        )]);
+        EnvLock::new("4c78adac", &published_at, &type_origins, &versions)
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let lock = GenLock::new(BTreeMap::from([("testnet".to_string(), sample_env_lock())]));
+        let content = lock.to_toml().unwrap();
+        assert!(content.starts_with("# This file is automatically generated"));
+        assert_eq!(GenLock::parse(&content).unwrap(), lock);
+    }
+
+    #[test]
+    fn test_tables_for() {
+        let env_lock = sample_env_lock();
+        let published_at = BTreeMap::from([(addr("0x10"), addr("0x12"))]);
+
+        let (type_origins, versions) = env_lock
+            .tables_for("testnet", "4c78adac", &published_at)
+            .unwrap();
+        assert_eq!(type_origins[&addr("0x10")]["pool::Receipt"], addr("0x12"));
+        assert_eq!(versions[&addr("0x10")][&addr("0x12")].value(), 3);
+    }
+
+    #[test]
+    fn test_tables_for_out_of_date() {
+        let env_lock = sample_env_lock();
+
+        let upgraded = BTreeMap::from([(addr("0x10"), addr("0x13"))]);
+        let err = env_lock
+            .tables_for("testnet", "4c78adac", &upgraded)
+            .unwrap_err();
+        assert!(err.to_string().contains("--update-lock"));
+        assert!(err.to_string().contains("0x10"));
+
+        let published_at = BTreeMap::from([(addr("0x10"), addr("0x12"))]);
+        let err = env_lock
+            .tables_for("testnet", "35834a8a", &published_at)
+            .unwrap_err();
+        assert!(err.to_string().contains("chain id changed"));
+    }
+
+    #[test]
+    fn test_unsupported_version() {
+        let err = GenLock::parse("version = 99\n").unwrap_err();
+        assert!(err.to_string().contains("Unsupported lockfile version"));
+    }
+
+    #[test]
+    fn test_lockfile_path() {
+        assert_eq!(
+            lockfile_path(Path::new("some/dir/gen.toml")),
+            PathBuf::from("some/dir/gen.lock")
+        );
+    }
+}
@@ -14,6 +14,9 @@ use clap::*;
     about = "Generate TS SDKs for Sui Move smart contracts."
 )]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     #[arg(
         short,
         long,
@@ -44,21 +47,70 @@ struct Args {
 
     #[arg(
         long,
-        help = "Remove all contents of the output directory before generating, except for gen.toml. Use with caution."
+        help = "Remove all contents of the output directory before generating, except for gen.toml and gen.lock. Use with caution."
     )]
     clean: bool,
+
+    #[arg(
+        long,
+        help = "Check that the output directory is up to date without writing anything. Prints a diff of out of date files and exits with a non-zero status if any differ.",
+        conflicts_with = "clean"
+    )]
+    check: bool,
+
+    #[arg(
+        long,
+        help = "Reuse the previously generated output of modules whose inputs (bytecode, doc comments, generator version and options) haven't changed since the last run."
+    )]
+    incremental: bool,
+
+    #[arg(
+        long,
+        help = "Directory for the persistent GraphQL cache. Defaults to `$XDG_CACHE_HOME/sui-client-gen` (or `~/.cache/sui-client-gen`)."
+    )]
+    cache_dir: Option<String>,
+
+    #[arg(
+        long,
+        help = "Don't make any GraphQL requests. Chain ids and type origins are served exclusively from the cache and any cache miss is an error."
+    )]
+    offline: bool,
+
+    #[arg(
+        long,
+        help = "Ignore the existing `gen.lock` and re-resolve type origins and published addresses from chain.",
+        conflicts_with = "check"
+    )]
+    update_lock: bool,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print a JSON Schema for gen.toml, for validation and autocompletion in editors.
+    Schema,
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let args = Args::parse();
 
+    if let Some(Command::Schema) = args.command {
+        let schema = sui_client_gen::schema::manifest_schema();
+        println!("{}", serde_json::to_string_pretty(&schema)?);
+        return Ok(());
+    }
+
     sui_client_gen::driver::run(sui_client_gen::driver::RunOptions {
         manifest_path: PathBuf::from(&args.manifest),
         out_dir: args.out.map(PathBuf::from),
         environment: args.environment,
         graphql: args.graphql,
         clean: args.clean,
+        check: args.check,
+        incremental: args.incremental,
+        cache_dir: args.cache_dir.map(PathBuf::from),
+        offline: args.offline,
+        update_lock: args.update_lock,
     })
     .await
 }
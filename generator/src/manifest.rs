@@ -4,14 +4,63 @@
 
 use anyhow::{bail, Context, Result};
 use std::collections::BTreeMap;
-use std::path::Path;
-
-use move_package_alt::schema::{DefaultDependency, ManifestDependencyInfo, PackageName};
+use std::path::{Path, PathBuf};
+
+use move_core_types::account_address::AccountAddress;
+use move_package_alt::schema::{
+    DefaultDependency, LocalDepInfo, ManifestDependencyInfo, PackageName,
+};
+
+use crate::ts_gen::is_reserved_word;
+
+pub const PACKAGES_NAME: &str = "packages";
+pub const CONFIG_NAME: &str = "config";
+pub const ENVIRONMENTS_NAME: &str = "environments";
+pub const DEP_REPLACEMENTS_PREFIX: &str = "dep-replacements";
+pub const FILTERS_NAME: &str = "filters";
+pub const DEP_FUNCTIONS_NAME: &str = "dep-functions";
+pub const NAMING_NAME: &str = "naming";
+pub const EXTENDS_NAME: &str = "extends";
+
+/// A top-level key of the manifest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Section {
+    Extends,
+    Config,
+    Packages,
+    Environments,
+    DepReplacements,
+    Filters,
+    DepFunctions,
+    Naming,
+}
 
-const PACKAGES_NAME: &str = "packages";
-const CONFIG_NAME: &str = "config";
-const ENVIRONMENTS_NAME: &str = "environments";
-const DEP_REPLACEMENTS_PREFIX: &str = "dep-replacements";
+impl Section {
+    /// Every section. Any other top-level key is an error.
+    pub const ALL: &'static [Section] = &[
+        Section::Extends,
+        Section::Config,
+        Section::Packages,
+        Section::Environments,
+        Section::DepReplacements,
+        Section::Filters,
+        Section::DepFunctions,
+        Section::Naming,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Section::Extends => EXTENDS_NAME,
+            Section::Config => CONFIG_NAME,
+            Section::Packages => PACKAGES_NAME,
+            Section::Environments => ENVIRONMENTS_NAME,
+            Section::DepReplacements => DEP_REPLACEMENTS_PREFIX,
+            Section::Filters => FILTERS_NAME,
+            Section::DepFunctions => DEP_FUNCTIONS_NAME,
+            Section::Naming => NAMING_NAME,
+        }
+    }
+}
 
 const MIGRATION_URL: &str = "https://github.com/kunalabs-io/sui-client-gen";
 
@@ -30,6 +79,9 @@ pub struct Environment {
     pub chain_id: Option<String>,
     /// GraphQL endpoint override. Optional.
     pub graphql: Option<String>,
+    /// Extra HTTP headers sent to this environment's GraphQL endpoint.
+    /// Values may reference environment variables as `${VAR}`.
+    pub graphql_headers: BTreeMap<String, String>,
 }
 
 /// Environments map - environment name to configuration.
@@ -60,6 +112,41 @@ pub struct DepReplacement {
 /// Uses DefaultDependency which includes override, rename-from, and modes flags.
 pub type Packages = BTreeMap<PackageName, DefaultDependency>;
 
+/// Filters limiting which modules and items of a package are generated.
+///
+/// Module filters are glob patterns matched against module names (e.g. `"pool"`, `"test_*"`).
+/// Item patterns are `module::item` globs (e.g. `"pool::*"`), optionally negated with a
+/// leading `!` (e.g. `"!*::test_*"`), matched against struct, enum and function names.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct PackageFilter {
+    /// Only generate modules matching one of these patterns. Empty means all modules.
+    pub include_modules: Vec<String>,
+    /// Skip modules matching any of these patterns.
+    pub exclude_modules: Vec<String>,
+    /// Item patterns, applied in order (the last matching pattern wins).
+    pub items: Vec<String>,
+}
+
+/// Package filters - maps package (by name or address, like in [naming]) to its filter.
+pub type Filters = BTreeMap<String, PackageFilter>;
+
+/// Dependencies to generate functions.ts for - maps the dependency (by name or address,
+/// like in [naming]) to glob patterns of the modules to generate it for.
+pub type DepFunctions = BTreeMap<String, Vec<String>>;
+
+/// Naming overrides from the `[naming]` section.
+///
+/// Packages are referred to by name (as in [packages], or the dependency's own package
+/// name) or by address, see `layout::resolve_package_key`. Addresses are normalized to
+/// their short hex literal (e.g. `0x2`).
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Naming {
+    /// Output folder names, keyed by package.
+    pub folders: BTreeMap<String, String>,
+    /// TypeScript names of structs, enums and functions, keyed by `package::module::name`.
+    pub identifiers: BTreeMap<String, String>,
+}
+
 /// Configuration for the code generator.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Config {
@@ -69,6 +156,21 @@ pub struct Config {
     pub environment: String,
     /// Output directory for generated code. Optional, can be specified via CLI.
     pub output: Option<String>,
+    /// Max number of packages per batched GraphQL type origins query. Optional.
+    pub graphql_batch_size: Option<usize>,
+    /// Per-request GraphQL timeout in seconds. Optional.
+    pub graphql_timeout_secs: Option<u64>,
+    /// Max number of retries for failed GraphQL requests. Optional.
+    pub graphql_max_retries: Option<u32>,
+    /// Extra HTTP headers sent to the [config] `graphql` endpoint, and only to it.
+    /// Values may reference environment variables as `${VAR}`.
+    pub graphql_headers: BTreeMap<String, String>,
+    /// Generate bindings for all functions, including private and `public(package)`
+    /// ones that can't be called from a transaction (e.g. for test harnesses).
+    pub all_functions: bool,
+    /// Generate runtime validators for the JSON representations of structs and enums,
+    /// checked in `fromJSON`.
+    pub json_validators: bool,
 }
 
 /// The parsed generator manifest (gen.toml).
@@ -82,6 +184,430 @@ pub struct GenManifest {
     pub environments: Environments,
     /// Environment-scoped dependency replacements. Optional.
     pub dep_replacements: DepReplacements,
+    /// Per-package module and item filters. Optional.
+    pub filters: Filters,
+    /// Dependencies to generate function bindings for. Optional.
+    pub dep_functions: DepFunctions,
+    /// Output folder and identifier naming overrides. Optional.
+    pub naming: Naming,
+}
+
+/// Value type of a manifest field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldKind {
+    String,
+    Bool,
+    /// An integer of at least the given value.
+    Integer(i64),
+    /// A list of strings.
+    StringList,
+    /// A table with string values.
+    StringTable,
+}
+
+/// A field of a table in the manifest.
+///
+/// The parser rejects fields that aren't defined, and the JSON Schema printed by
+/// `sui-client-gen schema` is built from the same definitions (see `schema.rs`).
+#[derive(Debug, Clone, Copy)]
+pub struct Field {
+    pub name: &'static str,
+    pub kind: FieldKind,
+    pub required: bool,
+    pub description: &'static str,
+}
+
+const fn field(name: &'static str, kind: FieldKind, description: &'static str) -> Field {
+    Field {
+        name,
+        kind,
+        required: false,
+        description,
+    }
+}
+
+const fn required_field(name: &'static str, kind: FieldKind, description: &'static str) -> Field {
+    Field {
+        name,
+        kind,
+        required: true,
+        description,
+    }
+}
+
+const CONFIG_ENVIRONMENT: Field = required_field(
+    "environment",
+    FieldKind::String,
+    "Environment to generate for. Must exist in [environments] or be a default (mainnet/testnet).",
+);
+const CONFIG_GRAPHQL: Field = field(
+    "graphql",
+    FieldKind::String,
+    "GraphQL endpoint URL override, takes precedence over the environment's graphql.",
+);
+const CONFIG_OUTPUT: Field = field(
+    "output",
+    FieldKind::String,
+    "Output directory for generated code.",
+);
+const CONFIG_GRAPHQL_BATCH_SIZE: Field = field(
+    "graphql-batch-size",
+    FieldKind::Integer(1),
+    "Max number of packages per batched GraphQL type origins query.",
+);
+const CONFIG_GRAPHQL_TIMEOUT: Field = field(
+    "graphql-timeout",
+    FieldKind::Integer(1),
+    "Per-request GraphQL timeout in seconds.",
+);
+const CONFIG_GRAPHQL_RETRIES: Field = field(
+    "graphql-retries",
+    FieldKind::Integer(0),
+    "Max number of retries for failed GraphQL requests.",
+);
+const CONFIG_GRAPHQL_HEADERS: Field = field(
+    "graphql-headers",
+    FieldKind::StringTable,
+    "Extra HTTP headers sent to the [config] graphql endpoint. Values may reference environment variables as ${VAR}.",
+);
+const CONFIG_ALL_FUNCTIONS: Field = field(
+    "all-functions",
+    FieldKind::Bool,
+    "Also generate bindings for private and public(package) functions.",
+);
+const CONFIG_JSON_VALIDATORS: Field = field(
+    "json-validators",
+    FieldKind::Bool,
+    "Generate validators for the JSON representations of structs and enums, checked in fromJSON.",
+);
+
+/// Fields of the [config] section.
+pub const CONFIG_FIELDS: &[Field] = &[
+    CONFIG_ENVIRONMENT,
+    CONFIG_GRAPHQL,
+    CONFIG_OUTPUT,
+    CONFIG_GRAPHQL_BATCH_SIZE,
+    CONFIG_GRAPHQL_TIMEOUT,
+    CONFIG_GRAPHQL_RETRIES,
+    CONFIG_GRAPHQL_HEADERS,
+    CONFIG_ALL_FUNCTIONS,
+    CONFIG_JSON_VALIDATORS,
+];
+
+const ENV_CHAIN_ID: Field = required_field("chain-id", FieldKind::String, "Chain identifier.");
+const ENV_GRAPHQL: Field = field("graphql", FieldKind::String, "GraphQL endpoint URL.");
+const ENV_GRAPHQL_HEADERS: Field = field(
+    "graphql-headers",
+    FieldKind::StringTable,
+    "Extra HTTP headers sent to this environment's GraphQL endpoint. Values may reference environment variables as ${VAR}.",
+);
+
+/// Fields of an environment in table form. `chain-id` is required for custom
+/// environments and not allowed for the default ones.
+pub const ENVIRONMENT_FIELDS: &[Field] = &[ENV_CHAIN_ID, ENV_GRAPHQL, ENV_GRAPHQL_HEADERS];
+
+/// A form of dependency specification, i.e. a variant of `move_package_alt`'s
+/// `ManifestDependencyInfo` (see `dependency_form`).
+#[derive(Debug, Clone, Copy)]
+pub struct DependencyForm {
+    pub description: &'static str,
+    pub fields: &'static [Field],
+}
+
+const LOCAL_DEPENDENCY: DependencyForm = DependencyForm {
+    description: "Package from a local directory",
+    fields: &[required_field(
+        "local",
+        FieldKind::String,
+        "Path to the package, relative to gen.toml.",
+    )],
+};
+
+const GIT_DEPENDENCY: DependencyForm = DependencyForm {
+    description: "Package from a git repository",
+    fields: &[
+        required_field("git", FieldKind::String, "URL of the repository."),
+        field("rev", FieldKind::String, "Branch, tag or commit."),
+        field(
+            "subdir",
+            FieldKind::String,
+            "Path to the package within the repository.",
+        ),
+    ],
+};
+
+const ON_CHAIN_DEPENDENCY: DependencyForm = DependencyForm {
+    description: "Package fetched from chain",
+    fields: &[required_field(
+        "on-chain",
+        FieldKind::Bool,
+        "Must be true. The package is looked up by name in the environment.",
+    )],
+};
+
+const EXTERNAL_DEPENDENCY: DependencyForm = DependencyForm {
+    description: "Package resolved by an external resolver, e.g. MVR",
+    fields: &[required_field(
+        "r",
+        FieldKind::StringTable,
+        "Resolver name to resolver data, e.g. { mvr = \"@potatoes/ascii\" }.",
+    )],
+};
+
+const SYSTEM_DEPENDENCY: DependencyForm = DependencyForm {
+    description: "System package bundled with the Sui toolchain",
+    fields: &[required_field(
+        "system",
+        FieldKind::String,
+        "Name of the system package, e.g. \"sui\" or \"std\".",
+    )],
+};
+
+/// Dependency forms accepted in [packages] and [dep-replacements.<env>].
+pub const DEPENDENCY_FORMS: &[DependencyForm] = &[
+    LOCAL_DEPENDENCY,
+    GIT_DEPENDENCY,
+    ON_CHAIN_DEPENDENCY,
+    EXTERNAL_DEPENDENCY,
+    SYSTEM_DEPENDENCY,
+];
+
+/// The form of a dependency parsed by `move_package_alt`. The match is exhaustive, so a new
+/// kind of dependency fails to compile until it has a form.
+fn dependency_form(info: &ManifestDependencyInfo) -> &'static DependencyForm {
+    match info {
+        ManifestDependencyInfo::Local(_) => &LOCAL_DEPENDENCY,
+        ManifestDependencyInfo::Git(_) => &GIT_DEPENDENCY,
+        ManifestDependencyInfo::OnChain(_) => &ON_CHAIN_DEPENDENCY,
+        ManifestDependencyInfo::External(_) => &EXTERNAL_DEPENDENCY,
+        ManifestDependencyInfo::System(_) => &SYSTEM_DEPENDENCY,
+    }
+}
+
+/// Fields accepted next to any dependency form in [packages].
+pub const DEPENDENCY_FLAGS: &[Field] = &[
+    field(
+        "override",
+        FieldKind::Bool,
+        "Override the versions of this package required by other dependencies.",
+    ),
+    field(
+        "rename-from",
+        FieldKind::String,
+        "Name of the package in its own Move.toml, if different.",
+    ),
+    field(
+        "modes",
+        FieldKind::StringList,
+        "Only include the dependency in these build modes.",
+    ),
+];
+
+const REPLACEMENT_PUBLISHED_AT: Field = field(
+    "published-at",
+    FieldKind::String,
+    "Override the published-at address.",
+);
+const REPLACEMENT_ORIGINAL_ID: Field = field(
+    "original-id",
+    FieldKind::String,
+    "Override the original-id address.",
+);
+const REPLACEMENT_USE_ENVIRONMENT: Field = field(
+    "use-environment",
+    FieldKind::String,
+    "Use a different environment in the dependency's namespace.",
+);
+const REPLACEMENT_RENAME_FROM: Field = field(
+    "rename-from",
+    FieldKind::String,
+    "Rename the package from this name.",
+);
+const REPLACEMENT_OVERRIDE: Field =
+    field("override", FieldKind::Bool, "Mark as override dependency.");
+
+/// Fields of a dependency replacement, next to an optional dependency form.
+pub const DEP_REPLACEMENT_FIELDS: &[Field] = &[
+    REPLACEMENT_PUBLISHED_AT,
+    REPLACEMENT_ORIGINAL_ID,
+    REPLACEMENT_USE_ENVIRONMENT,
+    REPLACEMENT_RENAME_FROM,
+    REPLACEMENT_OVERRIDE,
+];
+
+const FILTER_INCLUDE_MODULES: Field = field(
+    "include-modules",
+    FieldKind::StringList,
+    "Only generate modules matching one of these globs.",
+);
+const FILTER_EXCLUDE_MODULES: Field = field(
+    "exclude-modules",
+    FieldKind::StringList,
+    "Skip modules matching any of these globs.",
+);
+const FILTER_ITEMS: Field = field(
+    "items",
+    FieldKind::StringList,
+    "Struct, enum and function patterns ('module::item' globs, '!' to exclude), the last match wins.",
+);
+
+/// Fields of a [filters.<package>] section.
+pub const PACKAGE_FILTER_FIELDS: &[Field] =
+    &[FILTER_INCLUDE_MODULES, FILTER_EXCLUDE_MODULES, FILTER_ITEMS];
+
+const NAMING_FOLDERS: Field = field(
+    "folders",
+    FieldKind::StringTable,
+    "Output folder names, keyed by package name or address.",
+);
+const NAMING_IDENTIFIERS: Field = field(
+    "identifiers",
+    FieldKind::StringTable,
+    "TypeScript names of structs, enums and functions, keyed by 'package::module::name'.",
+);
+
+/// Fields of the [naming] section.
+pub const NAMING_FIELDS: &[Field] = &[NAMING_FOLDERS, NAMING_IDENTIFIERS];
+
+impl Field {
+    /// The value of this field in `table`, checked against the field's kind.
+    fn get<'a>(
+        &self,
+        table: &'a toml::map::Map<String, toml::Value>,
+    ) -> Result<Option<&'a toml::Value>> {
+        let Some(value) = table.get(self.name) else {
+            return Ok(None);
+        };
+        match (self.kind, value) {
+            (FieldKind::String, toml::Value::String(_))
+            | (FieldKind::Bool, toml::Value::Boolean(_)) => {}
+            (FieldKind::Integer(minimum), toml::Value::Integer(n)) if *n >= minimum => {}
+            (FieldKind::StringList, toml::Value::Array(values))
+                if values.iter().all(|v| v.is_str()) => {}
+            (FieldKind::StringTable, toml::Value::Table(entries)) => {
+                if let Some((key, v)) = entries.iter().find(|(_, v)| !v.is_str()) {
+                    bail!(
+                        "Invalid '{}.{}': expected a string, got {}",
+                        self.name,
+                        key,
+                        v
+                    );
+                }
+            }
+            _ => bail!(
+                "Invalid '{}': expected {}, got {}",
+                self.name,
+                self.kind.expected(),
+                value
+            ),
+        }
+        Ok(Some(value))
+    }
+
+    fn string(&self, table: &toml::map::Map<String, toml::Value>) -> Result<Option<String>> {
+        Ok(self.get(table)?.and_then(|v| v.as_str()).map(String::from))
+    }
+
+    /// A missing boolean field is false.
+    fn bool(&self, table: &toml::map::Map<String, toml::Value>) -> Result<bool> {
+        Ok(self.get(table)?.and_then(|v| v.as_bool()).unwrap_or(false))
+    }
+
+    fn integer(&self, table: &toml::map::Map<String, toml::Value>) -> Result<Option<i64>> {
+        Ok(self.get(table)?.and_then(|v| v.as_integer()))
+    }
+
+    fn string_list(&self, table: &toml::map::Map<String, toml::Value>) -> Result<Vec<String>> {
+        let values = self.get(table)?.and_then(|v| v.as_array());
+        Ok(values
+            .into_iter()
+            .flatten()
+            .filter_map(|v| v.as_str().map(String::from))
+            .collect())
+    }
+
+    fn string_table(
+        &self,
+        table: &toml::map::Map<String, toml::Value>,
+    ) -> Result<Vec<(String, String)>> {
+        let entries = self.get(table)?.and_then(|v| v.as_table());
+        Ok(entries
+            .into_iter()
+            .flatten()
+            .filter_map(|(k, v)| v.as_str().map(|s| (k.clone(), s.to_string())))
+            .collect())
+    }
+}
+
+impl FieldKind {
+    fn expected(self) -> String {
+        match self {
+            FieldKind::String => "a string".to_string(),
+            FieldKind::Bool => "a boolean".to_string(),
+            FieldKind::Integer(0) => "a non-negative integer".to_string(),
+            FieldKind::Integer(1) => "a positive integer".to_string(),
+            FieldKind::Integer(minimum) => format!("an integer of at least {}", minimum),
+            FieldKind::StringList => "a list of strings".to_string(),
+            FieldKind::StringTable => "a table of strings".to_string(),
+        }
+    }
+}
+
+/// Fail on keys of `table` that aren't one of `names`, e.g. typos like `all_functions`.
+fn check_keys<'a>(
+    table: &toml::map::Map<String, toml::Value>,
+    names: impl IntoIterator<Item = &'a str> + Clone,
+    what: &str,
+) -> Result<()> {
+    let Some(key) = table
+        .keys()
+        .find(|key| !names.clone().into_iter().any(|name| name == key.as_str()))
+    else {
+        return Ok(());
+    };
+
+    let names: Vec<String> = names
+        .into_iter()
+        .map(|name| format!("'{}'", name))
+        .collect();
+    let expected = match names.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} or {}", rest.join(", "), last),
+        _ => names.join(""),
+    };
+    bail!("Unknown {} '{}'. Expected {}", what, key, expected);
+}
+
+/// Fail on fields of `table` that aren't in `fields`.
+fn check_fields(table: &toml::map::Map<String, toml::Value>, fields: &[Field]) -> Result<()> {
+    check_keys(table, fields.iter().map(|f| f.name), "field")
+}
+
+/// Fail on fields of a dependency table that aren't part of a dependency form or `extra`.
+fn check_dependency_fields(
+    table: &toml::map::Map<String, toml::Value>,
+    extra: &[Field],
+) -> Result<()> {
+    let names = DEPENDENCY_FORMS
+        .iter()
+        .flat_map(|form| form.fields)
+        .chain(extra)
+        .map(|f| f.name);
+    check_keys(table, names, "field")
+}
+
+/// Fail on fields of a dependency table that aren't part of the form `move_package_alt`
+/// parsed it as, e.g. `rev` next to `local`.
+fn check_dependency_form(
+    table: &toml::map::Map<String, toml::Value>,
+    info: &ManifestDependencyInfo,
+    extra: &[Field],
+) -> Result<()> {
+    let names = dependency_form(info)
+        .fields
+        .iter()
+        .chain(extra)
+        .map(|f| f.name);
+    check_keys(table, names, "field")
 }
 
 /// Detects if the manifest uses the old format and returns an error with migration instructions.
@@ -126,17 +652,178 @@ fn detect_old_manifest_format(table: &toml::map::Map<String, toml::Value>) -> Re
     Ok(())
 }
 
+/// The sections of a single manifest file, before `extends` is resolved.
+#[derive(Default)]
+struct ManifestSections {
+    extends: Option<String>,
+    config: Option<Config>,
+    packages: Option<Packages>,
+    environments: Environments,
+    dep_replacements: DepReplacements,
+    filters: Option<Filters>,
+    dep_functions: Option<DepFunctions>,
+    naming: Option<Naming>,
+}
+
+impl ManifestSections {
+    /// Merge these sections over the ones of the manifest they extend.
+    ///
+    /// `[packages]`, `[environments]` and `[dep-replacements.<env>]` are merged entry by
+    /// entry, with entries of the same name replacing the base's. Other sections replace
+    /// the base's section as a whole.
+    fn merge_onto(self, base: ManifestSections) -> ManifestSections {
+        let packages = match (base.packages, self.packages) {
+            (Some(mut packages), Some(overrides)) => {
+                packages.extend(overrides);
+                Some(packages)
+            }
+            (base, packages) => packages.or(base),
+        };
+
+        let mut environments = base.environments;
+        environments.extend(self.environments);
+
+        let mut dep_replacements = base.dep_replacements;
+        for (env_name, replacements) in self.dep_replacements {
+            dep_replacements
+                .entry(env_name)
+                .or_default()
+                .extend(replacements);
+        }
+
+        ManifestSections {
+            extends: None,
+            config: self.config.or(base.config),
+            packages,
+            environments,
+            dep_replacements,
+            filters: self.filters.or(base.filters),
+            dep_functions: self.dep_functions.or(base.dep_functions),
+            naming: self.naming.or(base.naming),
+        }
+    }
+
+    /// Make the local dependency paths relative to the directory of the extending manifest,
+    /// given the directory of this manifest relative to it.
+    fn rebase_local_paths(&mut self, dir: &Path) {
+        let rebase = |dep: &mut ManifestDependencyInfo| {
+            if let ManifestDependencyInfo::Local(LocalDepInfo { local }) = dep {
+                *local = dir.join(&*local);
+            }
+        };
+        for dep in self.packages.iter_mut().flat_map(|pkgs| pkgs.values_mut()) {
+            rebase(&mut dep.dependency_info);
+        }
+        for replacements in self.dep_replacements.values_mut() {
+            for replacement in replacements.values_mut() {
+                if let Some(dep) = &mut replacement.dependency {
+                    rebase(dep);
+                }
+            }
+        }
+    }
+
+    /// Check that the (merged) manifest is complete and consistent.
+    fn finish(self) -> Result<GenManifest> {
+        let Some(packages) = self.packages else {
+            bail!("Missing [packages] section in manifest")
+        };
+        let Some(config) = self.config else {
+            bail!("Missing [config] section in manifest")
+        };
+
+        // Validate: config.environment must exist in environments or be a default
+        let env_name = &config.environment;
+        if !is_default_environment(env_name) && !self.environments.contains_key(env_name) {
+            bail!(
+                "Environment '{}' specified in [config] not found. \
+                 It must be defined in [environments] or be a default environment (mainnet, testnet).",
+                env_name
+            );
+        }
+
+        Ok(GenManifest {
+            config,
+            packages,
+            environments: self.environments,
+            dep_replacements: self.dep_replacements,
+            filters: self.filters.unwrap_or_default(),
+            dep_functions: self.dep_functions.unwrap_or_default(),
+            naming: self.naming.unwrap_or_default(),
+        })
+    }
+}
+
+/// Parse a manifest file, merged with the manifests it `extends`.
+///
+/// `extends` paths are relative to the manifest containing them, as are local
+/// dependency paths, which are rebased onto the directory of the given manifest.
 pub fn parse_gen_manifest_from_file(path: &Path) -> Result<GenManifest> {
-    let file_contents = if path.is_file() {
-        std::fs::read_to_string(path)
+    let path = if path.is_file() {
+        path.to_path_buf()
     } else {
-        std::fs::read_to_string(path.join(Path::new("gen.toml")))
+        path.join(Path::new("gen.toml"))
+    };
+    let sections = parse_manifest_file(&path, &mut vec![])?;
+    sections
+        .finish()
+        .with_context(|| format!("Error in manifest {}", path.display()))
+}
+
+/// Parse a single manifest file and the chain of manifests it extends. `chain` holds the
+/// canonical paths of the extending manifests, to detect cycles.
+fn parse_manifest_file(path: &Path, chain: &mut Vec<PathBuf>) -> Result<ManifestSections> {
+    let canonical_path = std::fs::canonicalize(path)
+        .with_context(|| format!("Unable to find generator manifest at {:?}", path))?;
+    if chain.contains(&canonical_path) {
+        bail!(
+            "Manifest {} extends itself (through {})",
+            path.display(),
+            chain
+                .iter()
+                .map(|p| p.display().to_string())
+                .collect::<Vec<_>>()
+                .join(" -> ")
+        );
     }
-    .with_context(|| format!("Unable to find generator manifest at {:?}", path))?;
-    parse_gen_manifest(&file_contents)
+
+    let file_contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Unable to find generator manifest at {:?}", path))?;
+    let mut sections = parse_manifest_sections(&file_contents)
+        .with_context(|| format!("Error in manifest {}", path.display()))?;
+    let Some(extends) = sections.extends.take() else {
+        return Ok(sections);
+    };
+
+    chain.push(canonical_path);
+    let base_path = path.parent().unwrap_or(Path::new("")).join(&extends);
+    let mut base = parse_manifest_file(&base_path, chain).with_context(|| {
+        format!(
+            "Error in manifest '{}' extended by {}",
+            extends,
+            path.display()
+        )
+    })?;
+    chain.pop();
+
+    base.rebase_local_paths(Path::new(&extends).parent().unwrap_or(Path::new("")));
+    Ok(sections.merge_onto(base))
 }
 
+/// Parse a manifest from a string. Manifests read this way can't use `extends`,
+/// as there's no file to resolve its path against.
 pub fn parse_gen_manifest(manifest_string: &str) -> Result<GenManifest> {
+    let sections = parse_manifest_sections(manifest_string)?;
+    if let Some(extends) = sections.extends {
+        bail!(
+            "Manifest extends '{}', which is only supported when reading it from a file",
+            extends
+        );
+    }
+    sections.finish()
+}
+
+fn parse_manifest_sections(manifest_string: &str) -> Result<ManifestSections> {
     let tval: toml::Value =
         toml::from_str(manifest_string).context("Unable to parse generator manifest")?;
 
@@ -144,23 +831,29 @@ pub fn parse_gen_manifest(manifest_string: &str) -> Result<GenManifest> {
         toml::Value::Table(mut table) => {
             // Check for old manifest format before proceeding
             detect_old_manifest_format(&table)?;
+            check_keys(&table, Section::ALL.iter().map(|s| s.name()), "section")?;
 
-            if !table.contains_key(PACKAGES_NAME) {
-                bail!("Missing [packages] section in manifest")
-            };
+            // Expand ${VAR} references in string values
+            for (key, value) in table.iter_mut() {
+                interpolate_manifest_value(value, key)?;
+            }
 
-            if !table.contains_key(CONFIG_NAME) {
-                bail!("Missing [config] section in manifest")
+            let extends = match table.remove(EXTENDS_NAME) {
+                None => None,
+                Some(toml::Value::String(path)) => Some(path),
+                Some(x) => bail!(
+                    "Invalid 'extends': expected a path to a manifest, got {}",
+                    x.type_str()
+                ),
             };
 
-            let config = parse_config(table.remove(CONFIG_NAME).unwrap())?;
+            let config = table.remove(CONFIG_NAME).map(parse_config).transpose()?;
 
             let packages = table
                 .remove(PACKAGES_NAME)
                 .map(parse_packages)
                 .transpose()
-                .context("Error parsing '[packages]' section of manifest")?
-                .unwrap();
+                .context("Error parsing '[packages]' section of manifest")?;
 
             // Parse [environments] section
             let environments = table
@@ -180,21 +873,36 @@ pub fn parse_gen_manifest(manifest_string: &str) -> Result<GenManifest> {
                 .context("Error parsing '[dep-replacements]' sections of manifest")?
                 .unwrap_or_default();
 
-            // Validate: config.environment must exist in environments or be a default
-            let env_name = &config.environment;
-            if !is_default_environment(env_name) && !environments.contains_key(env_name) {
-                bail!(
-                    "Environment '{}' specified in [config] not found. \
-                     It must be defined in [environments] or be a default environment (mainnet, testnet).",
-                    env_name
-                );
-            }
+            // Parse [filters.<package>] sections
+            let filters = table
+                .remove(FILTERS_NAME)
+                .map(parse_filters)
+                .transpose()
+                .context("Error parsing '[filters]' sections of manifest")?;
+
+            // Parse [dep-functions] section
+            let dep_functions = table
+                .remove(DEP_FUNCTIONS_NAME)
+                .map(parse_dep_functions)
+                .transpose()
+                .context("Error parsing '[dep-functions]' section of manifest")?;
+
+            // Parse [naming.folders] and [naming.identifiers] sections
+            let naming = table
+                .remove(NAMING_NAME)
+                .map(parse_naming)
+                .transpose()
+                .context("Error parsing '[naming]' section of manifest")?;
 
-            Ok(GenManifest {
+            Ok(ManifestSections {
+                extends,
                 config,
                 packages,
                 environments,
                 dep_replacements,
+                filters,
+                dep_functions,
+                naming,
             })
         }
         x => {
@@ -207,29 +915,89 @@ pub fn parse_gen_manifest(manifest_string: &str) -> Result<GenManifest> {
     }
 }
 
+/// Expand `${VAR}` references in the string values of a manifest value, `key` being its
+/// dotted path. `graphql-headers` are skipped, they're expanded when the headers are used.
+fn interpolate_manifest_value(tval: &mut toml::Value, key: &str) -> Result<()> {
+    match tval {
+        toml::Value::String(s) => {
+            *s = interpolate_env_vars(s).with_context(|| format!("Invalid value of '{}'", key))?;
+        }
+        toml::Value::Array(values) => {
+            for value in values.iter_mut() {
+                interpolate_manifest_value(value, key)?;
+            }
+        }
+        toml::Value::Table(table) => {
+            for (name, value) in table.iter_mut() {
+                if name != CONFIG_GRAPHQL_HEADERS.name {
+                    interpolate_manifest_value(value, &format!("{}.{}", key, name))?;
+                }
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
 fn parse_config(tval: toml::Value) -> Result<Config> {
     match tval {
         toml::Value::Table(table) => {
-            let graphql = table
-                .get("graphql")
-                .and_then(|tval| tval.as_str())
-                .map(|s| s.to_string());
-
-            let environment = table
-                .get("environment")
-                .and_then(|tval| tval.as_str())
-                .map(|s| s.to_string())
-                .ok_or_else(|| anyhow::anyhow!("Missing required 'environment' field in [config]"))?;
-
-            let output = table
-                .get("output")
-                .and_then(|tval| tval.as_str())
-                .map(|s| s.to_string());
+            check_fields(&table, CONFIG_FIELDS).context("Invalid [config] section")?;
+
+            let graphql = CONFIG_GRAPHQL.string(&table)?;
+
+            let environment = CONFIG_ENVIRONMENT.string(&table)?.ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Missing required '{}' field in [config]",
+                    CONFIG_ENVIRONMENT.name
+                )
+            })?;
+
+            let output = CONFIG_OUTPUT.string(&table)?;
+
+            let graphql_batch_size = CONFIG_GRAPHQL_BATCH_SIZE
+                .integer(&table)?
+                .map(|n| n as usize);
+
+            let graphql_timeout_secs = CONFIG_GRAPHQL_TIMEOUT.integer(&table)?.map(|n| n as u64);
+
+            let graphql_max_retries = match CONFIG_GRAPHQL_RETRIES.integer(&table)? {
+                None => None,
+                Some(n) => match u32::try_from(n) {
+                    Ok(n) => Some(n),
+                    Err(_) => bail!(
+                        "Invalid '{}' in [config]: {} is too large",
+                        CONFIG_GRAPHQL_RETRIES.name,
+                        n
+                    ),
+                },
+            };
+
+            let graphql_headers: BTreeMap<String, String> = CONFIG_GRAPHQL_HEADERS
+                .string_table(&table)?
+                .into_iter()
+                .collect();
+            if !graphql_headers.is_empty() && graphql.is_none() {
+                bail!(
+                    "'graphql-headers' in [config] are only sent to the [config] 'graphql' endpoint, \
+                     which isn't set. Set them on the environment in [environments] instead"
+                );
+            }
+
+            let all_functions = CONFIG_ALL_FUNCTIONS.bool(&table)?;
+
+            let json_validators = CONFIG_JSON_VALIDATORS.bool(&table)?;
 
             Ok(Config {
                 graphql,
                 environment,
                 output,
+                graphql_batch_size,
+                graphql_timeout_secs,
+                graphql_max_retries,
+                graphql_headers,
+                all_functions,
+                json_validators,
             })
         }
         x => {
@@ -250,6 +1018,11 @@ fn parse_packages(tval: toml::Value) -> Result<Packages> {
                 let pkg_name_ident = PackageName::new(pkg_name.as_str())
                     .map_err(|e| anyhow::anyhow!("Invalid package name '{}': {}", pkg_name, e))?;
 
+                if let toml::Value::Table(t) = &dep_value {
+                    check_dependency_fields(t, DEPENDENCY_FLAGS)
+                        .with_context(|| format!("Error parsing dependency '{}'", pkg_name))?;
+                }
+
                 // Deserialize using move_package_alt's built-in deserializer
                 // DefaultDependency includes override, rename-from, and modes flags
                 let dep: DefaultDependency = dep_value
@@ -257,6 +1030,11 @@ fn parse_packages(tval: toml::Value) -> Result<Packages> {
                     .try_into()
                     .with_context(|| format!("Error parsing dependency '{}'", pkg_name))?;
 
+                if let toml::Value::Table(t) = &dep_value {
+                    check_dependency_form(t, &dep.dependency_info, DEPENDENCY_FLAGS)
+                        .with_context(|| format!("Error parsing dependency '{}'", pkg_name))?;
+                }
+
                 pkgs.insert(pkg_name_ident, dep);
             }
             Ok(pkgs)
@@ -271,6 +1049,187 @@ fn parse_packages(tval: toml::Value) -> Result<Packages> {
     }
 }
 
+/// Parse [filters.<package>] sections.
+fn parse_filters(tval: toml::Value) -> Result<Filters> {
+    match tval {
+        toml::Value::Table(table) => {
+            let mut filters = BTreeMap::new();
+            for (pkg_name, filter_value) in table.into_iter() {
+                let filter = parse_package_filter(filter_value)
+                    .with_context(|| format!("Error parsing filter for package '{}'", pkg_name))?;
+                filters.insert(pkg_name, filter);
+            }
+            Ok(filters)
+        }
+        x => {
+            bail!(
+                "Malformed section in manifest {}. Expected a table, but encountered a {}",
+                x,
+                x.type_str()
+            )
+        }
+    }
+}
+
+fn parse_package_filter(tval: toml::Value) -> Result<PackageFilter> {
+    let toml::Value::Table(table) = tval else {
+        bail!(
+            "Malformed filter {}. Expected a table, but encountered a {}",
+            tval,
+            tval.type_str()
+        );
+    };
+
+    check_fields(&table, PACKAGE_FILTER_FIELDS)?;
+
+    let include_modules = FILTER_INCLUDE_MODULES.string_list(&table)?;
+    let exclude_modules = FILTER_EXCLUDE_MODULES.string_list(&table)?;
+    let items = FILTER_ITEMS.string_list(&table)?;
+
+    for pattern in &items {
+        let (module, item) = pattern
+            .strip_prefix('!')
+            .unwrap_or(pattern)
+            .split_once("::")
+            .unwrap_or_default();
+        if module.is_empty() || item.is_empty() {
+            bail!(
+                "Invalid item pattern '{}': expected 'module::item', e.g. 'pool::*' or '!*::test_*'",
+                pattern
+            );
+        }
+    }
+
+    Ok(PackageFilter {
+        include_modules,
+        exclude_modules,
+        items,
+    })
+}
+
+/// Parse [dep-functions] section.
+/// Supports both `dep = true` (all modules) and `dep = ["module", "pattern_*"]`.
+fn parse_dep_functions(tval: toml::Value) -> Result<DepFunctions> {
+    match tval {
+        toml::Value::Table(table) => {
+            let mut dep_functions = BTreeMap::new();
+            for (pkg_name, value) in table.into_iter() {
+                let modules = match value {
+                    toml::Value::Boolean(true) => vec!["*".to_string()],
+                    toml::Value::Boolean(false) => vec![],
+                    x => parse_string_list(Some(x), &pkg_name).with_context(|| {
+                        format!(
+                            "Expected 'true' or a list of module names for dependency '{}'",
+                            pkg_name
+                        )
+                    })?,
+                };
+                dep_functions.insert(pkg_name, modules);
+            }
+            Ok(dep_functions)
+        }
+        x => {
+            bail!(
+                "Malformed section in manifest {}. Expected a table, but encountered a {}",
+                x,
+                x.type_str()
+            )
+        }
+    }
+}
+
+/// Parse [naming] section.
+fn parse_naming(tval: toml::Value) -> Result<Naming> {
+    let toml::Value::Table(table) = tval else {
+        bail!(
+            "Malformed section in manifest {}. Expected a table, but encountered a {}",
+            tval,
+            tval.type_str()
+        );
+    };
+
+    check_fields(&table, NAMING_FIELDS)?;
+
+    let mut folders = BTreeMap::new();
+    for (pkg, folder) in NAMING_FOLDERS.string_table(&table)? {
+        if folder.is_empty()
+            || folder.starts_with(['_', '.'])
+            || !folder
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        {
+            bail!(
+                "Invalid folder name '{}' for package '{}': expected letters, digits, '-', '_' \
+                 or '.', not starting with '_' or '.'",
+                folder,
+                pkg
+            );
+        }
+        if let Some((other, _)) = folders.iter().find(|(_, f)| **f == folder) {
+            bail!(
+                "Folder name '{}' is used for both packages '{}' and '{}'",
+                folder,
+                other,
+                pkg
+            );
+        }
+        folders.insert(normalize_package_key(&pkg)?, folder);
+    }
+
+    let mut identifiers = BTreeMap::new();
+    for (path, ident) in NAMING_IDENTIFIERS.string_table(&table)? {
+        let parts: Vec<&str> = path.split("::").collect();
+        let [pkg, module, name] = parts[..] else {
+            bail!(
+                "Invalid identifier path '{}': expected 'package::module::name'",
+                path
+            );
+        };
+        if !is_ts_identifier(&ident) || is_reserved_word(&ident) {
+            bail!("Invalid TypeScript identifier '{}' for '{}'", ident, path);
+        }
+        let key = format!("{}::{}::{}", normalize_package_key(pkg)?, module, name);
+        identifiers.insert(key, ident);
+    }
+
+    Ok(Naming {
+        folders,
+        identifiers,
+    })
+}
+
+/// Normalize a package reference: addresses to their short hex literal, names as-is.
+fn normalize_package_key(pkg: &str) -> Result<String> {
+    if !pkg.starts_with("0x") {
+        return Ok(pkg.to_string());
+    }
+    AccountAddress::from_hex_literal(pkg)
+        .map(|addr| addr.to_hex_literal())
+        .with_context(|| format!("Invalid package address '{}'", pkg))
+}
+
+fn is_ts_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+fn parse_string_list(tval: Option<toml::Value>, name: &str) -> Result<Vec<String>> {
+    match tval {
+        None => Ok(vec![]),
+        Some(toml::Value::Array(values)) => values
+            .into_iter()
+            .map(|v| match v {
+                toml::Value::String(s) => Ok(s),
+                x => bail!("Invalid '{}': expected a list of strings, got {}", name, x),
+            })
+            .collect(),
+        Some(x) => bail!("Invalid '{}': expected a list of strings, got {}", name, x),
+    }
+}
+
 /// Parse [environments] section.
 /// Supports both string shorthand (env = "chain_id") and table form (env = { chain-id = "...", graphql = "..." }).
 fn parse_environments(tval: toml::Value) -> Result<Environments> {
@@ -294,19 +1253,21 @@ fn parse_environments(tval: toml::Value) -> Result<Environments> {
                         Environment {
                             chain_id: Some(chain_id),
                             graphql: None,
+                            graphql_headers: BTreeMap::new(),
                         }
                     }
-                    // Table form: env = { chain-id = "...", graphql = "..." }
+                    // Table form: env = { chain-id = "...", graphql = "...", graphql-headers = { ... } }
                     toml::Value::Table(t) => {
-                        let chain_id = t
-                            .get("chain-id")
-                            .and_then(|v| v.as_str())
-                            .map(String::from);
+                        check_fields(&t, ENVIRONMENT_FIELDS)
+                            .with_context(|| format!("Invalid environment '{}'", env_name))?;
 
-                        let graphql = t
-                            .get("graphql")
-                            .and_then(|v| v.as_str())
-                            .map(String::from);
+                        let chain_id = ENV_CHAIN_ID
+                            .string(&t)
+                            .with_context(|| format!("Invalid environment '{}'", env_name))?;
+
+                        let graphql = ENV_GRAPHQL
+                            .string(&t)
+                            .with_context(|| format!("Invalid environment '{}'", env_name))?;
 
                         // Validation: default envs can't set chain-id
                         if is_default && chain_id.is_some() {
@@ -325,7 +1286,25 @@ fn parse_environments(tval: toml::Value) -> Result<Environments> {
                             );
                         }
 
-                        Environment { chain_id, graphql }
+                        let graphql_headers: BTreeMap<String, String> = ENV_GRAPHQL_HEADERS
+                            .string_table(&t)
+                            .with_context(|| format!("Invalid environment '{}'", env_name))?
+                            .into_iter()
+                            .collect();
+                        if !graphql_headers.is_empty() && graphql.is_none() {
+                            bail!(
+                                "'graphql-headers' of environment '{}' are only sent to its \
+                                 'graphql' endpoint, which isn't set. Set 'graphql' too, so they \
+                                 aren't sent to the default endpoint",
+                                env_name
+                            );
+                        }
+
+                        Environment {
+                            chain_id,
+                            graphql,
+                            graphql_headers,
+                        }
                     }
                     x => {
                         bail!(
@@ -350,6 +1329,31 @@ fn parse_environments(tval: toml::Value) -> Result<Environments> {
     }
 }
 
+/// Expand `${VAR}` references to environment variables in a string.
+///
+/// Fails if a referenced variable is not set, so secrets are never silently sent empty.
+pub fn interpolate_env_vars(value: &str) -> Result<String> {
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after
+            .find('}')
+            .ok_or_else(|| anyhow::anyhow!("Unterminated '${{' in '{}'", value))?;
+        let var_name = &after[..end];
+        if var_name.is_empty() {
+            bail!("Empty variable reference '${{}}' in '{}'", value);
+        }
+        let var_value = std::env::var(var_name)
+            .map_err(|_| anyhow::anyhow!("Environment variable '{}' is not set", var_name))?;
+        result.push_str(&var_value);
+        rest = &after[end + 1..];
+    }
+    result.push_str(rest);
+    Ok(result)
+}
+
 /// Parse all [dep-replacements.<env>] sections.
 /// The TOML parser represents [dep-replacements.env_name] as a nested table.
 fn parse_all_dep_replacements(tval: toml::Value) -> Result<DepReplacements> {
@@ -403,35 +1407,29 @@ fn parse_env_dep_replacements(tval: toml::Value) -> Result<BTreeMap<PackageName,
 
 fn parse_single_dep_replacement(tval: toml::Value) -> Result<DepReplacement> {
     match tval {
-        toml::Value::Table(mut table) => {
-            // Extract the special fields first
-            let published_at = table
-                .remove("published-at")
-                .and_then(|v| v.as_str().map(String::from));
-
-            let original_id = table
-                .remove("original-id")
-                .and_then(|v| v.as_str().map(String::from));
-
-            let use_environment = table
-                .remove("use-environment")
-                .and_then(|v| v.as_str().map(String::from));
-
-            let rename_from = table
-                .remove("rename-from")
-                .and_then(|v| v.as_str().map(String::from));
-
-            let is_override = table
-                .remove("override")
-                .and_then(|v| v.as_bool())
-                .unwrap_or(false);
-
-            // If there are remaining fields, try to parse them as a dependency source
-            let dependency = if !table.is_empty() {
-                let dep_value = toml::Value::Table(table);
-                let dep: ManifestDependencyInfo = dep_value
+        toml::Value::Table(table) => {
+            check_dependency_fields(&table, DEP_REPLACEMENT_FIELDS)?;
+
+            // Read the replacement fields, the rest is the dependency source
+            let published_at = REPLACEMENT_PUBLISHED_AT.string(&table)?;
+            let original_id = REPLACEMENT_ORIGINAL_ID.string(&table)?;
+            let use_environment = REPLACEMENT_USE_ENVIRONMENT.string(&table)?;
+            let rename_from = REPLACEMENT_RENAME_FROM.string(&table)?;
+            let is_override = REPLACEMENT_OVERRIDE.bool(&table)?;
+
+            let source: toml::map::Map<String, toml::Value> = table
+                .into_iter()
+                .filter(|(key, _)| {
+                    !DEP_REPLACEMENT_FIELDS
+                        .iter()
+                        .any(|f| f.name == key.as_str())
+                })
+                .collect();
+            let dependency = if !source.is_empty() {
+                let dep: ManifestDependencyInfo = toml::Value::Table(source.clone())
                     .try_into()
                     .context("Error parsing dependency source in dep-replacement")?;
+                check_dependency_form(&source, &dep, &[])?;
                 Some(dep)
             } else {
                 None
@@ -459,8 +1457,10 @@ fn parse_single_dep_replacement(tval: toml::Value) -> Result<DepReplacement> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use move_package_alt::schema::{LocalDepInfo, ManifestGitDependency};
-    use std::path::PathBuf;
+    use move_package_alt::schema::ManifestGitDependency;
+
+    /// Serializes tests that modify the process environment.
+    static ENV_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
 
     #[test]
     fn test_parse_gen_manifest() {
@@ -518,9 +1518,288 @@ mod tests {
         assert_eq!(act.config.environment, "mainnet");
         assert_eq!(act.config.graphql, None);
         assert_eq!(act.config.output, None);
+        assert_eq!(act.config.graphql_batch_size, None);
+        assert_eq!(act.config.graphql_timeout_secs, None);
+        assert_eq!(act.config.graphql_max_retries, None);
+        assert!(act.config.graphql_headers.is_empty());
+        assert!(!act.config.all_functions);
+        assert!(!act.config.json_validators);
         assert_eq!(act.packages.len(), 1);
     }
 
+    #[test]
+    fn test_parse_graphql_batch_size() {
+        let manifest_str = r#"
+        [config]
+        environment = "mainnet"
+        graphql-batch-size = 5
+
+        [packages]
+        mypackage = { local = "./my-package" }
+        "#;
+
+        let act = parse_gen_manifest(manifest_str).unwrap();
+        assert_eq!(act.config.graphql_batch_size, Some(5));
+
+        let manifest_str = r#"
+        [config]
+        environment = "mainnet"
+        graphql-batch-size = 0
+
+        [packages]
+        mypackage = { local = "./my-package" }
+        "#;
+
+        let result = parse_gen_manifest(manifest_str);
+        assert!(result.unwrap_err().to_string().contains("graphql-batch-size"));
+    }
+
+    #[test]
+    fn test_parse_all_functions() {
+        let manifest_str = r#"
+        [config]
+        environment = "mainnet"
+        all-functions = true
+
+        [packages]
+        mypackage = { local = "./my-package" }
+        "#;
+
+        let act = parse_gen_manifest(manifest_str).unwrap();
+        assert!(act.config.all_functions);
+
+        let manifest_str = r#"
+        [config]
+        environment = "mainnet"
+        all-functions = "yes"
+
+        [packages]
+        mypackage = { local = "./my-package" }
+        "#;
+
+        let result = parse_gen_manifest(manifest_str);
+        assert!(result.unwrap_err().to_string().contains("all-functions"));
+    }
+
+    #[test]
+    fn test_parse_json_validators() {
+        let manifest_str = r#"
+        [config]
+        environment = "mainnet"
+        json-validators = true
+
+        [packages]
+        mypackage = { local = "./my-package" }
+        "#;
+
+        let act = parse_gen_manifest(manifest_str).unwrap();
+        assert!(act.config.json_validators);
+
+        let manifest_str = r#"
+        [config]
+        environment = "mainnet"
+        json-validators = 1
+
+        [packages]
+        mypackage = { local = "./my-package" }
+        "#;
+
+        let result = parse_gen_manifest(manifest_str);
+        assert!(result.unwrap_err().to_string().contains("json-validators"));
+    }
+
+    #[test]
+    fn test_parse_filters() {
+        let manifest_str = r#"
+        [config]
+        environment = "mainnet"
+
+        [packages]
+        mypackage = { local = "./my-package" }
+
+        [filters.sui]
+        include-modules = ["coin", "balance"]
+        exclude-modules = ["test_*"]
+
+        [filters.mypackage]
+        items = ["pool::*", "!*::test_*"]
+        "#;
+
+        let act = parse_gen_manifest(manifest_str).unwrap();
+        assert_eq!(act.filters.len(), 2);
+        assert_eq!(
+            act.filters["sui"],
+            PackageFilter {
+                include_modules: vec!["coin".to_string(), "balance".to_string()],
+                exclude_modules: vec!["test_*".to_string()],
+                items: vec![],
+            }
+        );
+        assert_eq!(
+            act.filters["mypackage"].items,
+            vec!["pool::*".to_string(), "!*::test_*".to_string()]
+        );
+
+        // No filters by default
+        let manifest_str = r#"
+        [config]
+        environment = "mainnet"
+
+        [packages]
+        mypackage = { local = "./my-package" }
+        "#;
+        assert!(parse_gen_manifest(manifest_str).unwrap().filters.is_empty());
+    }
+
+    #[test]
+    fn test_parse_dep_functions() {
+        let manifest_str = r#"
+        [config]
+        environment = "mainnet"
+
+        [packages]
+        mypackage = { local = "./my-package" }
+
+        [dep-functions]
+        sui = ["coin", "pay"]
+        deepbook = true
+        std = false
+        "#;
+
+        let act = parse_gen_manifest(manifest_str).unwrap();
+        assert_eq!(act.dep_functions["sui"], vec!["coin", "pay"]);
+        assert_eq!(act.dep_functions["deepbook"], vec!["*"]);
+        assert!(act.dep_functions["std"].is_empty());
+
+        let manifest_str = r#"
+        [config]
+        environment = "mainnet"
+
+        [packages]
+        mypackage = { local = "./my-package" }
+
+        [dep-functions]
+        sui = "coin"
+        "#;
+
+        let err = parse_gen_manifest(manifest_str).unwrap_err();
+        assert!(format!("{:#}", err).contains("dependency 'sui'"));
+    }
+
+    #[test]
+    fn test_parse_naming() {
+        let manifest_str = r#"
+        [config]
+        environment = "mainnet"
+
+        [packages]
+        mypackage = { local = "./my-package" }
+
+        [naming.folders]
+        deepbook = "deepbook-v3"
+        "0x000000000000000000000000000000000000000000000000000000000000dee9" = "deepbook-v2"
+
+        [naming.identifiers]
+        "sui::coin::Coin" = "SuiCoin"
+        "0x2::pay::split" = "paySplit"
+        "#;
+
+        let act = parse_gen_manifest(manifest_str).unwrap();
+        assert_eq!(act.naming.folders["deepbook"], "deepbook-v3");
+        assert_eq!(act.naming.folders["0xdee9"], "deepbook-v2");
+        assert_eq!(act.naming.identifiers["sui::coin::Coin"], "SuiCoin");
+        assert_eq!(act.naming.identifiers["0x2::pay::split"], "paySplit");
+    }
+
+    #[test]
+    fn test_parse_naming_invalid() {
+        let parse = |naming: &str| {
+            let manifest_str = format!(
+                r#"
+                [config]
+                environment = "mainnet"
+
+                [packages]
+                mypackage = {{ local = "./my-package" }}
+
+                {}
+                "#,
+                naming
+            );
+            format!("{:#}", parse_gen_manifest(&manifest_str).unwrap_err())
+        };
+
+        assert!(parse("[naming.folders]\nsui = '_sui'").contains("Invalid folder name '_sui'"));
+        assert!(parse("[naming.folders]\nsui = 'a/b'").contains("Invalid folder name 'a/b'"));
+        assert!(parse("[naming.folders]\nsui = 'x'\nstd = 'x'").contains("used for both"));
+        assert!(parse("[naming.identifiers]\n'coin::Coin' = 'C'")
+            .contains("Invalid identifier path 'coin::Coin'"));
+        assert!(
+            parse("[naming.identifiers]\n'sui::coin::Coin' = 'Sui Coin'")
+                .contains("Invalid TypeScript identifier 'Sui Coin'")
+        );
+        assert!(parse("[naming.identifiers]\n'sui::coin::split' = 'delete'")
+            .contains("Invalid TypeScript identifier 'delete'"));
+        assert!(parse("[naming.identifiers]\n'0xzz::coin::Coin' = 'C'")
+            .contains("Invalid package address '0xzz'"));
+        assert!(parse("[naming.classes]\n").contains("Unknown field 'classes'"));
+    }
+
+    #[test]
+    fn test_parse_filters_invalid() {
+        let parse = |filter: &str| {
+            let manifest_str = format!(
+                r#"
+                [config]
+                environment = "mainnet"
+
+                [packages]
+                mypackage = {{ local = "./my-package" }}
+
+                [filters.sui]
+                {}
+                "#,
+                filter
+            );
+            format!("{:#}", parse_gen_manifest(&manifest_str).unwrap_err())
+        };
+
+        assert!(parse(r#"items = ["pool"]"#).contains("Invalid item pattern 'pool'"));
+        assert!(parse(r#"items = ["!::Pool"]"#).contains("Invalid item pattern '!::Pool'"));
+        assert!(parse(r#"include-modules = "coin""#).contains("include-modules"));
+        assert!(parse(r#"exclude-modules = [1]"#).contains("exclude-modules"));
+        assert!(parse(r#"modules = ["coin"]"#).contains("Unknown field 'modules'"));
+    }
+
+    #[test]
+    fn test_parse_graphql_timeout_and_retries() {
+        let manifest_str = r#"
+        [config]
+        environment = "mainnet"
+        graphql-timeout = 60
+        graphql-retries = 0
+
+        [packages]
+        mypackage = { local = "./my-package" }
+        "#;
+
+        let act = parse_gen_manifest(manifest_str).unwrap();
+        assert_eq!(act.config.graphql_timeout_secs, Some(60));
+        assert_eq!(act.config.graphql_max_retries, Some(0));
+
+        let manifest_str = r#"
+        [config]
+        environment = "mainnet"
+        graphql-retries = -1
+
+        [packages]
+        mypackage = { local = "./my-package" }
+        "#;
+
+        let result = parse_gen_manifest(manifest_str);
+        assert!(result.unwrap_err().to_string().contains("graphql-retries"));
+    }
+
     #[test]
     fn test_missing_environment_fails() {
         let manifest_str = r#"
@@ -701,6 +1980,468 @@ mod tests {
         assert_eq!(env.graphql, Some("https://my-testnet.example.com/graphql".to_string()));
     }
 
+    #[test]
+    fn test_parse_graphql_headers() {
+        let manifest_str = r#"
+        [config]
+        environment = "staging"
+        graphql = "https://provider.example.com/graphql"
+        graphql-headers = { User-Agent = "my-app" }
+
+        [packages]
+        mypackage = { local = "./my-package" }
+
+        [environments]
+        staging = { chain-id = "12345678", graphql = "https://provider.example.com/graphql", graphql-headers = { X-Api-Key = "${PROVIDER_API_KEY}" } }
+        "#;
+
+        let act = parse_gen_manifest(manifest_str).unwrap();
+        assert_eq!(act.config.graphql_headers["User-Agent"], "my-app");
+        let env = act.environments.get("staging").unwrap();
+        assert_eq!(env.graphql_headers["X-Api-Key"], "${PROVIDER_API_KEY}");
+
+        let manifest_str = r#"
+        [config]
+        environment = "mainnet"
+        graphql-headers = { X-Api-Key = 42 }
+
+        [packages]
+        mypackage = { local = "./my-package" }
+        "#;
+
+        let err = parse_gen_manifest(manifest_str).unwrap_err();
+        assert!(format!("{:#}", err).contains("X-Api-Key"));
+
+        // Environment headers without the environment's endpoint would go to the default one
+        let manifest_str = r#"
+        [config]
+        environment = "testnet"
+
+        [packages]
+        mypackage = { local = "./my-package" }
+
+        [environments]
+        testnet = { graphql-headers = { X-Api-Key = "${PROVIDER_API_KEY}" } }
+        "#;
+
+        let err = parse_gen_manifest(manifest_str).unwrap_err();
+        assert!(
+            format!("{:#}", err).contains("'graphql-headers' of environment 'testnet'"),
+            "{:#}",
+            err
+        );
+
+        // [config] headers without a [config] endpoint would go to any endpoint
+        let manifest_str = r#"
+        [config]
+        environment = "mainnet"
+        graphql-headers = { X-Api-Key = "secret" }
+
+        [packages]
+        mypackage = { local = "./my-package" }
+        "#;
+
+        let err = parse_gen_manifest(manifest_str).unwrap_err();
+        assert!(
+            format!("{:#}", err).contains("only sent to the [config] 'graphql' endpoint"),
+            "{:#}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_interpolate_env_vars() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        std::env::set_var("SUI_CLIENT_GEN_TEST_INTERPOLATE_KEY", "abc123");
+
+        assert_eq!(interpolate_env_vars("plain").unwrap(), "plain");
+        assert_eq!(
+            interpolate_env_vars("Bearer ${SUI_CLIENT_GEN_TEST_INTERPOLATE_KEY}").unwrap(),
+            "Bearer abc123"
+        );
+        assert_eq!(
+            interpolate_env_vars(
+                "${SUI_CLIENT_GEN_TEST_INTERPOLATE_KEY}-${SUI_CLIENT_GEN_TEST_INTERPOLATE_KEY}"
+            )
+            .unwrap(),
+            "abc123-abc123"
+        );
+
+        let err = interpolate_env_vars("${SUI_CLIENT_GEN_TEST_INTERPOLATE_UNSET}").unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("SUI_CLIENT_GEN_TEST_INTERPOLATE_UNSET"));
+        assert!(interpolate_env_vars("${UNTERMINATED").is_err());
+        assert!(interpolate_env_vars("${}").is_err());
+
+        std::env::remove_var("SUI_CLIENT_GEN_TEST_INTERPOLATE_KEY");
+    }
+
+    #[test]
+    fn test_parse_interpolated_values() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        std::env::set_var("SUI_CLIENT_GEN_TEST_PARSE_MOVE_DIR", "../move");
+        std::env::set_var("SUI_CLIENT_GEN_TEST_PARSE_HOST", "graphql.example.com");
+
+        let manifest_str = r#"
+        [config]
+        environment = "mainnet"
+        graphql = "https://${SUI_CLIENT_GEN_TEST_PARSE_HOST}/graphql"
+        graphql-headers = { X-Api-Key = "${PROVIDER_API_KEY}" }
+
+        [packages]
+        amm = { local = "${SUI_CLIENT_GEN_TEST_PARSE_MOVE_DIR}/amm" }
+        "#;
+
+        let act = parse_gen_manifest(manifest_str).unwrap();
+        assert_eq!(
+            act.config.graphql.as_deref(),
+            Some("https://graphql.example.com/graphql")
+        );
+        // Headers are expanded when used
+        assert_eq!(
+            act.config.graphql_headers["X-Api-Key"],
+            "${PROVIDER_API_KEY}"
+        );
+        let amm = act.packages.get(&PackageName::new("amm").unwrap()).unwrap();
+        assert!(
+            matches!(&amm.dependency_info, ManifestDependencyInfo::Local(LocalDepInfo { local }) if local == &PathBuf::from("../move/amm"))
+        );
+
+        let manifest_str = r#"
+        [config]
+        environment = "mainnet"
+
+        [packages]
+        amm = { local = "${SUI_CLIENT_GEN_TEST_PARSE_UNSET}/amm" }
+        "#;
+
+        let err = format!("{:#}", parse_gen_manifest(manifest_str).unwrap_err());
+        assert!(err.contains("packages.amm.local"), "{}", err);
+        assert!(err.contains("SUI_CLIENT_GEN_TEST_PARSE_UNSET"), "{}", err);
+
+        std::env::remove_var("SUI_CLIENT_GEN_TEST_PARSE_MOVE_DIR");
+        std::env::remove_var("SUI_CLIENT_GEN_TEST_PARSE_HOST");
+    }
+
+    #[test]
+    fn test_parse_manifest_extends() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("base")).unwrap();
+        std::fs::create_dir_all(dir.path().join("app")).unwrap();
+        std::fs::write(
+            dir.path().join("base/gen.toml"),
+            r#"
+            [config]
+            environment = "staging"
+
+            [packages]
+            amm = { local = "../move/amm" }
+            fixture = { local = "../move/fixture" }
+
+            [environments]
+            staging = "11111111"
+            devnet = "22222222"
+
+            [dep-replacements.staging]
+            sui = { published-at = "0x1234" }
+            "#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("app/gen.toml"),
+            r#"
+            extends = "../base/gen.toml"
+
+            [packages]
+            fixture = { local = "../fixture-fork" }
+
+            [environments]
+            staging = "33333333"
+
+            [dep-replacements.staging]
+            deepbook = { published-at = "0x5678" }
+            "#,
+        )
+        .unwrap();
+
+        let act = parse_gen_manifest_from_file(&dir.path().join("app")).unwrap();
+        assert_eq!(act.config.environment, "staging");
+
+        // Local paths of the base manifest are relative to the extending one
+        let amm = act.packages.get(&PackageName::new("amm").unwrap()).unwrap();
+        assert!(
+            matches!(&amm.dependency_info, ManifestDependencyInfo::Local(LocalDepInfo { local }) if local == &PathBuf::from("../base/../move/amm"))
+        );
+        let fixture = act
+            .packages
+            .get(&PackageName::new("fixture").unwrap())
+            .unwrap();
+        assert!(
+            matches!(&fixture.dependency_info, ManifestDependencyInfo::Local(LocalDepInfo { local }) if local == &PathBuf::from("../fixture-fork"))
+        );
+
+        assert_eq!(
+            act.environments["staging"].chain_id.as_deref(),
+            Some("33333333")
+        );
+        assert_eq!(
+            act.environments["devnet"].chain_id.as_deref(),
+            Some("22222222")
+        );
+        assert_eq!(act.dep_replacements["staging"].len(), 2);
+    }
+
+    #[test]
+    fn test_parse_manifest_extends_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let base_path = dir.path().join("base.toml");
+        let app_path = dir.path().join("gen.toml");
+
+        // Errors point at the manifest they're in
+        std::fs::write(&base_path, "[environments]\nstaging = 42\n").unwrap();
+        std::fs::write(&app_path, "extends = \"base.toml\"\n").unwrap();
+        let err = format!("{:#}", parse_gen_manifest_from_file(&app_path).unwrap_err());
+        assert!(err.contains(&base_path.display().to_string()), "{}", err);
+        assert!(err.contains("staging"), "{}", err);
+
+        std::fs::write(&base_path, "extends = \"missing.toml\"\n").unwrap();
+        let err = format!("{:#}", parse_gen_manifest_from_file(&app_path).unwrap_err());
+        assert!(err.contains("missing.toml"), "{}", err);
+
+        std::fs::write(&base_path, "extends = \"gen.toml\"\n").unwrap();
+        let err = format!("{:#}", parse_gen_manifest_from_file(&app_path).unwrap_err());
+        assert!(err.contains("extends itself"), "{}", err);
+
+        let err = parse_gen_manifest("extends = \"base.toml\"\n").unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("only supported when reading it from a file"));
+    }
+
+    #[test]
+    fn test_unknown_fields_fail() {
+        let parse = |s: &str| format!("{:#}", parse_gen_manifest(s).unwrap_err());
+
+        let err = parse(
+            r#"
+            [config]
+            environment = "mainnet"
+            all_functions = true
+
+            [packages]
+            mypackage = { local = "./my-package" }
+            "#,
+        );
+        assert!(err.contains("Unknown field 'all_functions'"), "{}", err);
+        assert!(err.contains("'all-functions'"), "{}", err);
+
+        let err = parse(
+            r#"
+            [config]
+            environment = "staging"
+
+            [packages]
+            mypackage = { local = "./my-package" }
+
+            [environments]
+            staging = { chain_id = "12345678" }
+            "#,
+        );
+        assert!(err.contains("Unknown field 'chain_id'"), "{}", err);
+
+        let err = parse(
+            r#"
+            [config]
+            environment = "mainnet"
+
+            [packages]
+            mypackage = { on_chain = true }
+            "#,
+        );
+        assert!(err.contains("Unknown field 'on_chain'"), "{}", err);
+        assert!(err.contains("'on-chain'"), "{}", err);
+
+        let err = parse(
+            r#"
+            [config]
+            environment = "mainnet"
+
+            [packages]
+            mypackage = { local = "./my-package" }
+
+            [dep-replacements.mainnet]
+            sui = { published_at = "0x1234" }
+            "#,
+        );
+        assert!(err.contains("Unknown field 'published_at'"), "{}", err);
+
+        let err = parse(
+            r#"
+            [config]
+            environment = "mainnet"
+
+            [packages]
+            mypackage = { local = "./my-package" }
+
+            [dep_replacements.mainnet]
+            sui = { published-at = "0x1234" }
+            "#,
+        );
+        assert!(
+            err.contains("Unknown section 'dep_replacements'"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_dependency_forms_parse() {
+        // Every field of every dependency form (and the flags) must be accepted by the parser
+        let sample = |field: &Field| match (field.name, field.kind) {
+            ("git", _) => r#""https://github.com/MystenLabs/sui.git""#.to_string(),
+            ("system", _) => r#""sui""#.to_string(),
+            ("r", _) => r#"{ mvr = "@potatoes/ascii" }"#.to_string(),
+            ("rename-from", _) => r#""my_package""#.to_string(),
+            (_, FieldKind::String) => r#""./my-package""#.to_string(),
+            (_, FieldKind::Bool) => "true".to_string(),
+            (_, FieldKind::StringList) => r#"["test"]"#.to_string(),
+            (name, kind) => panic!("no sample value for {} ({:?})", name, kind),
+        };
+
+        for form in DEPENDENCY_FORMS {
+            let fields: Vec<String> = form
+                .fields
+                .iter()
+                .chain(DEPENDENCY_FLAGS)
+                .map(|field| format!("{} = {}", field.name, sample(field)))
+                .collect();
+            let manifest_str = format!(
+                "[config]\nenvironment = \"mainnet\"\n\n[packages]\nmypackage = {{ {} }}\n",
+                fields.join(", ")
+            );
+            let act = parse_gen_manifest(&manifest_str)
+                .unwrap_or_else(|err| panic!("{}: {:#}\n{}", form.description, err, manifest_str));
+
+            // ...and parse into the dependency kind the form describes
+            let dep = &act.packages[&PackageName::new("mypackage").unwrap()];
+            assert_eq!(
+                dependency_form(&dep.dependency_info).description,
+                form.description
+            );
+        }
+    }
+
+    #[test]
+    fn test_dependency_form_fields() {
+        let parse = |packages: &str, replacements: &str| {
+            let manifest_str = format!(
+                "[config]\nenvironment = \"mainnet\"\n\n[packages]\n{}\n\n[dep-replacements.mainnet]\n{}\n",
+                packages, replacements
+            );
+            parse_gen_manifest(&manifest_str).map_err(|err| format!("{:#}", err))
+        };
+
+        // Fields of another form are rejected
+        let err = parse(
+            r#"mypackage = { local = "./my-package", rev = "main" }"#,
+            "",
+        )
+        .unwrap_err();
+        assert!(err.contains("Unknown field 'rev'"), "{}", err);
+
+        let err = parse(
+            r#"mypackage = { local = "./my-package" }"#,
+            r#"sui = { local = "../sui", subdir = "crates" }"#,
+        )
+        .unwrap_err();
+        assert!(err.contains("Unknown field 'subdir'"), "{}", err);
+
+        let act = parse(
+            r#"mypackage = { local = "./my-package" }"#,
+            r#"sui = { git = "https://github.com/MystenLabs/sui.git", subdir = "crates/sui-framework/packages/sui-framework", published-at = "0x2" }"#,
+        )
+        .unwrap();
+        let sui = &act.dep_replacements["mainnet"][&PackageName::new("sui").unwrap()];
+        assert!(matches!(
+            sui.dependency,
+            Some(ManifestDependencyInfo::Git(_))
+        ));
+        assert_eq!(sui.published_at.as_deref(), Some("0x2"));
+    }
+
+    #[test]
+    fn test_field_kinds_fail() {
+        let parse = |s: &str| format!("{:#}", parse_gen_manifest(s).unwrap_err());
+
+        let err = parse(
+            r#"
+            [config]
+            environment = "mainnet"
+            output = 1
+
+            [packages]
+            mypackage = { local = "./my-package" }
+            "#,
+        );
+        assert!(
+            err.contains("Invalid 'output': expected a string, got 1"),
+            "{}",
+            err
+        );
+
+        let err = parse(
+            r#"
+            [config]
+            environment = "staging"
+
+            [packages]
+            mypackage = { local = "./my-package" }
+
+            [environments]
+            staging = { chain-id = 12345678 }
+            "#,
+        );
+        assert!(err.contains("Invalid environment 'staging'"), "{}", err);
+        assert!(err.contains("Invalid 'chain-id'"), "{}", err);
+
+        let err = parse(
+            r#"
+            [config]
+            environment = "mainnet"
+
+            [packages]
+            mypackage = { local = "./my-package" }
+
+            [dep-replacements.mainnet]
+            sui = { published-at = 2 }
+            "#,
+        );
+        assert!(
+            err.contains("Invalid 'published-at': expected a string"),
+            "{}",
+            err
+        );
+
+        let err = parse(
+            r#"
+            [config]
+            environment = "mainnet"
+
+            [packages]
+            mypackage = { local = "./my-package" }
+
+            [naming.folders]
+            sui = 2
+            "#,
+        );
+        assert!(
+            err.contains("Invalid 'folders.sui': expected a string"),
+            "{}",
+            err
+        );
+    }
+
     #[test]
     fn test_default_env_with_chain_id_fails() {
         // String shorthand for default env should fail
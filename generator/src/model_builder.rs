@@ -8,7 +8,7 @@ use std::fs::{self, File};
 use std::io::Write;
 use std::path::Path;
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use move_compiler::editions::Flavor;
 use move_core_types::account_address::AccountAddress;
 use move_model_2::model::Model;
@@ -24,7 +24,8 @@ use sui_package_alt::SuiFlavor;
 use sui_sdk::types::base_types::SequenceNumber;
 use tempfile::TempDir;
 
-use crate::graphql::GraphQLClient;
+use crate::graphql::{GraphQLCache, GraphQLHeaders};
+use crate::lockfile::EnvLock;
 use crate::manifest::{DepReplacement, DepReplacements, Environments, Packages, is_default_environment};
 
 const STUB_PACKAGE_NAME: &str = "SuiClientGenRootPackageStub";
@@ -49,6 +50,8 @@ pub struct ModelResult {
     pub version_table: VersionTable,
     /// Set of top-level package names (from gen.toml)
     pub top_level_packages: BTreeSet<PackageName>,
+    /// On-chain data resolved for this environment, to be recorded in gen.lock
+    pub lock: EnvLock,
 }
 
 /// Build a Move model from packages using the move_package_alt system.
@@ -60,7 +63,12 @@ pub struct ModelResult {
 /// * `chain_id` - The chain ID for this environment
 /// * `environments` - Custom environments from gen.toml (for stub Move.toml)
 /// * `dep_replacements` - Environment-scoped dep replacements from gen.toml
-/// * `graphql_client` - GraphQL client for type origin queries
+/// * `graphql_cache` - GraphQL cache for type origin queries
+/// * `graphql_url` - GraphQL endpoint for this environment
+/// * `graphql_headers` - Extra HTTP headers for the GraphQL endpoint
+/// * `locked` - Locked data from gen.lock; if set, type origins are taken from it
+///   instead of being queried via GraphQL
+#[allow(clippy::too_many_arguments)]
 pub async fn build_model(
     packages: &Packages,
     manifest_path: &Path,
@@ -68,7 +76,10 @@ pub async fn build_model(
     chain_id: &str,
     environments: &Environments,
     dep_replacements: &DepReplacements,
-    graphql_client: &GraphQLClient,
+    graphql_cache: &mut GraphQLCache,
+    graphql_url: &str,
+    graphql_headers: &GraphQLHeaders,
+    locked: Option<&EnvLock>,
 ) -> Result<ModelResult> {
     // Get the manifest directory for resolving relative paths
     let manifest_dir = manifest_path
@@ -109,9 +120,34 @@ pub async fn build_model(
         .await
         .context("Failed to build Move model")?;
 
-    // Resolve type origins via GraphQL
-    let (type_origin_table, version_table) =
-        resolve_type_origins(graphql_client, &id_map, &published_at, &model).await?;
+    // Resolve type origins of published packages, from gen.lock if available or via GraphQL
+    let (published_type_origins, published_versions) = match locked {
+        Some(lock) => lock.tables_for(environment, chain_id, &published_at)?,
+        None => {
+            fetch_published_type_origins(
+                graphql_cache,
+                chain_id,
+                graphql_url,
+                graphql_headers,
+                &published_at,
+            )
+            .await?
+        }
+    };
+    let lock = EnvLock::new(
+        chain_id,
+        &published_at,
+        &published_type_origins,
+        &published_versions,
+    );
+
+    // Fill in self-origins for everything not resolved on-chain
+    let (type_origin_table, version_table) = fill_self_origins(
+        published_type_origins,
+        published_versions,
+        &id_map,
+        &model,
+    );
 
     Ok(ModelResult {
         model,
@@ -120,6 +156,7 @@ pub async fn build_model(
         type_origin_table,
         version_table,
         top_level_packages,
+        lock,
     })
 }
 
@@ -525,58 +562,108 @@ fn build_id_map_from_root_pkg(
     id_map
 }
 
-/// Resolve type origins using GraphQL queries.
+/// Resolve type origins of published packages using GraphQL queries.
 ///
-/// The version table assigns sequential version numbers (1, 2, 3...) to each unique
-/// defining address encountered, rather than querying actual on-chain versions.
-/// This is sufficient since we only need unique identifiers for PKG_V{N} exports.
-async fn resolve_type_origins(
-    graphql: &GraphQLClient,
-    id_map: &BTreeMap<AccountAddress, PackageName>,
+/// The version table maps every defining package of a type (and the published-at
+/// package itself) to its actual on-chain version, so `PKG_V{N}` numbering stays
+/// stable across upgrades.
+async fn fetch_published_type_origins(
+    graphql: &mut GraphQLCache,
+    chain_id: &str,
+    graphql_url: &str,
+    graphql_headers: &GraphQLHeaders,
     published_at: &BTreeMap<AccountAddress, AccountAddress>,
-    model: &Model<WithSource>,
 ) -> Result<(TypeOriginTable, VersionTable)> {
     let mut type_origin_table: TypeOriginTable = BTreeMap::new();
     let mut version_table: VersionTable = BTreeMap::new();
 
     // Query published packages via GraphQL
     let published_addrs: Vec<AccountAddress> = published_at.values().copied().collect();
+    if published_addrs.is_empty() {
+        return Ok((type_origin_table, version_table));
+    }
 
-    if !published_addrs.is_empty() {
-        let graphql_results = graphql
-            .query_multiple_packages_type_origins(published_addrs)
-            .await?;
-
-        // Build type origin table and version table from GraphQL results
-        for (original_id, &published_addr) in published_at.iter() {
-            if let Some(origins) = graphql_results.get(&published_addr) {
-                let mut origin_map: BTreeMap<String, AccountAddress> = BTreeMap::new();
-                // Collect unique defining addresses for this package
-                let mut defining_addrs: BTreeSet<AccountAddress> = BTreeSet::new();
-
-                for origin in origins {
-                    let key = format!("{}::{}", origin.module, origin.struct_name);
-                    if let Ok(defining_addr) = AccountAddress::from_hex_literal(&origin.defining_id)
-                    {
-                        origin_map.insert(key, defining_addr);
-                        defining_addrs.insert(defining_addr);
-                    }
-                }
+    let graphql_results = graphql
+        .query_type_origins(chain_id, graphql_url, graphql_headers, published_addrs)
+        .await
+        .context("Failed to resolve type origins for published packages")?;
 
-                // Assign sequential version numbers in reverse order (newest = V1)
-                // BTreeSet is sorted, so we reverse to get the most recent address first
-                let mut versions: BTreeMap<AccountAddress, SequenceNumber> = BTreeMap::new();
-                let addr_list: Vec<_> = defining_addrs.iter().collect();
-                for (idx, addr) in addr_list.iter().rev().enumerate() {
-                    versions.insert(**addr, SequenceNumber::from_u64((idx + 1) as u64));
-                }
+    // Falling back to self-origin for a published package would generate wrong type names
+    let unresolved: Vec<String> = published_at
+        .values()
+        .filter(|addr| !graphql_results.contains_key(*addr))
+        .map(|addr| addr.to_hex_literal())
+        .collect();
+    if !unresolved.is_empty() {
+        bail!(
+            "Failed to resolve type origins of published packages at {}",
+            unresolved.join(", ")
+        );
+    }
 
-                type_origin_table.insert(*original_id, origin_map);
-                version_table.insert(*original_id, versions);
-            }
+    // Build type origin table and collect the package versions each package spans
+    let mut package_addrs: BTreeMap<AccountAddress, BTreeSet<AccountAddress>> = BTreeMap::new();
+    for (original_id, &published_addr) in published_at.iter() {
+        let origins = &graphql_results[&published_addr];
+        let mut origin_map: BTreeMap<String, AccountAddress> = BTreeMap::new();
+        // Collect unique defining addresses for this package
+        let mut defining_addrs: BTreeSet<AccountAddress> = BTreeSet::from([published_addr]);
+
+        for origin in origins {
+            let key = format!("{}::{}", origin.module, origin.struct_name);
+            let defining_addr = AccountAddress::from_hex_literal(&origin.defining_id)
+                .with_context(|| {
+                    format!(
+                        "Invalid defining id '{}' of {} in package {}",
+                        origin.defining_id,
+                        key,
+                        published_addr.to_hex_literal()
+                    )
+                })?;
+            origin_map.insert(key, defining_addr);
+            defining_addrs.insert(defining_addr);
         }
+
+        type_origin_table.insert(*original_id, origin_map);
+        package_addrs.insert(*original_id, defining_addrs);
     }
 
+    // Query the on-chain version of every package version involved
+    let all_addrs: BTreeSet<AccountAddress> = package_addrs.values().flatten().copied().collect();
+    let versions = graphql
+        .query_package_versions(
+            chain_id,
+            graphql_url,
+            graphql_headers,
+            all_addrs.into_iter().collect(),
+        )
+        .await
+        .context("Failed to resolve versions of published packages")?;
+
+    for (original_id, addrs) in package_addrs {
+        let mut package_versions: BTreeMap<AccountAddress, SequenceNumber> = BTreeMap::new();
+        for addr in addrs {
+            let version = versions.get(&addr).ok_or_else(|| {
+                anyhow::anyhow!("No version returned for package {}", addr.to_hex_literal())
+            })?;
+            package_versions.insert(addr, SequenceNumber::from_u64(*version));
+        }
+        version_table.insert(original_id, package_versions);
+    }
+
+    Ok((type_origin_table, version_table))
+}
+
+/// Complete the type origin and version tables with self-origins.
+///
+/// Every datatype in the model that wasn't resolved on-chain (e.g. unpublished
+/// packages) originates from its own package, at version 1.
+fn fill_self_origins(
+    mut type_origin_table: TypeOriginTable,
+    mut version_table: VersionTable,
+    id_map: &BTreeMap<AccountAddress, PackageName>,
+    model: &Model<WithSource>,
+) -> (TypeOriginTable, VersionTable) {
     // Fill in unpublished packages from model (self-origin)
     // We need to process ALL modules, not just the first one per package
     for module in model.modules() {
@@ -614,7 +701,7 @@ async fn resolve_type_origins(
             .or_insert(SequenceNumber::from_u64(1));
     }
 
-    Ok((type_origin_table, version_table))
+    (type_origin_table, version_table)
 }
 
 #[cfg(test)]
@@ -10,7 +10,9 @@ use anyhow::{Context, Result};
 use move_core_types::account_address::AccountAddress;
 use move_symbol_pool::Symbol;
 
+use crate::filter::Selection;
 use crate::graphql::GraphQLCache;
+use crate::lockfile::{EnvLock, GenLock};
 use crate::manifest::{is_default_environment, GenManifest};
 use crate::model_builder::{self, ModelResult, TypeOriginTable, VersionTable};
 use crate::ts_gen::compat::{
@@ -19,7 +21,7 @@ use crate::ts_gen::compat::{
 use crate::ts_gen::{
     EnumIR, EnumIRBuilder, FunctionIR, FunctionIRBuilder, StructIR, StructIRBuilder,
 };
-use crate::{resolve_chain_id, resolve_graphql};
+use crate::{resolve_chain_id, resolve_graphql, resolve_graphql_headers};
 
 /// Result of building models for all environments.
 pub struct MultiEnvResult {
@@ -41,6 +43,8 @@ pub struct MultiEnvResult {
     pub env_version_tables: BTreeMap<String, VersionTable>,
     /// Per-environment id_maps (address -> package name)
     pub env_id_maps: BTreeMap<String, BTreeMap<AccountAddress, move_package_alt::schema::PackageName>>,
+    /// Per-environment on-chain data to be recorded in gen.lock
+    pub env_locks: BTreeMap<String, EnvLock>,
 }
 
 /// IR snapshot of an environment for compatibility checking.
@@ -108,6 +112,7 @@ fn build_top_level_addr_map(
 /// * `manifest` - The parsed gen.toml manifest
 /// * `manifest_path` - Path to the gen.toml file
 /// * `graphql_cache` - Cache for GraphQL queries (avoids duplicate fetches per chain)
+/// * `lock` - Existing gen.lock; environments present in it use the locked on-chain data
 ///
 /// # Returns
 /// * `Ok(MultiEnvResult)` - All environments are compatible
@@ -116,6 +121,7 @@ pub async fn build_multi_env_models(
     manifest: &GenManifest,
     manifest_path: &Path,
     graphql_cache: &mut GraphQLCache,
+    lock: Option<&GenLock>,
 ) -> Result<MultiEnvResult> {
     let all_envs = collect_all_environments(manifest);
     let default_env = &manifest.config.environment;
@@ -128,9 +134,17 @@ pub async fn build_multi_env_models(
         default_env,
         &manifest.environments,
     );
+    let default_headers = resolve_graphql_headers(
+        manifest.config.graphql.as_deref(),
+        &manifest.config.graphql_headers,
+        default_env,
+        &manifest.environments,
+    )?;
 
     // Validate chain ID
-    let actual_chain_id = graphql_cache.query_chain_identifier(&default_graphql).await?;
+    let actual_chain_id = graphql_cache
+        .query_chain_identifier(&default_graphql, &default_headers)
+        .await?;
     if actual_chain_id != default_chain_id {
         return Err(anyhow::anyhow!(
             "Chain ID mismatch for '{}': expected '{}', got '{}' from {}",
@@ -145,7 +159,10 @@ pub async fn build_multi_env_models(
         &default_chain_id,
         &manifest.environments,
         &manifest.dep_replacements,
-        graphql_cache.get_client(&default_chain_id, &default_graphql),
+        graphql_cache,
+        &default_graphql,
+        &default_headers,
+        lock.and_then(|l| l.environments.get(default_env)),
     )
     .await
     .context(format!("Failed to build model for default environment '{}'", default_env))?;
@@ -153,8 +170,11 @@ pub async fn build_multi_env_models(
     // Build top-level address map and folder names from default model
     let top_level_addr_map =
         build_top_level_addr_map(&default_model.id_map, &default_model.top_level_packages);
-    let folder_names =
-        crate::layout::build_package_folder_names(&default_model.id_map, &top_level_addr_map);
+    let folder_names = crate::layout::build_package_folder_names(
+        &default_model.id_map,
+        &top_level_addr_map,
+        &manifest.naming.folders,
+    )?;
 
     // Extract IR snapshot from default environment
     let default_snapshot = extract_ir_snapshot(
@@ -162,6 +182,7 @@ pub async fn build_multi_env_models(
         &default_model,
         &folder_names,
         &top_level_addr_map,
+        manifest,
     )?;
 
     // Store per-environment data
@@ -169,11 +190,13 @@ pub async fn build_multi_env_models(
     let mut env_published_at = BTreeMap::new();
     let mut env_version_tables = BTreeMap::new();
     let mut env_id_maps = BTreeMap::new();
+    let mut env_locks = BTreeMap::new();
 
     env_type_origins.insert(default_env.clone(), default_model.type_origin_table.clone());
     env_published_at.insert(default_env.clone(), default_model.published_at.clone());
     env_version_tables.insert(default_env.clone(), default_model.version_table.clone());
     env_id_maps.insert(default_env.clone(), default_model.id_map.clone());
+    env_locks.insert(default_env.clone(), default_model.lock.clone());
 
     // Process other environments
     let mut compat_errors = Vec::new();
@@ -196,9 +219,17 @@ pub async fn build_multi_env_models(
             env_name,
             &manifest.environments,
         );
+        let graphql_headers = resolve_graphql_headers(
+            manifest.config.graphql.as_deref(),
+            &manifest.config.graphql_headers,
+            env_name,
+            &manifest.environments,
+        )?;
 
         // Validate chain ID (if different from what we've already validated)
-        let actual_chain_id = graphql_cache.query_chain_identifier(&graphql_url).await?;
+        let actual_chain_id = graphql_cache
+            .query_chain_identifier(&graphql_url, &graphql_headers)
+            .await?;
         if actual_chain_id != chain_id {
             return Err(anyhow::anyhow!(
                 "Chain ID mismatch for '{}': expected '{}', got '{}' from {}",
@@ -214,7 +245,10 @@ pub async fn build_multi_env_models(
             &chain_id,
             &manifest.environments,
             &manifest.dep_replacements,
-            graphql_cache.get_client(&chain_id, &graphql_url),
+            graphql_cache,
+            &graphql_url,
+            &graphql_headers,
+            lock.and_then(|l| l.environments.get(env_name)),
         )
         .await
         .context(format!("Failed to build model for environment '{}'", env_name))?;
@@ -224,6 +258,7 @@ pub async fn build_multi_env_models(
         env_published_at.insert(env_name.clone(), env_model.published_at.clone());
         env_version_tables.insert(env_name.clone(), env_model.version_table.clone());
         env_id_maps.insert(env_name.clone(), env_model.id_map.clone());
+        env_locks.insert(env_name.clone(), env_model.lock.clone());
 
         // Build environment-specific folder_names and top_level_addr_map for IR extraction
         let env_top_level_addr_map =
@@ -231,7 +266,9 @@ pub async fn build_multi_env_models(
         let env_folder_names = crate::layout::build_package_folder_names(
             &env_model.id_map,
             &env_top_level_addr_map,
-        );
+            &manifest.naming.folders,
+        )
+        .context(format!("Failed to assign folder names in environment '{}'", env_name))?;
 
         // Extract IR and check compatibility
         let env_snapshot = extract_ir_snapshot(
@@ -239,6 +276,7 @@ pub async fn build_multi_env_models(
             &env_model,
             &env_folder_names,
             &env_top_level_addr_map,
+            manifest,
         )?;
 
         // Check compatibility for items that exist in BOTH environments
@@ -265,15 +303,21 @@ pub async fn build_multi_env_models(
         env_published_at,
         env_version_tables,
         env_id_maps,
+        env_locks,
     })
 }
 
 /// Extract IR snapshot from a built model for compatibility checking.
+///
+/// Only the items that are generated are extracted: the manifest filters, `[dep-functions]`
+/// and `all-functions` are applied to the environment's model like the driver does, including
+/// the structs and enums kept because generated items reference them.
 fn extract_ir_snapshot(
     env_name: &str,
     model_result: &ModelResult,
     folder_names: &BTreeMap<AccountAddress, String>,
     top_level_addr_map: &BTreeMap<AccountAddress, Symbol>,
+    manifest: &GenManifest,
 ) -> Result<EnvIRSnapshot> {
     let mut snapshot = EnvIRSnapshot {
         env_name: env_name.to_string(),
@@ -282,8 +326,26 @@ fn extract_ir_snapshot(
         functions: BTreeMap::new(),
     };
 
+    let pkgs: BTreeMap<_, _> = model_result
+        .model
+        .packages()
+        .map(|pkg| (pkg.address(), pkg))
+        .collect();
+    let selection = Selection::new(
+        &pkgs,
+        &model_result.id_map,
+        top_level_addr_map,
+        &manifest.filters,
+        &manifest.dep_functions,
+        manifest.config.all_functions,
+    )
+    .context(format!("Failed to apply filters in environment '{}'", env_name))?;
+
     for module in model_result.model.modules() {
         let pkg_addr = module.package().address();
+        let Some(module_selection) = selection.module(pkg_addr, module.name()) else {
+            continue;
+        };
         let pkg_name = folder_names
             .get(&pkg_addr)
             .cloned()
@@ -296,6 +358,9 @@ fn extract_ir_snapshot(
 
         // Extract structs
         for strct in module.structs() {
+            if !module_selection.datatypes.contains(&strct.name()) {
+                continue;
+            }
             let builder = StructIRBuilder::new(
                 strct,
                 &model_result.type_origin_table,
@@ -311,6 +376,9 @@ fn extract_ir_snapshot(
 
         // Extract enums
         for enum_ in module.enums() {
+            if !module_selection.datatypes.contains(&enum_.name()) {
+                continue;
+            }
             let mut builder = EnumIRBuilder::new(
                 enum_,
                 &model_result.type_origin_table,
@@ -324,19 +392,17 @@ fn extract_ir_snapshot(
             snapshot.enums.insert(key, ir);
         }
 
-        // Extract functions (only for top-level packages)
-        if is_top_level {
-            for func in module.functions() {
-                if let Some(builder) = FunctionIRBuilder::new(
-                    func,
-                    folder_names,
-                    top_level_addr_map,
-                    levels_from_root,
-                ) {
-                    let ir = builder.build();
-                    let key = format!("{}::{}::{}", pkg_name, mod_name, ir.move_name);
-                    snapshot.functions.insert(key, ir);
-                }
+        // Extract functions
+        for func in module.functions() {
+            if !module_selection.functions.contains(&func.name()) {
+                continue;
+            }
+            if let Some(builder) =
+                FunctionIRBuilder::new(func, folder_names, top_level_addr_map, levels_from_root)
+            {
+                let ir = builder.build();
+                let key = format!("{}::{}::{}", pkg_name, mod_name, ir.move_name);
+                snapshot.functions.insert(key, ir);
             }
         }
     }
@@ -410,6 +476,7 @@ mod tests {
                 Environment {
                     chain_id: chain_id.map(String::from),
                     graphql: None,
+                    graphql_headers: BTreeMap::new(),
                 },
             );
         }
@@ -419,10 +486,19 @@ mod tests {
                 environment: "env_1".to_string(),
                 graphql: None,
                 output: None,
+                graphql_batch_size: None,
+                graphql_timeout_secs: None,
+                graphql_max_retries: None,
+                graphql_headers: BTreeMap::new(),
+                all_functions: false,
+                json_validators: false,
             },
             packages: BTreeMap::new(),
             environments,
             dep_replacements: BTreeMap::new(),
+            filters: BTreeMap::new(),
+            dep_functions: BTreeMap::new(),
+            naming: Default::default(),
         }
     }
 
@@ -0,0 +1,208 @@
+//! JSON Schema of the generator manifest (gen.toml), printed by `sui-client-gen schema`.
+//!
+//! The schema is built from the field definitions in `manifest.rs`, which the parser
+//! also checks manifests against, so the two always accept the same fields.
+
+use serde_json::{json, Map, Value};
+
+use crate::manifest::{
+    Field, FieldKind, Section, CONFIG_FIELDS, CONFIG_NAME, DEFAULT_ENVIRONMENTS, DEPENDENCY_FLAGS,
+    DEPENDENCY_FORMS, DEP_REPLACEMENT_FIELDS, ENVIRONMENT_FIELDS, EXTENDS_NAME, NAMING_FIELDS,
+    PACKAGES_NAME, PACKAGE_FILTER_FIELDS,
+};
+
+const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Build the JSON Schema of gen.toml.
+pub fn manifest_schema() -> Value {
+    let properties: Map<String, Value> = Section::ALL
+        .iter()
+        .map(|section| (section.name().to_string(), section_schema(*section)))
+        .collect();
+
+    json!({
+        "$schema": JSON_SCHEMA_DIALECT,
+        "title": "sui-client-gen manifest (gen.toml)",
+        "type": "object",
+        "properties": properties,
+        // [config] and [packages] may come from an extended manifest instead
+        "anyOf": [
+            { "required": [EXTENDS_NAME] },
+            { "required": [CONFIG_NAME, PACKAGES_NAME] }
+        ],
+        "additionalProperties": false
+    })
+}
+
+fn section_schema(section: Section) -> Value {
+    match section {
+        Section::Extends => json!({
+            "description": "Path to a base manifest to extend, relative to this one.",
+            "type": "string"
+        }),
+        Section::Config => described(
+            table_schema(CONFIG_FIELDS),
+            "Configuration for the code generator.",
+        ),
+        Section::Packages => json!({
+            "description": "Packages to generate code for, keyed by package name.",
+            "type": "object",
+            "additionalProperties": { "oneOf": dependency_schemas(DEPENDENCY_FLAGS) }
+        }),
+        Section::Environments => environments_schema(),
+        Section::DepReplacements => {
+            let mut replacements = dependency_schemas(DEP_REPLACEMENT_FIELDS);
+            replacements.push(table_schema(DEP_REPLACEMENT_FIELDS));
+            json!({
+                "description": "Environment-scoped dependency replacements, keyed by environment and package name.",
+                "type": "object",
+                "additionalProperties": {
+                    "type": "object",
+                    "additionalProperties": { "anyOf": replacements }
+                }
+            })
+        }
+        Section::Filters => json!({
+            "description": "Module and item filters, keyed by package name or address.",
+            "type": "object",
+            "additionalProperties": table_schema(PACKAGE_FILTER_FIELDS)
+        }),
+        Section::DepFunctions => json!({
+            "description": "Dependencies to generate function bindings for, keyed by package name or address: true for all modules or a list of module globs.",
+            "type": "object",
+            "additionalProperties": {
+                "oneOf": [
+                    { "type": "boolean" },
+                    field_schema(FieldKind::StringList)
+                ]
+            }
+        }),
+        Section::Naming => described(
+            table_schema(NAMING_FIELDS),
+            "Output folder and TypeScript identifier overrides.",
+        ),
+    }
+}
+
+/// Environments are a chain id (string shorthand) or a table. The default environments
+/// can only override their GraphQL endpoint and headers.
+fn environments_schema() -> Value {
+    let optional_fields: Vec<Field> = ENVIRONMENT_FIELDS
+        .iter()
+        .filter(|f| !f.required)
+        .copied()
+        .collect();
+    let default_envs: Map<String, Value> = DEFAULT_ENVIRONMENTS
+        .iter()
+        .map(|env| (env.to_string(), table_schema(&optional_fields)))
+        .collect();
+
+    json!({
+        "description": "Environment definitions, keyed by environment name.",
+        "type": "object",
+        "properties": default_envs,
+        "additionalProperties": {
+            "oneOf": [
+                { "description": "Chain identifier.", "type": "string" },
+                table_schema(ENVIRONMENT_FIELDS)
+            ]
+        }
+    })
+}
+
+/// One schema per dependency form, each also accepting the `extra` fields.
+fn dependency_schemas(extra: &[Field]) -> Vec<Value> {
+    DEPENDENCY_FORMS
+        .iter()
+        .map(|form| {
+            let extra = extra.iter().map(|f| Field {
+                required: false,
+                ..*f
+            });
+            let fields: Vec<Field> = form.fields.iter().copied().chain(extra).collect();
+            described(table_schema(&fields), form.description)
+        })
+        .collect()
+}
+
+fn table_schema(fields: &[Field]) -> Value {
+    let properties: Map<String, Value> = fields
+        .iter()
+        .map(|f| {
+            let schema = described(field_schema(f.kind), f.description);
+            (f.name.to_string(), schema)
+        })
+        .collect();
+    let required: Vec<&str> = fields
+        .iter()
+        .filter(|f| f.required)
+        .map(|f| f.name)
+        .collect();
+
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false
+    })
+}
+
+fn field_schema(kind: FieldKind) -> Value {
+    match kind {
+        FieldKind::String => json!({ "type": "string" }),
+        FieldKind::Bool => json!({ "type": "boolean" }),
+        FieldKind::Integer(minimum) => json!({ "type": "integer", "minimum": minimum }),
+        FieldKind::StringList => json!({ "type": "array", "items": { "type": "string" } }),
+        FieldKind::StringTable => {
+            json!({ "type": "object", "additionalProperties": { "type": "string" } })
+        }
+    }
+}
+
+fn described(mut schema: Value, description: &str) -> Value {
+    schema["description"] = description.into();
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest_schema() {
+        let schema = manifest_schema();
+        assert_eq!(schema["$schema"], JSON_SCHEMA_DIALECT);
+
+        // Every section has a schema
+        let properties = schema["properties"].as_object().unwrap();
+        assert_eq!(properties.len(), Section::ALL.len());
+
+        let config = &properties["config"];
+        assert_eq!(config["required"], json!(["environment"]));
+        assert_eq!(config["additionalProperties"], false);
+        assert_eq!(
+            config["properties"]["graphql-batch-size"],
+            json!({
+                "type": "integer",
+                "minimum": 1,
+                "description": "Max number of packages per batched GraphQL type origins query."
+            })
+        );
+
+        let forms = properties["packages"]["additionalProperties"]["oneOf"]
+            .as_array()
+            .unwrap();
+        assert_eq!(forms.len(), DEPENDENCY_FORMS.len());
+        for form in forms {
+            assert!(form["properties"]["override"].is_object());
+            assert_eq!(form["required"].as_array().unwrap().len(), 1);
+        }
+
+        let environments = &properties["environments"];
+        assert!(environments["properties"]["mainnet"]["properties"]["chain-id"].is_null());
+        assert_eq!(
+            environments["additionalProperties"]["oneOf"][1]["required"],
+            json!(["chain-id"])
+        );
+    }
+}
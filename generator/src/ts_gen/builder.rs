@@ -2,10 +2,11 @@
 //!
 //! This module bridges the Move model types to our coarse-grained IR.
 
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 use anyhow::{Context, Result};
 use convert_case::{Case, Casing};
+use move_binary_format::file_format::AbilitySet;
 use move_core_types::account_address::AccountAddress;
 use move_model_2::model::{self, Datatype};
 use move_model_2::normalized::Type;
@@ -17,9 +18,10 @@ use super::enums::{EnumIR, EnumVariantIR};
 use super::imports::{ImportPathResolver, TsImportsBuilder};
 use super::jsdoc::format_jsdoc;
 use super::structs::{
-    DatatypeKind, FieldIR, FieldTypeIR, PackageInfo, StructIR, StructImport, TypeParamIR,
+    Ability, DatatypeKind, FieldIR, FieldTypeIR, PackageInfo, StructIR, StructImport, TypeParamIR,
 };
-use super::utils::sanitize_identifier;
+use super::utils::{datatype_ts_name, function_ts_name, sanitize_identifier, IdentifierRenames};
+use crate::filter::ModuleSelection;
 use crate::model_builder::{TypeOriginTable, VersionTable};
 
 /// Compute the relative path to the `_framework` directory from a given depth.
@@ -31,6 +33,19 @@ fn compute_framework_path(levels_from_root: u8) -> String {
     }
 }
 
+/// Convert a Move ability set to IR abilities, in declaration order (copy, drop, store, key).
+fn build_abilities(abilities: &AbilitySet) -> Vec<Ability> {
+    [
+        (abilities.has_copy(), Ability::Copy),
+        (abilities.has_drop(), Ability::Drop),
+        (abilities.has_store(), Ability::Store),
+        (abilities.has_key(), Ability::Key),
+    ]
+    .into_iter()
+    .filter_map(|(has, ability)| has.then_some(ability))
+    .collect()
+}
+
 /// Get the origin package address for a datatype (struct or enum).
 /// Returns an error with context if the origin address cannot be found.
 fn get_origin_pkg_addr_for_datatype(
@@ -103,6 +118,8 @@ pub struct StructIRBuilder<'a, 'model, HasSource: SourceKind> {
     framework_path: String,
     /// Tracks struct imports to avoid name conflicts
     struct_imports: HashMap<String, StructImport>,
+    /// Renamed identifiers from `[naming.identifiers]`
+    renames: Option<&'a IdentifierRenames>,
 }
 
 impl<'a, 'model, HasSource: SourceKind> StructIRBuilder<'a, 'model, HasSource> {
@@ -131,12 +148,24 @@ impl<'a, 'model, HasSource: SourceKind> StructIRBuilder<'a, 'model, HasSource> {
             top_level_pkg_names,
             framework_path,
             struct_imports: HashMap::new(),
+            renames: None,
         }
     }
 
+    /// Use the TypeScript names from `[naming.identifiers]` for renamed datatypes.
+    pub fn with_renames(mut self, renames: &'a IdentifierRenames) -> Self {
+        self.renames = Some(renames);
+        self
+    }
+
     /// Build the StructIR from the Move model.
     pub fn build(mut self) -> (StructIR, String) {
-        let name = self.strct.name().to_string();
+        let name = datatype_ts_name(
+            self.renames,
+            self.package_address,
+            self.module_name,
+            self.strct.name(),
+        );
         let module_struct_path = format!("{}::{}", self.strct.module().name(), self.strct.name());
         let package_info = self.build_package_info();
         let type_params = self.build_type_params();
@@ -174,6 +203,8 @@ impl<'a, 'model, HasSource: SourceKind> StructIRBuilder<'a, 'model, HasSource> {
             has_non_phantom_type_params,
             uses_field_to_json,
             doc_comment,
+            abilities: build_abilities(&self.strct.compiled().abilities),
+            json_validators: false,
         };
 
         (ir, self.framework_path)
@@ -387,13 +418,23 @@ impl<'a, 'model, HasSource: SourceKind> StructIRBuilder<'a, 'model, HasSource> {
     }
 
     fn get_import_for_struct<S: SourceKind>(&mut self, strct: &model::Struct<S>) -> String {
-        let class_name = strct.name().to_string();
+        let class_name = datatype_ts_name(
+            self.renames,
+            strct.module().package().address(),
+            strct.module().name(),
+            strct.name(),
+        );
         let import_path = self.import_path_for_module(&strct.module());
         self.resolve_import(class_name, import_path)
     }
 
     fn get_import_for_enum<S: SourceKind>(&mut self, enum_: &model::Enum<S>) -> String {
-        let class_name = enum_.name().to_string();
+        let class_name = datatype_ts_name(
+            self.renames,
+            enum_.module().package().address(),
+            enum_.module().name(),
+            enum_.name(),
+        );
         let import_path = self.import_path_for_module(&enum_.module());
         self.resolve_import(class_name, import_path)
     }
@@ -413,6 +454,10 @@ impl<'a, 'model, HasSource: SourceKind> StructIRBuilder<'a, 'model, HasSource> {
 }
 
 /// Generate structs.ts for a module (handles both structs and enums).
+///
+/// Only the structs and enums in `selection` are generated, named as in `renames`.
+/// With `json_validators`, every struct and enum also gets a JSON validator used by `fromJSON`.
+#[allow(clippy::too_many_arguments)]
 pub fn gen_module_structs<HasSource: SourceKind>(
     module: &model::Module<HasSource>,
     type_origin_table: &TypeOriginTable,
@@ -420,6 +465,9 @@ pub fn gen_module_structs<HasSource: SourceKind>(
     folder_names: &BTreeMap<AccountAddress, String>,
     top_level_pkg_names: &BTreeMap<AccountAddress, Symbol>,
     levels_from_root: u8,
+    json_validators: bool,
+    selection: &ModuleSelection,
+    renames: &IdentifierRenames,
 ) -> String {
     // Extract module-level documentation
     let module_doc = match module.kind() {
@@ -433,8 +481,11 @@ pub fn gen_module_structs<HasSource: SourceKind>(
     let mut all_imports: HashMap<String, StructImport> = HashMap::new();
     let mut framework_path = String::new();
 
-    // Build IR for all structs
-    for strct in module.structs() {
+    // Build IR for the selected structs
+    for strct in module
+        .structs()
+        .filter(|s| selection.datatypes.contains(&s.name()))
+    {
         let builder = StructIRBuilder::new(
             strct,
             type_origin_table,
@@ -442,8 +493,10 @@ pub fn gen_module_structs<HasSource: SourceKind>(
             folder_names,
             top_level_pkg_names,
             levels_from_root + 2,
-        );
-        let (ir, fp) = builder.build();
+        )
+        .with_renames(renames);
+        let (mut ir, fp) = builder.build();
+        ir.json_validators = json_validators;
         framework_path = fp;
 
         for imp in &ir.struct_imports {
@@ -454,8 +507,11 @@ pub fn gen_module_structs<HasSource: SourceKind>(
         all_struct_irs.push(ir);
     }
 
-    // Build IR for all enums
-    for enum_ in module.enums() {
+    // Build IR for the selected enums
+    for enum_ in module
+        .enums()
+        .filter(|e| selection.datatypes.contains(&e.name()))
+    {
         let mut builder = EnumIRBuilder::new(
             enum_,
             type_origin_table,
@@ -463,8 +519,10 @@ pub fn gen_module_structs<HasSource: SourceKind>(
             folder_names,
             top_level_pkg_names,
             levels_from_root + 2,
-        );
-        let ir = builder.build();
+        )
+        .with_renames(renames);
+        let mut ir = builder.build();
+        ir.json_validators = json_validators;
 
         // Collect imports from enum
         for imp in builder.get_struct_imports() {
@@ -551,6 +609,8 @@ fn emit_combined_imports_with_enums(
         || enums.iter().any(|e| e.uses_phantom_struct_args);
     let uses_field_to_json = structs.iter().any(|s| s.uses_field_to_json);
     let has_enums = !enums.is_empty();
+    // Only objects (`key` structs) get fromCoreObject / fromSuiObjectData / fetch
+    let has_objects = structs.iter().any(|s| s.has_key());
 
     // Named reified imports - always include base set (including phantom, used in every struct's .phantom() method)
     let reified_path = format!("{}/reified", framework_path);
@@ -609,6 +669,10 @@ fn emit_combined_imports_with_enums(
         imports.add_named(&reified_path, "EnumVariantClass");
     }
 
+    if has_objects {
+        imports.add_named(&reified_path, "KeyStructClassReified");
+    }
+
     if uses_phantom_struct_args {
         imports.add_named_as(&reified_path, "ToTypeStr", "ToPhantom");
     }
@@ -624,9 +688,12 @@ fn emit_combined_imports_with_enums(
         ],
     );
 
-    // parseTypeName is needed for structs/enums with type params (used in fromCoreObject,
+    // parseTypeName is needed for objects with type params (used in fromCoreObject,
     // fromSuiObjectData, and fetch to extract and validate runtime type arguments).
-    if has_type_params {
+    if structs
+        .iter()
+        .any(|s| s.has_key() && !s.type_params.is_empty())
+    {
         imports.add_named(&util_path, "parseTypeName");
     }
 
@@ -635,6 +702,20 @@ fn emit_combined_imports_with_enums(
         imports.add_named(format!("{}/vector", framework_path), "Vector");
     }
 
+    // JSON validation helpers (validators are opt-in)
+    if structs.iter().any(|s| s.json_validators) || enums.iter().any(|e| e.json_validators) {
+        imports.add_named_many(
+            format!("{}/validate", framework_path),
+            &["validateJSONField", "validateObject"],
+        );
+    }
+    if enums.iter().any(|e| e.json_validators) {
+        imports.add_named(
+            format!("{}/validate", framework_path),
+            "JSONValidationError",
+        );
+    }
+
     // Environment imports (getTypeOrigin) if any struct/enum uses dynamic package info
     // Import from _envs (sibling to _framework) to ensure auto-initialization
     let uses_env = structs.iter().any(|s| s.uses_env()) || enums.iter().any(|e| e.uses_env());
@@ -660,18 +741,19 @@ fn emit_combined_imports_with_enums(
         imports.add_named("@mysten/sui/bcs", "bcs");
     }
 
-    // Sui client imports — type-only since they're only used in method signatures.
-    imports.add_type_named("@mysten/sui/client", "ClientWithCoreApi");
-    imports.add_type_named("@mysten/sui/client", "SuiClientTypes");
-    // JSON-RPC-only types used by the deprecated fromSuiParsedData / fromSuiObjectData methods.
-    imports.add_type_named("@mysten/sui/jsonRpc", "SuiObjectData");
-    imports.add_type_named("@mysten/sui/jsonRpc", "SuiParsedData");
+    if has_objects {
+        // Sui client imports — type-only since they're only used in method signatures.
+        imports.add_type_named("@mysten/sui/client", "ClientWithCoreApi");
+        imports.add_type_named("@mysten/sui/client", "SuiClientTypes");
+        // JSON-RPC-only types used by the deprecated fromSuiParsedData / fromSuiObjectData methods.
+        imports.add_type_named("@mysten/sui/jsonRpc", "SuiObjectData");
+        imports.add_type_named("@mysten/sui/jsonRpc", "SuiParsedData");
+        imports.add_named("@mysten/sui/utils", "fromBase64");
+    }
 
     // Sui utils imports
     if uses_address {
-        imports.add_named_many("@mysten/sui/utils", &["fromBase64", "fromHex", "toHex"]);
-    } else {
-        imports.add_named("@mysten/sui/utils", "fromBase64");
+        imports.add_named_many("@mysten/sui/utils", &["fromHex", "toHex"]);
     }
 
     imports.emit()
@@ -710,6 +792,8 @@ pub struct EnumIRBuilder<'a, 'model, HasSource: SourceKind> {
     framework_path: String,
     /// Tracks struct imports to avoid name conflicts
     struct_imports: HashMap<String, StructImport>,
+    /// Renamed identifiers from `[naming.identifiers]`
+    renames: Option<&'a IdentifierRenames>,
 }
 
 impl<'a, 'model, HasSource: SourceKind> EnumIRBuilder<'a, 'model, HasSource> {
@@ -738,11 +822,23 @@ impl<'a, 'model, HasSource: SourceKind> EnumIRBuilder<'a, 'model, HasSource> {
             top_level_pkg_names,
             framework_path,
             struct_imports: HashMap::new(),
+            renames: None,
         }
     }
 
+    /// Use the TypeScript names from `[naming.identifiers]` for renamed datatypes.
+    pub fn with_renames(mut self, renames: &'a IdentifierRenames) -> Self {
+        self.renames = Some(renames);
+        self
+    }
+
     pub fn build(&mut self) -> EnumIR {
-        let name = self.enum_.name().to_string();
+        let name = datatype_ts_name(
+            self.renames,
+            self.package_address,
+            self.module_name,
+            self.enum_.name(),
+        );
 
         // Determine package info (System vs Versioned)
         let _origin_addr = get_origin_pkg_addr_for_enum(&self.enum_, self.type_origin_table);
@@ -767,7 +863,7 @@ impl<'a, 'model, HasSource: SourceKind> EnumIRBuilder<'a, 'model, HasSource> {
             .collect();
 
         // Build module path
-        let module_enum_path = format!("{}::{}", self.module_name, name);
+        let module_enum_path = format!("{}::{}", self.module_name, self.enum_.name());
 
         // Build variants - collect first to avoid borrow issues
         let model_variants: Vec<_> = self.enum_.variants().collect();
@@ -802,6 +898,8 @@ impl<'a, 'model, HasSource: SourceKind> EnumIRBuilder<'a, 'model, HasSource> {
             uses_address,
             uses_phantom_struct_args,
             doc_comment,
+            abilities: build_abilities(&self.enum_.compiled().abilities),
+            json_validators: false,
         }
     }
 
@@ -1050,16 +1148,16 @@ impl<'a, 'model, HasSource: SourceKind> EnumIRBuilder<'a, 'model, HasSource> {
     }
 
     fn get_import_for_struct(&mut self, s: &model::Struct<'model, HasSource>) -> String {
-        let class_name = s.name().to_string();
         let pkg_addr = s.module().package().address();
         let mod_name = s.module().name();
+        let class_name = datatype_ts_name(self.renames, pkg_addr, mod_name, s.name());
         self.resolve_import(class_name, pkg_addr, mod_name)
     }
 
     fn get_import_for_enum(&mut self, e: &model::Enum<'model, HasSource>) -> String {
-        let class_name = e.name().to_string();
         let pkg_addr = e.module().package().address();
         let mod_name = e.module().name();
+        let class_name = datatype_ts_name(self.renames, pkg_addr, mod_name, e.name());
         self.resolve_import(class_name, pkg_addr, mod_name)
     }
 
@@ -1089,7 +1187,10 @@ impl<'a, 'model, HasSource: SourceKind> EnumIRBuilder<'a, 'model, HasSource> {
 // FunctionIRBuilder
 // ============================================================================
 
-use super::functions::{FunctionIR, FunctionParamIR, FunctionStructImport, ParamTypeIR};
+use super::functions::{
+    FunctionIR, FunctionParamIR, FunctionStructImport, FunctionTypeParamIR, ParamTypeIR,
+};
+use move_binary_format::file_format::Visibility;
 
 /// Builds FunctionIR from a Move model function.
 pub struct FunctionIRBuilder<'a, 'model, HasSource: SourceKind> {
@@ -1102,6 +1203,8 @@ pub struct FunctionIRBuilder<'a, 'model, HasSource: SourceKind> {
     #[allow(dead_code)]
     framework_path: String,
     struct_imports: HashMap<String, FunctionStructImport>,
+    /// Renamed identifiers from `[naming.identifiers]`
+    renames: Option<&'a IdentifierRenames>,
 }
 
 impl<'a, 'model, HasSource: SourceKind> FunctionIRBuilder<'a, 'model, HasSource> {
@@ -1128,16 +1231,53 @@ impl<'a, 'model, HasSource: SourceKind> FunctionIRBuilder<'a, 'model, HasSource>
             top_level_pkg_names,
             framework_path,
             struct_imports: HashMap::new(),
+            renames: None,
         })
     }
 
+    /// Use the TypeScript names from `[naming.identifiers]` for renamed functions and datatypes.
+    pub fn with_renames(mut self, renames: &'a IdentifierRenames) -> Self {
+        self.renames = Some(renames);
+        self
+    }
+
+    /// Whether the function can be called from a programmable transaction block,
+    /// i.e. it's `public` or `entry`. `public(package)` and private functions
+    /// can only be called from other Move code.
+    pub fn is_callable(&self) -> bool {
+        let compiled = self.func.maybe_compiled().unwrap();
+        compiled.is_entry || compiled.visibility == Visibility::Public
+    }
+
     /// Build the FunctionIR from the Move function.
     pub fn build(mut self) -> FunctionIR {
         let move_name = self.func.name().to_string();
-        let ts_name = self.generate_ts_name(&move_name);
+        let ts_name = self
+            .renames
+            .and_then(|renames| {
+                renames.get(&(self.package_address, self.module_name, self.func.name()))
+            })
+            .cloned()
+            .unwrap_or_else(|| function_ts_name(&move_name));
         let module_name = self.module_name.to_string();
-        let type_params = self.build_type_params();
+        let type_params = self
+            .build_type_params()
+            .into_iter()
+            .zip(&self.func.maybe_compiled().unwrap().type_parameters)
+            .map(|(name, abilities)| FunctionTypeParamIR {
+                name,
+                constraints: build_abilities(abilities),
+            })
+            .collect();
         let params = self.build_params();
+        let returns = self.build_returns();
+        let returns_reference = self
+            .func
+            .maybe_compiled()
+            .unwrap()
+            .return_
+            .iter()
+            .any(|ty| matches!(ty, Type::Reference(..)));
 
         // Determine what utilities are used
         let uses_generic = params.iter().any(|p| self.type_uses_generic(&p.param_type));
@@ -1184,6 +1324,8 @@ impl<'a, 'model, HasSource: SourceKind> FunctionIRBuilder<'a, 'model, HasSource>
             env_pkg_name,
             type_params,
             params,
+            returns,
+            returns_reference,
             struct_imports: self.struct_imports.values().cloned().collect(),
             uses_generic,
             uses_option,
@@ -1197,20 +1339,6 @@ impl<'a, 'model, HasSource: SourceKind> FunctionIRBuilder<'a, 'model, HasSource>
         }
     }
 
-    fn generate_ts_name(&self, move_name: &str) -> String {
-        // Use from_case(Snake) to prevent digit-letter boundaries from being treated as word splits
-        let ts_name = move_name.from_case(Case::Snake).to_case(Case::Camel);
-
-        let mut sanitized = sanitize_identifier(&ts_name);
-
-        // Handle trailing underscore in Move name
-        if move_name.ends_with('_') && !sanitized.ends_with('_') {
-            sanitized.push('_');
-        }
-
-        sanitized
-    }
-
     fn build_type_params(&self) -> Vec<String> {
         match self.func.kind() {
             model::Kind::WithSource(func) => func
@@ -1409,13 +1537,9 @@ impl<'a, 'model, HasSource: SourceKind> FunctionIRBuilder<'a, 'model, HasSource>
                         ParamTypeIR::Option(Box::new(inner))
                     }
                     _ => {
-                        // Regular struct/enum - only import if $typeName will be used
-                        let class_name = if needs_import {
-                            self.get_import_for_datatype(dt)
-                        } else {
-                            // Don't need import, just use the type name
-                            dt.name.to_string()
-                        };
+                        // Regular struct/enum - always imported, its $typeName tags the
+                        // param type (`ObjectArg<typeof Bar.$typeName>`)
+                        let class_name = self.get_import_for_datatype(dt);
                         let type_args: Vec<_> = dt
                             .type_arguments
                             .iter()
@@ -1424,6 +1548,13 @@ impl<'a, 'model, HasSource: SourceKind> FunctionIRBuilder<'a, 'model, HasSource>
                         ParamTypeIR::Struct {
                             class_name,
                             type_args,
+                            package_info: build_package_info_for_datatype(
+                                dt.module.address,
+                                dt.module.name.as_str(),
+                                dt.name.as_str(),
+                                self.folder_names,
+                            ),
+                            module_type_path: format!("{}::{}", dt.module.name, dt.name),
                         }
                     }
                 }
@@ -1435,9 +1566,12 @@ impl<'a, 'model, HasSource: SourceKind> FunctionIRBuilder<'a, 'model, HasSource>
                     .get(*idx as usize)
                     .cloned()
                     .unwrap_or_else(|| format!("T{}", idx));
+                let is_key =
+                    self.func.maybe_compiled().unwrap().type_parameters[*idx as usize].has_key();
                 ParamTypeIR::TypeParam {
                     name,
                     index: *idx as usize,
+                    is_key,
                 }
             }
             Type::Signer => ParamTypeIR::Primitive("address".to_string()),
@@ -1445,18 +1579,29 @@ impl<'a, 'model, HasSource: SourceKind> FunctionIRBuilder<'a, 'model, HasSource>
     }
 
     /// Build param type for a top-level function parameter.
-    /// Top-level object params don't need imports (just passed to obj()).
+    /// Type arguments of top-level object params don't need imports (just passed to obj()).
     fn build_param_type(&mut self, ty: &Type) -> ParamTypeIR {
         self.build_param_type_inner(ty, false)
     }
 
+    /// Build the return types. All datatypes are imported since their $typeName
+    /// is used in the `TypedResult` type tags.
+    fn build_returns(&mut self) -> Vec<ParamTypeIR> {
+        let compiled = self.func.maybe_compiled().unwrap();
+        compiled
+            .return_
+            .iter()
+            .map(|ty| self.build_param_type_inner(ty, true))
+            .collect()
+    }
+
     fn get_import_for_datatype(
         &mut self,
         dt: &move_binary_format::normalized::Datatype<Symbol>,
     ) -> String {
         let pkg_addr = dt.module.address;
         let mod_name = dt.module.name;
-        let name = dt.name.to_string();
+        let name = datatype_ts_name(self.renames, pkg_addr, mod_name, dt.name);
 
         // Check for duplicate class names (need alias)
         let mut alias = None;
@@ -1558,7 +1703,7 @@ impl<'a, 'model, HasSource: SourceKind> FunctionIRBuilder<'a, 'model, HasSource>
     #[allow(clippy::only_used_in_recursion)]
     fn type_uses_generic(&self, ty: &ParamTypeIR) -> bool {
         match ty {
-            ParamTypeIR::TypeParam { .. } => true,
+            ParamTypeIR::TypeParam { is_key, .. } => !is_key,
             ParamTypeIR::Vector(inner) => self.type_uses_generic(inner),
             ParamTypeIR::Option(inner) => self.type_uses_generic(inner),
             _ => false,
@@ -1586,7 +1731,7 @@ impl<'a, 'model, HasSource: SourceKind> FunctionIRBuilder<'a, 'model, HasSource>
     #[allow(clippy::only_used_in_recursion)]
     fn type_uses_obj(&self, ty: &ParamTypeIR) -> bool {
         match ty {
-            ParamTypeIR::Struct { .. } => true,
+            ParamTypeIR::Struct { .. } | ParamTypeIR::TypeParam { is_key: true, .. } => true,
             ParamTypeIR::Vector(inner) => self.type_uses_obj(inner),
             ParamTypeIR::Option(inner) => self.type_uses_obj(inner),
             _ => false,
@@ -1595,11 +1740,18 @@ impl<'a, 'model, HasSource: SourceKind> FunctionIRBuilder<'a, 'model, HasSource>
 }
 
 /// Generate functions.ts content for a module.
+///
+/// Only functions callable from a transaction (`public` or `entry`) are included,
+/// unless `all_functions` is set, and only those in `selection`.
+#[allow(clippy::too_many_arguments)]
 pub fn gen_module_functions<HasSource: SourceKind>(
     module: &model::Module<HasSource>,
     folder_names: &BTreeMap<AccountAddress, String>,
     top_level_pkg_names: &BTreeMap<AccountAddress, Symbol>,
     levels_from_root: u8,
+    all_functions: bool,
+    selection: &ModuleSelection,
+    renames: &IdentifierRenames,
 ) -> String {
     use super::functions::emit_functions_file;
 
@@ -1609,15 +1761,116 @@ pub fn gen_module_functions<HasSource: SourceKind>(
 
     let functions: Vec<_> = module
         .functions()
+        .filter(|func| selection.functions.contains(&func.name()))
         .filter_map(|func| {
             FunctionIRBuilder::new(func, folder_names, top_level_pkg_names, levels_from_root)
+                .map(|builder| builder.with_renames(renames))
         })
+        .filter(|builder| all_functions || builder.is_callable())
         .map(|builder| builder.build())
         .collect();
 
     emit_functions_file(&functions, &framework_path)
 }
 
+// ============================================================================
+// Constants
+// ============================================================================
+
+use super::constants::{
+    display_value, is_error_constant_name, ts_literal, CleverErrorIndices, ConstantIR,
+    ModuleConstantsIR,
+};
+use move_binary_format::file_format::Bytecode;
+use move_core_types::runtime_value::MoveValue;
+
+/// Build the constants IR for a module.
+///
+/// Named constants (and plain `E*` error codes) are only available for modules with
+/// source. Clever errors are recovered from the bytecode, so they're decoded either way.
+pub fn build_module_constants_ir<HasSource: SourceKind>(
+    module: &model::Module<HasSource>,
+) -> ModuleConstantsIR {
+    let mut ir = ModuleConstantsIR::default();
+
+    for (name, value) in named_constants(module) {
+        let Some(literal) = ts_literal(&value) else {
+            continue;
+        };
+        if let MoveValue::U64(code) = value {
+            if is_error_constant_name(&name) {
+                ir.error_codes.entry(code).or_default().push(name.clone());
+            }
+        }
+        ir.constants.push(ConstantIR {
+            name,
+            value: literal,
+        });
+    }
+
+    let compiled = &module.compiled().module;
+    let constant_value = |idx: u16| {
+        compiled
+            .constant_pool()
+            .get(idx as usize)
+            .and_then(|c| c.deserialize_constant())
+    };
+    for code in clever_error_codes(compiled) {
+        let Some(indices) = CleverErrorIndices::decode(code) else {
+            continue;
+        };
+        if let Some(idx) = indices.identifier {
+            if let Some(name) = constant_value(idx).as_ref().and_then(display_value) {
+                ir.clever_identifiers.insert(idx, name);
+            }
+        }
+        if let Some(idx) = indices.constant {
+            if let Some(message) = constant_value(idx).as_ref().and_then(display_value) {
+                ir.clever_messages.insert(idx, message);
+            }
+        }
+    }
+
+    ir
+}
+
+/// Named constants of a module, in declaration order. Empty for modules without source.
+fn named_constants<HasSource: SourceKind>(
+    module: &model::Module<HasSource>,
+) -> Vec<(String, MoveValue)> {
+    match module.kind() {
+        model::Kind::WithSource(m) => m
+            .named_constants()
+            .map(|c| (c.name().to_string(), c.value().clone()))
+            .collect(),
+        model::Kind::WithoutSource(_) => vec![],
+    }
+}
+
+/// Abort codes with the clever error tag loaded anywhere in the module's bytecode.
+fn clever_error_codes(compiled: &move_binary_format::CompiledModule) -> BTreeSet<u64> {
+    compiled
+        .function_defs()
+        .iter()
+        .filter_map(|def| def.code.as_ref())
+        .flat_map(|code| code.code.iter())
+        .filter_map(|instr| match instr {
+            Bytecode::LdU64(v) if CleverErrorIndices::decode(*v).is_some() => Some(*v),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Generate constants.ts for a module. Returns an empty string if the module has no constants.
+pub fn gen_module_constants<HasSource: SourceKind>(
+    module: &model::Module<HasSource>,
+    levels_from_root: u8,
+) -> String {
+    // constants.ts is at <package>/<module>/constants.ts, so +2 levels from package root
+    let framework_path = compute_framework_path(levels_from_root + 2);
+    build_module_constants_ir(module).emit(&framework_path)
+}
+
 // =============================================================================
 // Test-only helpers for module-level snapshot testing
 // =============================================================================
@@ -12,7 +12,7 @@ use std::fmt;
 
 use super::enums::{EnumIR, EnumVariantIR};
 use super::functions::{FunctionIR, FunctionParamIR, ParamTypeIR};
-use super::structs::{FieldIR, FieldTypeIR, StructIR, TypeParamIR};
+use super::structs::{Ability, FieldIR, FieldTypeIR, StructIR, TypeParamIR};
 
 // ============================================================================
 // Error Types
@@ -87,6 +87,11 @@ pub enum StructIncompatReason {
         env1_phantom: bool,
         env2_phantom: bool,
     },
+    /// Different abilities (e.g. `key` in one env only).
+    AbilitiesMismatch {
+        env1_abilities: String,
+        env2_abilities: String,
+    },
 }
 
 /// Reason why two enums are incompatible.
@@ -146,6 +151,11 @@ pub enum FunctionIncompatReason {
         env1_count: usize,
         env2_count: usize,
     },
+    /// Different return types.
+    ReturnTypeMismatch {
+        env1_types: String,
+        env2_types: String,
+    },
 }
 
 // ============================================================================
@@ -251,6 +261,16 @@ impl fmt::Display for StructIncompatReason {
                     index, env1_phantom, env2_phantom
                 )
             }
+            StructIncompatReason::AbilitiesMismatch {
+                env1_abilities,
+                env2_abilities,
+            } => {
+                write!(
+                    f,
+                    "different abilities ('{}' vs '{}')",
+                    env1_abilities, env2_abilities
+                )
+            }
         }
     }
 }
@@ -355,6 +375,16 @@ impl fmt::Display for FunctionIncompatReason {
                     env1_count, env2_count
                 )
             }
+            FunctionIncompatReason::ReturnTypeMismatch {
+                env1_types,
+                env2_types,
+            } => {
+                write!(
+                    f,
+                    "different return types ('{}' vs '{}')",
+                    env1_types, env2_types
+                )
+            }
         }
     }
 }
@@ -391,9 +421,30 @@ pub fn check_struct_compat(
         });
     }
 
+    // Check abilities (they decide e.g. whether `fetch` is generated)
+    if struct1.abilities != struct2.abilities {
+        return Err(CompatError::StructIncompat {
+            struct_path: struct_path.to_string(),
+            env1: env1.to_string(),
+            env2: env2.to_string(),
+            reason: StructIncompatReason::AbilitiesMismatch {
+                env1_abilities: format_abilities(&struct1.abilities),
+                env2_abilities: format_abilities(&struct2.abilities),
+            },
+        });
+    }
+
     Ok(())
 }
 
+fn format_abilities(abilities: &[Ability]) -> String {
+    abilities
+        .iter()
+        .map(|a| a.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 /// Check if two enums are compatible.
 pub fn check_enum_compat(
     enum1: &EnumIR,
@@ -504,6 +555,29 @@ pub fn check_function_compat(
         }
     }
 
+    // Check return types (they determine the generated TypedResult)
+    let returns_compat = func1.returns.len() == func2.returns.len()
+        && func1
+            .returns
+            .iter()
+            .zip(func2.returns.iter())
+            .all(|(r1, r2)| check_param_type_compat(r1, r2).is_ok());
+    if !returns_compat {
+        let format_returns = |returns: &[ParamTypeIR]| {
+            let types: Vec<_> = returns.iter().map(format_param_type).collect();
+            format!("({})", types.join(", "))
+        };
+        return Err(CompatError::FunctionIncompat {
+            func_path: func_path.to_string(),
+            env1: env1.to_string(),
+            env2: env2.to_string(),
+            reason: FunctionIncompatReason::ReturnTypeMismatch {
+                env1_types: format_returns(&func1.returns),
+                env2_types: format_returns(&func2.returns),
+            },
+        });
+    }
+
     Ok(())
 }
 
@@ -711,10 +785,12 @@ fn check_param_type_compat(type1: &ParamTypeIR, type2: &ParamTypeIR) -> Result<(
             ParamTypeIR::Struct {
                 class_name: name1,
                 type_args: args1,
+                ..
             },
             ParamTypeIR::Struct {
                 class_name: name2,
                 type_args: args2,
+                ..
             },
         ) => {
             // For structs in params, compare class names and type args
@@ -733,10 +809,18 @@ fn check_param_type_compat(type1: &ParamTypeIR, type2: &ParamTypeIR) -> Result<(
             Ok(())
         }
         (
-            ParamTypeIR::TypeParam { index: idx1, .. },
-            ParamTypeIR::TypeParam { index: idx2, .. },
+            ParamTypeIR::TypeParam {
+                index: idx1,
+                is_key: key1,
+                ..
+            },
+            ParamTypeIR::TypeParam {
+                index: idx2,
+                is_key: key2,
+                ..
+            },
         ) => {
-            if idx1 == idx2 {
+            if idx1 == idx2 && key1 == key2 {
                 Ok(())
             } else {
                 Err(format_param_type(type2))
@@ -805,6 +889,7 @@ fn format_param_type(param_type: &ParamTypeIR) -> String {
         ParamTypeIR::Struct {
             class_name,
             type_args,
+            ..
         } => {
             if type_args.is_empty() {
                 class_name.clone()
@@ -814,6 +899,9 @@ fn format_param_type(param_type: &ParamTypeIR) -> String {
             }
         }
         ParamTypeIR::Option(inner) => format!("Option<{}>", format_param_type(inner)),
+        ParamTypeIR::TypeParam {
+            name, is_key: true, ..
+        } => format!("{}: key", name),
         ParamTypeIR::TypeParam { name, .. } => name.clone(),
         ParamTypeIR::StringType { module } => {
             if module == "ascii" {
@@ -856,6 +944,8 @@ mod tests {
             has_non_phantom_type_params: false,
             uses_field_to_json: false,
             doc_comment: None,
+            abilities: vec![Ability::Store],
+            json_validators: false,
         }
     }
 
@@ -972,6 +1062,25 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_different_abilities_is_incompatible() {
+        let struct1 = make_simple_struct("Foo", vec![]);
+        let mut struct2 = make_simple_struct("Foo", vec![]);
+        struct2.abilities = vec![Ability::Key, Ability::Store];
+
+        let err = check_struct_compat(&struct1, &struct2, "env1", "env2", "test::Foo").unwrap_err();
+        assert!(matches!(
+            err,
+            CompatError::StructIncompat {
+                reason: StructIncompatReason::AbilitiesMismatch { .. },
+                ..
+            }
+        ));
+        assert!(err
+            .to_string()
+            .contains("different abilities ('store' vs 'key, store')"));
+    }
+
     #[test]
     fn test_error_message_format() {
         let struct1 = make_simple_struct("Foo", vec![make_primitive_field("value", "u64")]);
@@ -1001,4 +1110,49 @@ mod tests {
             "my_module::MyStruct"
         );
     }
+
+    fn make_function(returns: Vec<ParamTypeIR>) -> FunctionIR {
+        FunctionIR {
+            move_name: "split".to_string(),
+            ts_name: "split".to_string(),
+            module_name: "test".to_string(),
+            env_pkg_name: "test".to_string(),
+            type_params: vec![],
+            params: vec![],
+            returns,
+            returns_reference: false,
+            struct_imports: vec![],
+            uses_generic: false,
+            uses_option: false,
+            aliased_util_imports: vec![],
+            uses_vector: false,
+            uses_pure: false,
+            uses_obj: false,
+            doc_comment: None,
+            is_deprecated: false,
+            deprecation_note: None,
+        }
+    }
+
+    #[test]
+    fn test_different_return_types_are_incompatible() {
+        let u64_ty = || ParamTypeIR::Primitive("u64".to_string());
+        let func1 = make_function(vec![u64_ty()]);
+
+        assert!(check_function_compat(&func1, &func1.clone(), "env1", "env2", "test::split").is_ok());
+
+        let func2 = make_function(vec![u64_ty(), u64_ty()]);
+        let err = check_function_compat(&func1, &func2, "env1", "env2", "test::split").unwrap_err();
+        assert!(err.to_string().contains("different return types ('(u64)' vs '(u64, u64)')"));
+
+        let func3 = make_function(vec![ParamTypeIR::Primitive("u128".to_string())]);
+        let err = check_function_compat(&func1, &func3, "env1", "env2", "test::split").unwrap_err();
+        assert!(matches!(
+            err,
+            CompatError::FunctionIncompat {
+                reason: FunctionIncompatReason::ReturnTypeMismatch { .. },
+                ..
+            }
+        ));
+    }
 }
@@ -0,0 +1,432 @@
+//! Constant IR and emission for `<module>/constants.ts` and abort code decoding.
+//!
+//! Each module with constants gets a `constants.ts` exporting its named constants
+//! plus an `abortCodes` table. `_framework/init-errors.ts` maps every module to a
+//! lazy import of its table, which backs `decodeAbort` in `_framework/errors.ts`.
+
+use std::collections::BTreeMap;
+
+use indoc::formatdoc;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::runtime_value::MoveValue;
+use move_symbol_pool::Symbol;
+
+use super::imports::TsImportsBuilder;
+use super::utils::{module_import_name, sanitize_identifier};
+
+/// Clever error (Move 2024 `#[error]`) abort code layout:
+/// `| tag (1) | reserved (15) | line (16) | identifier index (16) | constant index (16) |`
+const CLEVER_ERROR_TAG: u64 = 0x8000_0000_0000_0000;
+/// Index value meaning "not present" in a clever error abort code.
+const CLEVER_ERROR_NO_INDEX: u16 = 0xffff;
+
+/// Constant pool indices referenced by a clever error abort code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CleverErrorIndices {
+    /// Index of the constant holding the error constant's name
+    pub identifier: Option<u16>,
+    /// Index of the constant holding the error value (usually a message)
+    pub constant: Option<u16>,
+}
+
+impl CleverErrorIndices {
+    /// Decode an abort code, returning `None` if it's not a clever error.
+    pub fn decode(code: u64) -> Option<Self> {
+        if code & CLEVER_ERROR_TAG == 0 {
+            return None;
+        }
+        let index = |shift: u32| {
+            let idx = ((code >> shift) & 0xffff) as u16;
+            (idx != CLEVER_ERROR_NO_INDEX).then_some(idx)
+        };
+        Some(Self {
+            identifier: index(16),
+            constant: index(0),
+        })
+    }
+}
+
+/// A named module constant.
+#[derive(Debug, Clone)]
+pub struct ConstantIR {
+    /// Move constant name
+    pub name: String,
+    /// TypeScript literal of the value
+    pub value: String,
+}
+
+/// Domain-focused IR for a module's constants.ts file.
+#[derive(Debug, Clone, Default)]
+pub struct ModuleConstantsIR {
+    /// Named constants, in declaration order
+    pub constants: Vec<ConstantIR>,
+    /// Plain error codes: u64 value of an `E*` constant -> constant names
+    pub error_codes: BTreeMap<u64, Vec<String>>,
+    /// Clever errors: constant pool index -> error constant name
+    pub clever_identifiers: BTreeMap<u16, String>,
+    /// Clever errors: constant pool index -> error message
+    pub clever_messages: BTreeMap<u16, String>,
+}
+
+impl ModuleConstantsIR {
+    pub fn is_empty(&self) -> bool {
+        self.constants.is_empty()
+            && self.error_codes.is_empty()
+            && self.clever_identifiers.is_empty()
+            && self.clever_messages.is_empty()
+    }
+
+    /// Emit the constants.ts file. `framework_path` is relative to the module directory.
+    pub fn emit(&self, framework_path: &str) -> String {
+        if self.is_empty() {
+            return String::new();
+        }
+
+        let mut imports = TsImportsBuilder::new();
+        imports.add_type_named(format!("{}/errors", framework_path), "ModuleAbortCodes");
+
+        let constants: Vec<String> = self
+            .constants
+            .iter()
+            .map(|c| format!("export const {} = {}", sanitize_identifier(&c.name), c.value))
+            .collect();
+
+        let codes: Vec<String> = self
+            .error_codes
+            .iter()
+            .map(|(code, names)| {
+                let names: Vec<String> = names.iter().map(|n| ts_string(n)).collect();
+                format!("    '{}': [{}],", code, names.join(", "))
+            })
+            .collect();
+        let identifiers: Vec<String> = self
+            .clever_identifiers
+            .iter()
+            .map(|(idx, name)| format!("    {}: {},", idx, ts_string(name)))
+            .collect();
+        let messages: Vec<String> = self
+            .clever_messages
+            .iter()
+            .map(|(idx, message)| format!("    {}: {},", idx, ts_string(message)))
+            .collect();
+
+        let mut sections = vec![imports.emit()];
+        if !constants.is_empty() {
+            sections.push(constants.join("\n"));
+        }
+        sections.push(formatdoc! {"
+            /** Abort codes raised by this module, used by `decodeAbort`. */
+            export const abortCodes: ModuleAbortCodes = {{
+              codes: {{
+            {codes}
+              }},
+              identifiers: {{
+            {identifiers}
+              }},
+              messages: {{
+            {messages}
+              }},
+            }}",
+            codes = codes.join("\n"),
+            identifiers = identifiers.join("\n"),
+            messages = messages.join("\n"),
+        });
+
+        sections.join("\n\n") + "\n"
+    }
+}
+
+/// Whether a constant name follows the Move error constant convention
+/// (`ENotOwner`, `E_NOT_OWNER`, `EINSUFFICIENT_FUNDS`).
+pub fn is_error_constant_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next() == Some('E')
+        && chars
+            .next()
+            .is_some_and(|c| c.is_ascii_uppercase() || c == '_')
+}
+
+/// Render a constant value as a TypeScript literal.
+///
+/// `u8`-`u32` become numbers and `u64`-`u256` bigints. `vector<u8>` becomes a
+/// string if it's valid UTF-8 (as `#[error]` messages are), a number array otherwise.
+pub fn ts_literal(value: &MoveValue) -> Option<String> {
+    Some(match value {
+        MoveValue::Bool(b) => b.to_string(),
+        MoveValue::U8(n) => n.to_string(),
+        MoveValue::U16(n) => n.to_string(),
+        MoveValue::U32(n) => n.to_string(),
+        MoveValue::U64(n) => format!("{}n", n),
+        MoveValue::U128(n) => format!("{}n", n),
+        MoveValue::U256(n) => format!("{}n", n),
+        MoveValue::Address(a) => ts_string(&a.to_hex_literal()),
+        MoveValue::Vector(elems) => match utf8_bytes(elems) {
+            Some(s) => ts_string(&s),
+            None => {
+                let elems = elems.iter().map(ts_literal).collect::<Option<Vec<_>>>()?;
+                format!("[{}]", elems.join(", "))
+            }
+        },
+        _ => return None,
+    })
+}
+
+/// Render a constant value for display in a decoded abort (e.g. an error message).
+pub fn display_value(value: &MoveValue) -> Option<String> {
+    Some(match value {
+        MoveValue::Bool(b) => b.to_string(),
+        MoveValue::U8(n) => n.to_string(),
+        MoveValue::U16(n) => n.to_string(),
+        MoveValue::U32(n) => n.to_string(),
+        MoveValue::U64(n) => n.to_string(),
+        MoveValue::U128(n) => n.to_string(),
+        MoveValue::U256(n) => n.to_string(),
+        MoveValue::Address(a) => a.to_hex_literal(),
+        MoveValue::Vector(elems) => match utf8_bytes(elems) {
+            Some(s) => s,
+            None => {
+                let elems = elems.iter().map(display_value).collect::<Option<Vec<_>>>()?;
+                format!("[{}]", elems.join(", "))
+            }
+        },
+        _ => return None,
+    })
+}
+
+/// Decode a `vector<u8>` as a UTF-8 string. Returns `None` for other vectors.
+fn utf8_bytes(elems: &[MoveValue]) -> Option<String> {
+    let bytes = elems
+        .iter()
+        .map(|e| match e {
+            MoveValue::U8(b) => Some(*b),
+            _ => None,
+        })
+        .collect::<Option<Vec<u8>>>()?;
+    if bytes.is_empty() {
+        return None;
+    }
+    String::from_utf8(bytes).ok()
+}
+
+/// Quote a string as a TypeScript string literal.
+fn ts_string(s: &str) -> String {
+    serde_json::to_string(s).expect("strings always serialize")
+}
+
+// ============================================================================
+// Init Errors IR (for _framework/init-errors.ts)
+// ============================================================================
+
+/// A module whose abort codes are registered in init-errors.ts.
+struct ModuleRef {
+    /// Import path to the module's constants.ts
+    import_path: String,
+    /// Move module name
+    module_name: String,
+}
+
+/// Domain-focused IR for the init-errors.ts file.
+pub struct InitErrorsIR {
+    /// Kebab-case package name -> modules with constants
+    packages: BTreeMap<String, Vec<ModuleRef>>,
+}
+
+impl InitErrorsIR {
+    /// Build the IR from the modules that have a constants.ts, per package.
+    pub fn new(
+        modules: &BTreeMap<AccountAddress, Vec<Symbol>>,
+        folder_names: &BTreeMap<AccountAddress, String>,
+        top_level_pkg_names: &BTreeMap<AccountAddress, Symbol>,
+    ) -> Self {
+        let mut packages: BTreeMap<String, Vec<ModuleRef>> = BTreeMap::new();
+        for (pkg_id, module_names) in modules {
+            let folder_name = folder_names
+                .get(pkg_id)
+                .expect("All packages should have folder names");
+            let pkg_path = if top_level_pkg_names.contains_key(pkg_id) {
+                format!("../{}", folder_name)
+            } else {
+                format!("../_dependencies/{}", folder_name)
+            };
+
+            let refs = module_names
+                .iter()
+                .map(|module_name| ModuleRef {
+                    import_path: format!(
+                        "{}/{}/constants",
+                        pkg_path,
+                        module_import_name(*module_name)
+                    ),
+                    module_name: module_name.to_string(),
+                })
+                .collect();
+            packages.insert(folder_name.clone(), refs);
+        }
+        InitErrorsIR { packages }
+    }
+
+    /// Emit as TypeScript code.
+    ///
+    /// Modules are imported lazily so that bundles only include the constants of
+    /// modules whose aborts are actually decoded.
+    pub fn emit(&self) -> String {
+        let entries: Vec<String> = self
+            .packages
+            .iter()
+            .map(|(pkg_name, modules)| {
+                let modules: Vec<String> = modules
+                    .iter()
+                    .map(|m| {
+                        format!(
+                            "    {}: () => import('{}').then(m => m.abortCodes),",
+                            m.module_name, m.import_path
+                        )
+                    })
+                    .collect();
+                format!("  {}: {{\n{}\n  }},", ts_string(pkg_name), modules.join("\n"))
+            })
+            .collect();
+
+        formatdoc! {"
+            import type {{ ModuleAbortCodes }} from './errors'
+
+            /** Lazy loaders of the abort codes of all generated modules: package name -> module name -> loader. */
+            export const abortCodeLoaders: Record<string, Record<string, () => Promise<ModuleAbortCodes>>> = {{
+            {entries}
+            }}
+        ",
+            entries = entries.join("\n"),
+        }
+    }
+}
+
+/// Generate _framework/init-errors.ts.
+pub fn gen_init_errors(
+    modules: &BTreeMap<AccountAddress, Vec<Symbol>>,
+    folder_names: &BTreeMap<AccountAddress, String>,
+    top_level_pkg_names: &BTreeMap<AccountAddress, Symbol>,
+) -> String {
+    InitErrorsIR::new(modules, folder_names, top_level_pkg_names).emit()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bytes(s: &str) -> MoveValue {
+        MoveValue::Vector(s.bytes().map(MoveValue::U8).collect())
+    }
+
+    #[test]
+    fn test_clever_error_decode() {
+        // line 42, identifier at index 3, constant at index 4
+        let code = CLEVER_ERROR_TAG | (42 << 32) | (3 << 16) | 4;
+        assert_eq!(
+            CleverErrorIndices::decode(code),
+            Some(CleverErrorIndices {
+                identifier: Some(3),
+                constant: Some(4),
+            })
+        );
+
+        // No constant (e.g. `assert!(cond)` without an error constant)
+        let code = CLEVER_ERROR_TAG | (7 << 32) | 0xffff_ffff;
+        assert_eq!(
+            CleverErrorIndices::decode(code),
+            Some(CleverErrorIndices {
+                identifier: None,
+                constant: None,
+            })
+        );
+
+        assert_eq!(CleverErrorIndices::decode(1), None);
+    }
+
+    #[test]
+    fn test_is_error_constant_name() {
+        assert!(is_error_constant_name("ENotOwner"));
+        assert!(is_error_constant_name("E_NOT_OWNER"));
+        assert!(is_error_constant_name("EINSUFFICIENT"));
+        assert!(!is_error_constant_name("E"));
+        assert!(!is_error_constant_name("Each"));
+        assert!(!is_error_constant_name("MAX_SUPPLY"));
+    }
+
+    #[test]
+    fn test_ts_literal() {
+        assert_eq!(ts_literal(&MoveValue::U8(1)).unwrap(), "1");
+        assert_eq!(ts_literal(&MoveValue::U64(1)).unwrap(), "1n");
+        assert_eq!(ts_literal(&MoveValue::Bool(true)).unwrap(), "true");
+        assert_eq!(
+            ts_literal(&MoveValue::Address(AccountAddress::TWO)).unwrap(),
+            "\"0x2\""
+        );
+        assert_eq!(ts_literal(&bytes("it's \"bad\"")).unwrap(), r#""it's \"bad\"""#);
+        assert_eq!(
+            ts_literal(&MoveValue::Vector(vec![MoveValue::U8(0xff), MoveValue::U8(0)])).unwrap(),
+            "[255, 0]"
+        );
+        assert_eq!(
+            ts_literal(&MoveValue::Vector(vec![MoveValue::U64(1), MoveValue::U64(2)])).unwrap(),
+            "[1n, 2n]"
+        );
+        assert_eq!(display_value(&bytes("Not the owner")).unwrap(), "Not the owner");
+    }
+
+    #[test]
+    fn test_module_constants_emit() {
+        let ir = ModuleConstantsIR {
+            constants: vec![
+                ConstantIR {
+                    name: "ENotOwner".to_string(),
+                    value: "0n".to_string(),
+                },
+                ConstantIR {
+                    name: "EInvalidAmount".to_string(),
+                    value: "\"Amount must be positive\"".to_string(),
+                },
+            ],
+            error_codes: BTreeMap::from([(0, vec!["ENotOwner".to_string()])]),
+            clever_identifiers: BTreeMap::from([(3, "EInvalidAmount".to_string())]),
+            clever_messages: BTreeMap::from([(4, "Amount must be positive".to_string())]),
+        };
+
+        let output = ir.emit("../../_framework");
+        assert!(output.contains("import type { ModuleAbortCodes } from '../../_framework/errors'"));
+        assert!(output.contains("export const ENotOwner = 0n"));
+        assert!(output.contains("export const EInvalidAmount = \"Amount must be positive\""));
+        assert!(output.contains("export const abortCodes: ModuleAbortCodes"));
+        assert!(output.contains("'0': [\"ENotOwner\"],"));
+        assert!(output.contains("3: \"EInvalidAmount\","));
+        assert!(output.contains("4: \"Amount must be positive\","));
+
+        assert!(ModuleConstantsIR::default().emit("../../_framework").is_empty());
+    }
+
+    #[test]
+    fn test_init_errors_emit() {
+        let sui = AccountAddress::TWO;
+        let app = AccountAddress::from_hex_literal("0xabc").unwrap();
+        let folder_names = BTreeMap::from([(sui, "sui".to_string()), (app, "my-app".to_string())]);
+        let top_level = BTreeMap::from([(app, Symbol::from("my_app"))]);
+        let modules = BTreeMap::from([
+            (sui, vec![Symbol::from("balance")]),
+            (app, vec![Symbol::from("pool_admin")]),
+        ]);
+
+        let output = gen_init_errors(&modules, &folder_names, &top_level);
+        // Constants are only loaded when needed, never imported statically
+        assert!(!output.contains("import * as"));
+        assert!(output.contains(
+            "\"my-app\": {\n    pool_admin: () => import('../my-app/pool-admin/constants').then(m => m.abortCodes),"
+        ));
+        assert!(output.contains(
+            "\"sui\": {\n    balance: () => import('../_dependencies/sui/balance/constants').then(m => m.abortCodes),"
+        ));
+
+        let empty = gen_init_errors(&BTreeMap::new(), &folder_names, &top_level);
+        assert!(empty.contains(
+            "export const abortCodeLoaders: Record<string, Record<string, () => Promise<ModuleAbortCodes>>> = {\n\n}"
+        ));
+    }
+}
@@ -10,7 +10,8 @@ use indoc::formatdoc;
 use super::doc_utils::process_doc_string;
 use super::jsdoc::format_jsdoc;
 use super::structs::{
-    is_balance_type, is_option_type, is_primitive_like_type, FieldIR, PackageInfo, TypeParamIR,
+    is_balance_type, is_option_type, is_primitive_like_type, Ability, FieldIR, PackageInfo,
+    TypeParamIR,
 };
 
 /// Represents a Move enum for TypeScript code generation.
@@ -34,6 +35,10 @@ pub struct EnumIR {
     pub uses_phantom_struct_args: bool,
     /// Enum-level documentation from Move source
     pub doc_comment: Option<String>,
+    /// Abilities of the enum, in declaration order (copy, drop, store)
+    pub abilities: Vec<Ability>,
+    /// Whether to emit a JSON validator and run it in `fromJSON` (`json-validators` config)
+    pub json_validators: bool,
 }
 
 /// Represents a single enum variant.
@@ -85,6 +90,11 @@ impl EnumIR {
         // Union type for all variant JSON types
         sections.push(self.emit_variant_json_union());
 
+        // JSON validator (opt-in)
+        if self.json_validators {
+            sections.push(self.emit_json_validator());
+        }
+
         // Variant name type
         sections.push(self.emit_variant_name_type());
 
@@ -348,6 +358,7 @@ impl EnumIR {
                   {type_name_decl}
                   static readonly $numTypeParams = {num_type_params}
                   static readonly $isPhantom = {is_phantom_array} as const
+                  static readonly $abilities = {abilities} as const
 
                   static reified{reified_type_params}(
                     {reified_args_list}
@@ -369,7 +380,7 @@ impl EnumIR {
                       fromBcs: (data: Uint8Array) => {name}.fromBcs([{reified_arg_names}], data),
                       bcs: reifiedBcs,
                       fromJSONField: (field: any) => {name}.fromJSONField([{reified_arg_names}], field),
-                      fromJSON: (json: Record<string, any>) => {name}.fromJSON([{reified_arg_names}], json),
+                      fromJSON: (json: Record<string, any>) => {name}.fromJSON([{reified_arg_names}], json),{validator_member}
                       new: (
                         variant: {name}VariantName,
                         fields: {name}Fields{to_type_args}
@@ -466,7 +477,7 @@ impl EnumIR {
                       composeSuiType({name}.$typeName, ...{type_arg_map_extract}),
                       json.$typeArgs,
                       {type_arg_param}
-                    )
+                    ){validate_json}
 
                     return {name}.fromJSONField({type_arg_param}, json)
                   }}
@@ -476,6 +487,7 @@ impl EnumIR {
                 type_name_decl = type_name_decl,
                 num_type_params = num_type_params,
                 is_phantom_array = is_phantom_array,
+                abilities = self.emit_abilities_array(),
                 reified_type_params = reified_type_params,
                 reified_args_list = reified_args_list,
                 reified_arg_names = reified_arg_names,
@@ -494,6 +506,8 @@ impl EnumIR {
                 type_arg_param = self.emit_type_arg_param(),
                 type_arg_type = self.emit_type_arg_type(),
                 type_arg_map_extract = self.emit_type_arg_map_extract(),
+                validator_member = self.emit_json_validator_reified_member(),
+                validate_json = self.emit_from_json_validation(),
             }
         }
     }
@@ -515,6 +529,7 @@ impl EnumIR {
               {type_name_decl}
               static readonly $numTypeParams = 0
               static readonly $isPhantom = [] as const
+              static readonly $abilities = {abilities} as const
 
               static reified(): {name}Reified {{
                 const reifiedBcs = {name}.bcs
@@ -529,7 +544,7 @@ impl EnumIR {
                   fromBcs: (data: Uint8Array) => {name}.fromBcs([], data),
                   bcs: reifiedBcs,
                   fromJSONField: (field: any) => {name}.fromJSONField([], field),
-                  fromJSON: (json: Record<string, any>) => {name}.fromJSON([], json),
+                  fromJSON: (json: Record<string, any>) => {name}.fromJSON([], json),{validator_member}
                   new: (variant: {name}VariantName, fields: {name}Fields) => {{
                     switch (variant) {{
             {new_switch_cases}
@@ -600,7 +615,7 @@ impl EnumIR {
               static fromJSON(typeArgs: [], json: Record<string, any>): {name}Variant {{
                 if (json.$typeName !== {name}.$typeName) {{
                   throw new Error(`not a {name} json object: expected '${{{name}.$typeName}}' but got '${{json.$typeName}}'`)
-                }}
+                }}{validate_json}
 
                 return {name}.fromJSONField(typeArgs, json)
               }}
@@ -609,11 +624,129 @@ impl EnumIR {
             name_lower = name.to_lowercase(),
             type_name_decl = type_name_decl,
             full_type_template = full_type_template,
+            abilities = self.emit_abilities_array(),
             bcs_section = bcs_section,
             new_switch_cases = new_switch_cases,
             from_fields_switch = from_fields_switch,
             from_fields_with_types_switch = from_fields_with_types_switch,
             from_json_field_switch = from_json_field_switch,
+            validator_member = self.emit_json_validator_reified_member(),
+            validate_json = self.emit_from_json_validation(),
+        }
+    }
+
+    /// Emit `validate<Name>JSONField`, which checks the `$kind` of a variant JSON value and
+    /// then its fields (see `validateJSONField` in `_framework/validate.ts`).
+    fn emit_json_validator(&self) -> String {
+        let num_type_params = self.type_params.len();
+        let cases: Vec<String> = self
+            .variants
+            .iter()
+            .map(|v| {
+                let reified =
+                    |f: &FieldIR| f.field_type.to_reified_runtime_indexed(num_type_params);
+                if v.is_unit() {
+                    format!("    case '{}':\n      return", v.name)
+                } else if v.is_tuple {
+                    let checks: Vec<String> = v
+                        .fields
+                        .iter()
+                        .enumerate()
+                        .map(|(i, f)| {
+                            format!(
+                                "      validateJSONField({}, obj.vec[{}], `${{path}}.vec[{}]`)",
+                                reified(f),
+                                i,
+                                i
+                            )
+                        })
+                        .collect();
+                    format!(
+                        "    case '{name}':\n      if (!Array.isArray(obj.vec) || obj.vec.length !== {len}) {{\n        throw new JSONValidationError(`${{path}}.vec`, 'array of {len} elements', obj.vec)\n      }}\n{checks}\n      return",
+                        name = v.name,
+                        len = v.fields.len(),
+                        checks = checks.join("\n"),
+                    )
+                } else {
+                    let checks: Vec<String> = v
+                        .fields
+                        .iter()
+                        .map(|f| {
+                            format!(
+                                "      validateJSONField({}, obj.{}, `${{path}}.{}`)",
+                                reified(f),
+                                f.ts_name,
+                                f.ts_name
+                            )
+                        })
+                        .collect();
+                    format!("    case '{}':\n{}\n      return", v.name, checks.join("\n"))
+                }
+            })
+            .collect();
+        let kinds: Vec<String> = self
+            .variants
+            .iter()
+            .map(|v| format!("'{}'", v.name))
+            .collect();
+
+        let signature = if num_type_params == 0 {
+            format!(
+                "export function validate{}JSONField(field: unknown, path = '$'): void {{",
+                self.name
+            )
+        } else {
+            format!(
+                "export function validate{}JSONField{}(\n  typeArgs: {},\n  field: unknown,\n  path = '$'\n): void {{",
+                self.name,
+                self.emit_reified_type_params(),
+                self.emit_type_arg_type()
+            )
+        };
+
+        formatdoc! {r#"
+            /** Checks that `field` is a valid `{name}VariantJSON` field, throwing a `JSONValidationError` otherwise. */
+            {signature}
+              const obj = validateObject(field, path) as Record<string, any>
+              switch (obj.$kind) {{
+            {cases}
+                default:
+                  throw new JSONValidationError(`${{path}}.$kind`, "{kinds}", obj.$kind)
+              }}
+            }}"#,
+            name = self.name,
+            signature = signature,
+            cases = cases.join("\n"),
+            kinds = kinds.join(" | "),
+        }
+    }
+
+    /// Emit the `validateJSONField` member of the reified object (empty unless JSON validators
+    /// are enabled).
+    fn emit_json_validator_reified_member(&self) -> String {
+        if !self.json_validators {
+            return String::new();
+        }
+        let args = if self.type_params.is_empty() {
+            String::new()
+        } else {
+            format!("[{}], ", self.emit_reified_arg_names())
+        };
+        format!(
+            "\n      validateJSONField: (field: unknown, path: string) => validate{}JSONField({}field, path),",
+            self.name, args
+        )
+    }
+
+    /// Emit the validator call of `fromJSON` (empty unless JSON validators are enabled).
+    fn emit_from_json_validation(&self) -> String {
+        if !self.json_validators {
+            return String::new();
+        }
+        if self.type_params.is_empty() {
+            format!("\n    validate{}JSONField(json)", self.name)
+        } else {
+            format!("\n    validate{}JSONField(typeArgs, json)", self.name)
         }
     }
 
@@ -1165,6 +1298,16 @@ impl EnumIR {
         }
     }
 
+    /// Emit the `$abilities` array, e.g. `['copy', 'drop', 'store']`.
+    fn emit_abilities_array(&self) -> String {
+        let names: Vec<String> = self
+            .abilities
+            .iter()
+            .map(|a| format!("'{}'", a.as_str()))
+            .collect();
+        format!("[{}]", names.join(", "))
+    }
+
     fn emit_is_phantom_array(&self) -> String {
         let values: Vec<&str> = self
             .type_params
@@ -1713,6 +1856,8 @@ mod tests {
             uses_address: false,
             uses_phantom_struct_args: false,
             doc_comment: None,
+            abilities: vec![Ability::Copy, Ability::Drop, Ability::Store],
+            json_validators: false,
         };
 
         let output = enum_ir.emit_body();
@@ -1720,5 +1865,6 @@ mod tests {
         assert!(output.contains("StatusVariant"));
         assert!(output.contains("StatusActive"));
         assert!(output.contains("StatusInactive"));
+        assert!(output.contains("static readonly $abilities = ['copy', 'drop', 'store'] as const"));
     }
 }
@@ -0,0 +1,345 @@
+//! Event file generation (events.ts).
+//!
+//! Event types are the structs a package passes to `sui::event::emit`, found by
+//! scanning the bytecode of its functions. Sui only allows emitting types defined
+//! in the calling module, so each module is scanned for its own types.
+
+use std::collections::BTreeSet;
+
+use convert_case::{Case, Casing};
+use indoc::formatdoc;
+use move_binary_format::file_format::{Bytecode, SignatureToken};
+use move_binary_format::CompiledModule;
+use move_core_types::account_address::AccountAddress;
+use move_model_2::model;
+use move_model_2::source_kind::SourceKind;
+
+use super::utils::{datatype_ts_name, module_import_name, sanitize_identifier, IdentifierRenames};
+use crate::filter::Selection;
+
+/// Functions of `sui::event` that emit their type argument as an event.
+const EMIT_FUNCTIONS: &[&str] = &["emit", "emit_authenticated"];
+
+/// Top-level names in events.ts, which the module import aliases must not shadow.
+const RESERVED_NAMES: &[&str] = &[
+    "MoveEvent",
+    "EnvConfig",
+    "loader",
+    "toActiveTypeName",
+    "compressSuiType",
+    "parseTypeName",
+    "PackageEvent",
+    "PackageEventKind",
+    "eventClasses",
+    "decodeEvent",
+];
+
+/// A single event type in events.ts.
+#[derive(Debug, Clone)]
+struct EventIR {
+    /// Import path for the module (e.g., "./pool/structs")
+    import_path: String,
+    /// Alias used for the module import (e.g., "pool")
+    module_alias: String,
+    /// Move module name
+    module_name: String,
+    /// Move name of the event struct (e.g., "PoolCreationEvent")
+    struct_name: String,
+    /// Name of the generated class, which differs from `struct_name` if renamed
+    class_name: String,
+    /// Number of type parameters of the struct
+    num_type_params: usize,
+}
+
+impl EventIR {
+    /// Discriminant of the event in the `PackageEvent` union (e.g., "pool::PoolCreationEvent")
+    fn kind(&self) -> String {
+        format!("{}::{}", self.module_name, self.struct_name)
+    }
+
+    /// TS type of a decoded event (e.g., "pool.Deposit<any, any>")
+    fn ts_type(&self) -> String {
+        let class = format!("{}.{}", self.module_alias, self.class_name);
+        if self.num_type_params == 0 {
+            class
+        } else {
+            format!(
+                "{}<{}>",
+                class,
+                vec!["any"; self.num_type_params].join(", ")
+            )
+        }
+    }
+}
+
+/// Domain-focused IR for a package's events.ts file.
+pub struct PackageEventsIR {
+    /// Path to the framework directory (e.g., "../_framework")
+    framework_path: String,
+    /// Event types, in module and declaration order
+    events: Vec<EventIR>,
+}
+
+impl PackageEventsIR {
+    /// Build the IR from a Move package, including only the event types kept by `selection`.
+    pub fn from_package<HasSource: SourceKind>(
+        pkg: &model::Package<HasSource>,
+        framework_path: &str,
+        selection: &Selection,
+        renames: &IdentifierRenames,
+    ) -> Self {
+        let mut events = Vec::new();
+        let mut aliases = BTreeSet::new();
+
+        for mod_ in pkg.modules() {
+            let emitted = emitted_event_types(&mod_.compiled().module);
+            if emitted.is_empty() {
+                continue;
+            }
+
+            let import_path = format!("./{}/structs", module_import_name(mod_.name()));
+            let module_name = mod_.name().to_string();
+            let alias = module_alias(&module_name, &aliases);
+            aliases.insert(alias.clone());
+
+            for strct in mod_.structs() {
+                if !emitted.contains(strct.name().as_str())
+                    || !selection.keeps_datatype(pkg.address(), mod_.name(), strct.name())
+                {
+                    continue;
+                }
+                events.push(EventIR {
+                    import_path: import_path.clone(),
+                    module_alias: alias.clone(),
+                    module_name: module_name.clone(),
+                    struct_name: strct.name().to_string(),
+                    class_name: datatype_ts_name(
+                        Some(renames),
+                        pkg.address(),
+                        mod_.name(),
+                        strct.name(),
+                    ),
+                    num_type_params: strct.compiled().type_parameters.len(),
+                });
+            }
+        }
+
+        PackageEventsIR {
+            framework_path: framework_path.to_string(),
+            events,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Emit as TypeScript code. Returns an empty string if the package emits no events.
+    pub fn emit(&self) -> String {
+        if self.is_empty() {
+            return String::new();
+        }
+
+        let mut module_imports: Vec<(&str, &str)> = self
+            .events
+            .iter()
+            .map(|e| (e.module_alias.as_str(), e.import_path.as_str()))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        module_imports.sort_by(|a, b| a.1.cmp(b.1));
+
+        let import_lines: Vec<String> = module_imports
+            .iter()
+            .map(|(alias, path)| format!("import * as {} from '{}'", alias, path))
+            .collect();
+
+        let union_members: Vec<String> = self
+            .events
+            .iter()
+            .map(|e| format!("  | {{ kind: '{}'; event: {} }}", e.kind(), e.ts_type()))
+            .collect();
+
+        let class_entries: Vec<String> = self
+            .events
+            .iter()
+            .map(|e| format!("  '{}': {}.{},", e.kind(), e.module_alias, e.class_name))
+            .collect();
+
+        formatdoc! {"
+            {imports}
+            import type {{ EnvConfig }} from '{framework}/env'
+            import type {{ MoveEvent }} from '{framework}/util'
+            import {{ loader, toActiveTypeName }} from '{framework}/loader'
+            import {{ compressSuiType, parseTypeName }} from '{framework}/util'
+
+            /** Events emitted by this package, discriminated by `kind` (`module::Struct`). */
+            export type PackageEvent =
+            {union}
+
+            export type PackageEventKind = PackageEvent['kind']
+
+            const eventClasses = {{
+            {classes}
+            }} as const
+
+            /**
+             * Decode an event emitted by this package. If `env` is given, the event type's
+             * addresses are those of `env` rather than of the active environment.
+             *
+             * @returns The decoded event, or `undefined` if the event type doesn't belong to this package.
+             */
+            export function decodeEvent(event: MoveEvent, env?: EnvConfig): PackageEvent | undefined {{
+              const {{ typeName }} = parseTypeName(compressSuiType(event.eventType))
+              const activeTypeName = env ? toActiveTypeName(typeName, env) : typeName
+              const kind = (Object.keys(eventClasses) as PackageEventKind[]).find(
+                k => eventClasses[k].$typeName === activeTypeName
+              )
+              if (kind === undefined) {{
+                return undefined
+              }}
+              const reified = loader.reified(event.eventType, env)
+              return {{ kind, event: reified.fromBcs(event.bcs) }} as PackageEvent
+            }}
+        ",
+            imports = import_lines.join("\n"),
+            framework = self.framework_path,
+            union = union_members.join("\n"),
+            classes = class_entries.join("\n"),
+        }
+    }
+}
+
+/// Alias of a module's import: its camelCase name, with `_` appended while it's a
+/// reserved name or the alias of another module.
+fn module_alias(module_name: &str, taken: &BTreeSet<String>) -> String {
+    let mut alias = sanitize_identifier(&module_name.from_case(Case::Snake).to_case(Case::Camel));
+    while RESERVED_NAMES.contains(&alias.as_str()) || taken.contains(&alias) {
+        alias.push('_');
+    }
+    alias
+}
+
+/// Names of the module's own datatypes passed to `sui::event::emit` anywhere in its bytecode.
+fn emitted_event_types(compiled: &CompiledModule) -> BTreeSet<String> {
+    let self_handle = compiled.self_handle_idx();
+    let mut types = BTreeSet::new();
+
+    let instrs = compiled
+        .function_defs()
+        .iter()
+        .filter_map(|def| def.code.as_ref())
+        .flat_map(|code| code.code.iter());
+    for instr in instrs {
+        let Bytecode::CallGeneric(idx) = instr else {
+            continue;
+        };
+        let inst = compiled.function_instantiation_at(*idx);
+        let handle = compiled.function_handle_at(inst.handle);
+        let module = compiled.module_handle_at(handle.module);
+        if *compiled.address_identifier_at(module.address) != AccountAddress::TWO
+            || compiled.identifier_at(module.name).as_str() != "event"
+            || !EMIT_FUNCTIONS.contains(&compiled.identifier_at(handle.name).as_str())
+        {
+            continue;
+        }
+
+        let datatype = match compiled.signature_at(inst.type_parameters).0.first() {
+            Some(SignatureToken::Datatype(idx)) => *idx,
+            Some(SignatureToken::DatatypeInstantiation(inst)) => inst.0,
+            _ => continue,
+        };
+        let datatype = compiled.datatype_handle_at(datatype);
+        if datatype.module == self_handle {
+            types.insert(compiled.identifier_at(datatype.name).to_string());
+        }
+    }
+
+    types
+}
+
+/// Generate events.ts for a package. Returns an empty string if the package emits no events.
+pub fn gen_package_events<HasSource: SourceKind>(
+    pkg: &model::Package<HasSource>,
+    framework_rel_path: &str,
+    selection: &Selection,
+    renames: &IdentifierRenames,
+) -> String {
+    PackageEventsIR::from_package(pkg, framework_rel_path, selection, renames).emit()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(module: &str, struct_name: &str, num_type_params: usize) -> EventIR {
+        EventIR {
+            import_path: format!("./{}/structs", module),
+            module_alias: module.to_string(),
+            module_name: module.to_string(),
+            struct_name: struct_name.to_string(),
+            class_name: struct_name.to_string(),
+            num_type_params,
+        }
+    }
+
+    #[test]
+    fn test_package_events_emit() {
+        let ir = PackageEventsIR {
+            framework_path: "../_framework".to_string(),
+            events: vec![
+                event("pool", "PoolCreationEvent", 0),
+                event("pool", "SwapEvent", 2),
+                event("admin", "AdminChanged", 0),
+            ],
+        };
+
+        let output = ir.emit();
+        assert!(output.contains(
+            "import * as admin from './admin/structs'\nimport * as pool from './pool/structs'"
+        ));
+        assert!(output
+            .contains("  | { kind: 'pool::PoolCreationEvent'; event: pool.PoolCreationEvent }"));
+        assert!(output.contains("  | { kind: 'pool::SwapEvent'; event: pool.SwapEvent<any, any> }"));
+        assert!(output.contains("  'admin::AdminChanged': admin.AdminChanged,"));
+        assert!(output.contains(
+            "export function decodeEvent(event: MoveEvent, env?: EnvConfig): PackageEvent | undefined"
+        ));
+        assert!(output.contains("const reified = loader.reified(event.eventType, env)"));
+    }
+
+    #[test]
+    fn test_package_events_renamed_class() {
+        let ir = PackageEventsIR {
+            framework_path: "../_framework".to_string(),
+            events: vec![EventIR {
+                class_name: "PoolCreated".to_string(),
+                ..event("pool", "PoolCreationEvent", 0)
+            }],
+        };
+
+        let output = ir.emit();
+        assert!(output.contains("  | { kind: 'pool::PoolCreationEvent'; event: pool.PoolCreated }"));
+        assert!(output.contains("  'pool::PoolCreationEvent': pool.PoolCreated,"));
+    }
+
+    #[test]
+    fn test_module_alias_avoids_reserved_and_taken_names() {
+        let mut taken = BTreeSet::new();
+        assert_eq!(module_alias("pool_events", &taken), "poolEvents");
+        assert_eq!(module_alias("loader", &taken), "loader_");
+        assert_eq!(module_alias("decode_event", &taken), "decodeEvent_");
+
+        taken.insert("fooBar".to_string());
+        assert_eq!(module_alias("foo_bar", &taken), "fooBar_");
+    }
+
+    #[test]
+    fn test_package_events_empty() {
+        let ir = PackageEventsIR {
+            framework_path: "../_framework".to_string(),
+            events: vec![],
+        };
+        assert_eq!(ir.emit(), "");
+    }
+}
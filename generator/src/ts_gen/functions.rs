@@ -6,7 +6,8 @@ use convert_case::{Case, Casing};
 use indoc::formatdoc;
 
 use super::jsdoc::format_jsdoc;
-use super::utils::is_reserved_word;
+use super::structs::{Ability, PackageInfo};
+use super::utils::{function_ts_name, is_reserved_word};
 
 /// Represents a function parameter's type for transaction arguments.
 #[derive(Debug, Clone)]
@@ -19,11 +20,20 @@ pub enum ParamTypeIR {
     Struct {
         class_name: String,
         type_args: Vec<ParamTypeIR>,
+        /// Where the type's address comes from
+        package_info: PackageInfo,
+        /// "module::TypeName" path of the type
+        module_type_path: String,
     },
     /// Option<T> - special handling for pure vs object options
     Option(Box<ParamTypeIR>),
     /// A type parameter (T0, T1, etc.)
-    TypeParam { name: String, index: usize },
+    TypeParam {
+        name: String,
+        index: usize,
+        /// Whether the type parameter has the `key` ability, i.e. values are objects
+        is_key: bool,
+    },
     /// String types (0x1::string::String, 0x1::ascii::String)
     StringType { module: String },
     /// ID type (0x2::object::ID)
@@ -55,8 +65,7 @@ impl ParamTypeIR {
 
         match self {
             // These types don't get | TransactionArgument suffix
-            ParamTypeIR::Struct { .. } => "TransactionObjectInput".to_string(),
-            ParamTypeIR::TypeParam { .. } => "GenericArg".to_string(),
+            ParamTypeIR::Struct { .. } | ParamTypeIR::TypeParam { .. } => base,
             // Option: base already includes | TransactionArgument from inner, just add | null
             ParamTypeIR::Option(_) => format!("{} | null", base),
             // Everything else: add | TransactionArgument
@@ -80,10 +89,16 @@ impl ParamTypeIR {
             ParamTypeIR::Vector(inner) => {
                 format!("Array<{}>", inner.to_ts_param_type())
             }
-            ParamTypeIR::Struct { .. } => "TransactionObjectInput".to_string(),
+            // Objects are tagged with their Move type so that typed results of
+            // other calls holding a different type are rejected
+            ParamTypeIR::Struct { class_name, .. } => {
+                format!("ObjectArg<typeof {}.$typeName>", class_name)
+            }
             // Option: return inner's full param type (includes | TransactionArgument)
             // The | null is added by to_ts_param_type, not here
             ParamTypeIR::Option(inner) => inner.to_ts_param_type(),
+            // Type params constrained to `key` can only be objects
+            ParamTypeIR::TypeParam { is_key: true, .. } => "ObjectArg".to_string(),
             ParamTypeIR::TypeParam { .. } => "GenericArg".to_string(),
             ParamTypeIR::StringType { .. } | ParamTypeIR::ID => "string".to_string(),
         }
@@ -99,6 +114,7 @@ impl ParamTypeIR {
             ParamTypeIR::Struct {
                 class_name,
                 type_args,
+                ..
             } => {
                 if type_args.is_empty() {
                     format!("${{{}.$typeName}}", class_name)
@@ -118,9 +134,9 @@ impl ParamTypeIR {
             }
             ParamTypeIR::TypeParam { index, .. } => {
                 if type_arg_count == 1 {
-                    "${typeArg}".to_string()
+                    "${resolvedTypeArg}".to_string()
                 } else {
-                    format!("${{typeArgs[{}]}}", index)
+                    format!("${{resolvedTypeArgs[{}]}}", index)
                 }
             }
             ParamTypeIR::StringType { module } => {
@@ -135,6 +151,161 @@ impl ParamTypeIR {
         }
     }
 
+    /// Generate the full Move type of a return value with the type origins resolved in
+    /// `options?.env` (used by the view variant).
+    pub fn to_move_type_in_env(&self, type_arg_count: usize) -> String {
+        match self {
+            ParamTypeIR::Primitive(p) => p.clone(),
+            ParamTypeIR::Vector(inner) => {
+                format!("vector<{}>", inner.to_move_type_in_env(type_arg_count))
+            }
+            ParamTypeIR::Struct {
+                type_args,
+                package_info,
+                module_type_path,
+                ..
+            } => {
+                let address = match package_info {
+                    PackageInfo::System { address } => address.clone(),
+                    PackageInfo::Dynamic { pkg_name, .. } => format!(
+                        "${{getTypeOrigin('{}', '{}', options?.env)}}",
+                        pkg_name, module_type_path
+                    ),
+                };
+                if type_args.is_empty() {
+                    format!("{}::{}", address, module_type_path)
+                } else {
+                    let args: Vec<_> = type_args
+                        .iter()
+                        .map(|ta| ta.to_move_type_in_env(type_arg_count))
+                        .collect();
+                    format!("{}::{}<{}>", address, module_type_path, args.join(", "))
+                }
+            }
+            ParamTypeIR::Option(inner) => {
+                format!(
+                    "0x1::option::Option<{}>",
+                    inner.to_move_type_in_env(type_arg_count)
+                )
+            }
+            ParamTypeIR::TypeParam { .. } => self.to_bcs_type(type_arg_count),
+            ParamTypeIR::StringType { module } => format!("0x1::{}::String", module),
+            ParamTypeIR::ID => "0x2::object::ID".to_string(),
+        }
+    }
+
+    /// Check if this type contains a datatype whose address is looked up in the environment.
+    pub fn uses_type_origin(&self) -> bool {
+        match self {
+            ParamTypeIR::Struct {
+                type_args,
+                package_info,
+                ..
+            } => {
+                matches!(package_info, PackageInfo::Dynamic { .. })
+                    || type_args.iter().any(|t| t.uses_type_origin())
+            }
+            ParamTypeIR::Vector(inner) | ParamTypeIR::Option(inner) => inner.uses_type_origin(),
+            _ => false,
+        }
+    }
+
+    /// Generate the Move type tag of a typed call result (a TypeScript template literal type).
+    ///
+    /// Datatypes are identified by their type name only, without type arguments,
+    /// so that results of generic calls can be passed to functions taking concrete types.
+    pub fn to_move_type_tag(&self) -> String {
+        format!("`{}`", self.move_type_tag_inner())
+    }
+
+    fn move_type_tag_inner(&self) -> String {
+        match self {
+            ParamTypeIR::Primitive(p) => p.clone(),
+            ParamTypeIR::Vector(inner) => format!("vector<{}>", inner.move_type_tag_inner()),
+            ParamTypeIR::Struct { class_name, .. } => {
+                format!("${{typeof {}.$typeName}}", class_name)
+            }
+            ParamTypeIR::Option(_) => "${typeof Option.$typeName}".to_string(),
+            ParamTypeIR::TypeParam { .. } => "${string}".to_string(),
+            ParamTypeIR::StringType { module } => {
+                if module == "ascii" {
+                    "${typeof String1.$typeName}".to_string()
+                } else {
+                    "${typeof String.$typeName}".to_string()
+                }
+            }
+            ParamTypeIR::ID => "${typeof ID.$typeName}".to_string(),
+        }
+    }
+
+    /// Get the TypeScript type of a decoded value of this type (as decoded by `decodeFromFields`).
+    pub fn to_ts_value_type(&self) -> String {
+        match self {
+            ParamTypeIR::TypeParam { .. } => "unknown".to_string(),
+            _ => format!("ToField<{}>", self.to_ts_type_argument()),
+        }
+    }
+
+    /// Get the `TypeArgument` type (reified.ts) for this type, e.g. `'u64'` or `Vector<Bar>`.
+    fn to_ts_type_argument(&self) -> String {
+        match self {
+            ParamTypeIR::Primitive(p) => format!("'{}'", p),
+            ParamTypeIR::Vector(inner) => format!("Vector<{}>", inner.to_ts_type_argument()),
+            ParamTypeIR::Struct {
+                class_name,
+                type_args,
+                ..
+            } => {
+                if type_args.is_empty() {
+                    class_name.clone()
+                } else {
+                    format!("{}<{}>", class_name, vec!["any"; type_args.len()].join(", "))
+                }
+            }
+            ParamTypeIR::Option(inner) => format!("Option<{}>", inner.to_ts_type_argument()),
+            ParamTypeIR::TypeParam { .. } => "any".to_string(),
+            ParamTypeIR::StringType { module } => {
+                if module == "ascii" {
+                    "String1".to_string()
+                } else {
+                    "String".to_string()
+                }
+            }
+            ParamTypeIR::ID => "ID".to_string(),
+        }
+    }
+
+    /// Check if this type contains a vector (struct type arguments aside).
+    pub fn contains_vector(&self) -> bool {
+        match self {
+            ParamTypeIR::Vector(_) => true,
+            ParamTypeIR::Option(inner) => inner.contains_vector(),
+            _ => false,
+        }
+    }
+
+    /// Check if this type references a type parameter (at any depth).
+    pub fn contains_type_param(&self) -> bool {
+        match self {
+            ParamTypeIR::TypeParam { .. } => true,
+            ParamTypeIR::Vector(inner) | ParamTypeIR::Option(inner) => inner.contains_type_param(),
+            ParamTypeIR::Struct { type_args, .. } => {
+                type_args.iter().any(|t| t.contains_type_param())
+            }
+            _ => false,
+        }
+    }
+
+    /// Check if this type is passed as an `ObjectArg` (at any depth): a struct/enum
+    /// or a type parameter with `key`.
+    pub fn contains_object_arg(&self) -> bool {
+        match self {
+            ParamTypeIR::Struct { .. } | ParamTypeIR::TypeParam { is_key: true, .. } => true,
+            ParamTypeIR::Vector(inner) | ParamTypeIR::Option(inner) => inner.contains_object_arg(),
+            _ => false,
+        }
+    }
+
     /// Get the inner type for Option.
     pub fn option_inner(&self) -> Option<&ParamTypeIR> {
         match self {
@@ -163,6 +334,26 @@ pub struct FunctionParamIR {
     pub doc_comment: Option<String>,
 }
 
+/// A function type parameter.
+#[derive(Debug, Clone)]
+pub struct FunctionTypeParamIR {
+    /// Type parameter name (T, U, etc.).
+    pub name: String,
+    /// Ability constraints (e.g. `[Drop, Store]` for `T: store + drop`).
+    pub constraints: Vec<Ability>,
+}
+
+impl FunctionTypeParamIR {
+    /// Move-style declaration, e.g. `T: drop + store`.
+    fn to_move_decl(&self) -> String {
+        if self.constraints.is_empty() {
+            return self.name.clone();
+        }
+        let constraints: Vec<_> = self.constraints.iter().map(|a| a.as_str()).collect();
+        format!("{}: {}", self.name, constraints.join(" + "))
+    }
+}
+
 /// Struct import needed by a function.
 #[derive(Debug, Clone)]
 pub struct FunctionStructImport {
@@ -185,10 +376,14 @@ pub struct FunctionIR {
     pub module_name: String,
     /// Kebab-case package name for environment lookups.
     pub env_pkg_name: String,
-    /// Type parameter names (T, U, etc.).
-    pub type_params: Vec<String>,
+    /// Type parameters with their ability constraints.
+    pub type_params: Vec<FunctionTypeParamIR>,
     /// Function parameters.
     pub params: Vec<FunctionParamIR>,
+    /// Return types (references stripped).
+    pub returns: Vec<ParamTypeIR>,
+    /// Whether any of the return values is a reference, which a transaction can't return.
+    pub returns_reference: bool,
     /// Struct imports needed.
     pub struct_imports: Vec<FunctionStructImport>,
     /// Whether this function uses GenericArg.
@@ -214,6 +409,12 @@ pub struct FunctionIR {
 impl FunctionIR {
     /// Generate the args interface name.
     fn args_interface_name(&self) -> String {
+        // Functions renamed in `[naming.identifiers]` are named after their TS name
+        if self.ts_name != function_ts_name(&self.move_name) {
+            let (first, rest) = self.ts_name.split_at(1);
+            return format!("{}{}Args", first.to_ascii_uppercase(), rest);
+        }
+
         // Convert directly from Move name to preserve digit-letter sequences
         let pascal_name = self.move_name.from_case(Case::Snake).to_case(Case::Pascal);
         if self.move_name.ends_with('_') {
@@ -244,15 +445,37 @@ impl FunctionIR {
         }
     }
 
+    /// Whether any type parameter has ability constraints.
+    fn has_constrained_type_params(&self) -> bool {
+        self.type_params.iter().any(|tp| !tp.constraints.is_empty())
+    }
+
     /// Generate the function signature.
     fn emit_function_signature(&self) -> String {
+        format!(
+            "export function {}(tx: Transaction, {}): {}",
+            self.ts_name,
+            self.emit_params(&["env?: EnvConfig"]),
+            self.emit_return_type()
+        )
+    }
+
+    /// Generate the parameter list after the leading `tx`/`client` parameter.
+    /// `options_fields` are the fields of the trailing `options` parameter.
+    fn emit_params(&self, options_fields: &[&str]) -> String {
+        let mut options_fields = options_fields.to_vec();
+        if self.has_constrained_type_params() {
+            options_fields.push("checkAbilities?: boolean");
+        }
+        let options_part = format!("options?: {{ {} }}", options_fields.join("; "));
+
         let has_type_params = !self.type_params.is_empty();
         let has_params = !self.params.is_empty();
 
         let type_arg_param = match self.type_params.len() {
             0 => String::new(),
-            1 => "typeArg: string".to_string(),
-            n => format!("typeArgs: [{}]", vec!["string"; n].join(", ")),
+            1 => "typeArg: TypeArg".to_string(),
+            n => format!("typeArgs: [{}]", vec!["TypeArg"; n].join(", ")),
         };
 
         let params_part = match self.params.len() {
@@ -269,22 +492,114 @@ impl FunctionIR {
             _ => format!("args: {}", self.args_interface_name()),
         };
 
-        let options_part = "options?: { env?: EnvConfig }".to_string();
-
         // Build the parameters list with proper comma handling
-        let all_params = match (has_type_params, has_params) {
+        match (has_type_params, has_params) {
             (true, true) => format!("{}, {}, {}", type_arg_param, params_part, options_part),
             (true, false) => format!("{}, {}", type_arg_param, options_part),
             (false, true) => format!("{}, {}", params_part, options_part),
             (false, false) => options_part,
-        };
+        }
+    }
 
+    /// Generate the opt-in runtime check that the type arguments have the abilities
+    /// required by the type parameter constraints. Empty if there are no constraints.
+    fn emit_ability_checks(&self) -> String {
+        let checks: Vec<_> = self
+            .type_params
+            .iter()
+            .enumerate()
+            .filter(|(_, tp)| !tp.constraints.is_empty())
+            .map(|(i, tp)| {
+                let type_arg = if self.type_params.len() == 1 {
+                    "resolvedTypeArg".to_string()
+                } else {
+                    format!("resolvedTypeArgs[{}]", i)
+                };
+                let abilities: Vec<_> = tp
+                    .constraints
+                    .iter()
+                    .map(|a| format!("'{}'", a.as_str()))
+                    .collect();
+                format!(
+                    "\n    assertAbilities({}, [{}])",
+                    type_arg,
+                    abilities.join(", ")
+                )
+            })
+            .collect();
+        if checks.is_empty() {
+            return String::new();
+        }
         format!(
-            "export function {}(tx: Transaction, {}): TransactionResult",
-            self.ts_name, all_params
+            "\n  if (options?.checkAbilities) {{{}\n  }}",
+            checks.join("")
         )
     }
 
+    /// Generate the resolution of the type arguments (type strings or reified types)
+    /// to type strings. Empty if the function has no type parameters.
+    fn emit_resolve_type_args(&self) -> String {
+        match self.type_params.len() {
+            0 => String::new(),
+            1 => "\n  const resolvedTypeArg = resolveTypeArg(typeArg)".to_string(),
+            _ => "\n  const resolvedTypeArgs = typeArgs.map(resolveTypeArg)".to_string(),
+        }
+    }
+
+    /// Generate the JSDoc tag documenting the type parameter constraints, if any.
+    fn emit_type_params_doc(&self) -> Option<String> {
+        if !self.has_constrained_type_params() {
+            return None;
+        }
+        let decls: Vec<_> = self
+            .type_params
+            .iter()
+            .map(|tp| format!("`{}`", tp.to_move_decl()))
+            .collect();
+        if self.type_params.len() == 1 {
+            Some(format!("@param typeArg - Type argument for {}", decls[0]))
+        } else {
+            Some(format!(
+                "@param typeArgs - Type arguments for {}",
+                decls.join(", ")
+            ))
+        }
+    }
+
+    /// Generate the argument list forwarding the parameters to the wrapper (used by the view variant).
+    fn emit_forwarded_args(&self) -> String {
+        let mut args = Vec::new();
+        match self.type_params.len() {
+            0 => (),
+            1 => args.push("typeArg".to_string()),
+            _ => args.push("typeArgs".to_string()),
+        }
+        match self.params.len() {
+            0 => (),
+            1 => {
+                let p = &self.params[0];
+                if is_reserved_word(&p.ts_name) {
+                    args.push(format!("{}_", p.ts_name));
+                } else {
+                    args.push(p.ts_name.clone());
+                }
+            }
+            _ => args.push("args".to_string()),
+        }
+        args.push("options".to_string());
+        args.join(", ")
+    }
+
+    /// Generate the return type: a `TypedResult` carrying the Move types of the
+    /// returned values, or a plain `TransactionResult` for functions returning nothing.
+    fn emit_return_type(&self) -> String {
+        if self.returns.is_empty() {
+            return "TransactionResult".to_string();
+        }
+        let tags: Vec<_> = self.returns.iter().map(|r| r.to_move_type_tag()).collect();
+        format!("TypedResult<[{}]>", tags.join(", "))
+    }
+
     /// Generate a single argument transformation.
     /// `module_aliased` contains util names that are aliased at module level.
     fn emit_arg(
@@ -322,7 +637,7 @@ impl FunctionIR {
             )
         } else {
             match &param.param_type {
-                ParamTypeIR::TypeParam { .. } => {
+                ParamTypeIR::TypeParam { is_key: false, .. } => {
                     format!(
                         "generic{}(tx, `{}`, {})",
                         alias_for("generic"),
@@ -385,47 +700,130 @@ impl FunctionIR {
 
         let type_arguments_line = match self.type_params.len() {
             0 => String::new(),
-            1 => "\n    typeArguments: [typeArg],".to_string(),
-            _ => "\n    typeArguments: typeArgs,".to_string(),
+            1 => "\n    typeArguments: [resolvedTypeArg],".to_string(),
+            _ => "\n    typeArguments: resolvedTypeArgs,".to_string(),
         };
 
         let args = self.emit_arguments(module_aliased);
 
+        // moveCall returns an untyped TransactionResult, narrow it to the typed result
+        let cast = if self.returns.is_empty() {
+            String::new()
+        } else {
+            format!(" as {}", self.emit_return_type())
+        };
+
+        let resolve_type_args = self.emit_resolve_type_args();
+        let ability_checks = self.emit_ability_checks();
+
         formatdoc! {r#"
-            {sig} {{
+            {sig} {{{resolve_type_args}{ability_checks}
               return tx.moveCall({{
                 target: {target},{type_arguments_line}
                 arguments: {args},
-              }})
+              }}){cast}
             }}"#
         }
     }
 
+    /// Whether the function gets a view variant: it returns values, none of them references.
+    pub fn has_view(&self) -> bool {
+        !self.returns.is_empty() && !self.returns_reference
+    }
+
+    /// Generate the view variant: runs the call in a simulated transaction and decodes
+    /// the return values with the reified BCS types. `None` if the function has no view.
+    ///
+    /// Named `<function>View`, with `_` appended until the name isn't taken by another
+    /// function or view. The chosen name is added to `taken_names`.
+    pub fn emit_view(
+        &self,
+        taken_names: &mut std::collections::HashSet<String>,
+    ) -> Option<String> {
+        if !self.has_view() {
+            return None;
+        }
+
+        let mut view_name = format!("{}View", self.ts_name);
+        while taken_names.contains(&view_name) {
+            view_name.push('_');
+        }
+        taken_names.insert(view_name.clone());
+
+        let params = self.emit_params(&["env?: EnvConfig", "sender?: string"]);
+        let type_arg_count = self.type_params.len();
+        let return_types: Vec<_> = self
+            .returns
+            .iter()
+            .map(|r| format!("`{}`", r.to_move_type_in_env(type_arg_count)))
+            .collect();
+        let value_types: Vec<_> = self.returns.iter().map(|r| r.to_ts_value_type()).collect();
+
+        // A single return value is returned as is, multiple ones as a tuple
+        let (result_type, result) = if value_types.len() == 1 {
+            (value_types[0].clone(), "values[0]")
+        } else {
+            (format!("[{}]", value_types.join(", ")), "values")
+        };
+
+        // The type args are only needed here to build the return types
+        let resolve_type_args = if self.returns.iter().any(|r| r.contains_type_param()) {
+            self.emit_resolve_type_args()
+        } else {
+            String::new()
+        };
+
+        let jsdoc = format!(
+            "/** Simulates `{}` and returns its decoded return values. Nothing is executed on chain. */",
+            self.move_name
+        );
+
+        Some(formatdoc! {r#"
+            {jsdoc}
+            export async function {view_name}(client: ClientWithCoreApi, {params}): Promise<{result_type}> {{{resolve_type_args}
+              const values = await viewCall(
+                client,
+                tx => {ts_name}(tx, {forwarded}),
+                [{return_types}],
+                options,
+              )
+              return {result} as {result_type}
+            }}"#,
+            ts_name = self.ts_name,
+            forwarded = self.emit_forwarded_args(),
+            resolve_type_args = resolve_type_args,
+            return_types = return_types.join(", "),
+        })
+    }
+
     /// Generate the full function (interface + function).
     pub fn emit(&self, module_aliased: &std::collections::HashSet<String>) -> String {
         let interface = self.emit_args_interface();
         let body = self.emit_body(module_aliased);
 
-        // Build combined doc comment with @deprecated tag if needed
-        let combined_doc = if self.is_deprecated {
-            let deprecated_line = if let Some(note) = &self.deprecation_note {
+        // Build combined doc comment with type param and @deprecated tags if needed
+        let mut tags: Vec<String> = self.emit_type_params_doc().into_iter().collect();
+        if self.is_deprecated {
+            tags.push(if let Some(note) = &self.deprecation_note {
                 format!("@deprecated {}", note)
             } else {
                 "@deprecated".to_string()
-            };
+            });
+        }
 
+        let combined_doc = if tags.is_empty() {
+            self.doc_comment.clone()
+        } else {
             match &self.doc_comment {
                 Some(doc) if !doc.trim().is_empty() => {
-                    // Combine doc comment and deprecated tag
-                    Some(format!("{}\n\n{}", doc, deprecated_line))
+                    // Combine doc comment and tags
+                    Some(format!("{}\n\n{}", doc, tags.join("\n")))
                 }
                 _ => {
-                    // Only deprecated tag, no doc comment
-                    Some(deprecated_line)
+                    // Only tags, no doc comment
+                    Some(tags.join("\n"))
                 }
             }
-        } else {
-            self.doc_comment.clone()
         };
 
         // Add function JSDoc to the body only (not the interface)
@@ -508,6 +906,43 @@ pub fn emit_function_imports(functions: &[FunctionIR], framework_path: &str) ->
             imports.add_named(&util_path, "vector");
         }
     }
+    if functions.iter().any(|f| !f.type_params.is_empty()) {
+        let reified_path = format!("{}/reified", framework_path);
+        imports.add_type_named(&reified_path, "TypeArg");
+        imports.add_named(&reified_path, "resolveTypeArg");
+    }
+    if functions.iter().any(|f| f.has_constrained_type_params()) {
+        imports.add_named(format!("{}/loader", framework_path), "assertAbilities");
+    }
+    if functions
+        .iter()
+        .any(|f| f.params.iter().any(|p| p.param_type.contains_object_arg()))
+    {
+        imports.add_type_named(&util_path, "ObjectArg");
+    }
+    if functions.iter().any(|f| !f.returns.is_empty()) {
+        imports.add_type_named(&util_path, "TypedResult");
+    }
+
+    // View variants
+    let views: Vec<_> = functions.iter().filter(|f| f.has_view()).collect();
+    if !views.is_empty() {
+        imports.add_type_named("@mysten/sui/client", "ClientWithCoreApi");
+        imports.add_type_named(format!("{}/reified", framework_path), "ToField");
+        imports.add_named(format!("{}/view", framework_path), "viewCall");
+    }
+    if views
+        .iter()
+        .any(|f| f.returns.iter().any(|r| r.uses_type_origin()))
+    {
+        imports.add_named(&envs_path, "getTypeOrigin");
+    }
+    if views
+        .iter()
+        .any(|f| f.returns.iter().any(|r| r.contains_vector()))
+    {
+        imports.add_type_named(format!("{}/vector", framework_path), "Vector");
+    }
 
     // Struct imports (from other modules)
     for f in functions {
@@ -526,18 +961,6 @@ pub fn emit_function_imports(functions: &[FunctionIR], framework_path: &str) ->
         &["Transaction", "TransactionArgument", "TransactionResult"],
     );
 
-    let needs_object_input = functions.iter().any(|f| {
-        f.params.iter().any(|p| {
-            matches!(
-                p.param_type,
-                ParamTypeIR::Struct { .. } | ParamTypeIR::Vector(_) | ParamTypeIR::Option(_)
-            )
-        })
-    });
-    if needs_object_input {
-        imports.add_named("@mysten/sui/transactions", "TransactionObjectInput");
-    }
-
     imports.emit()
 }
 
@@ -554,7 +977,15 @@ pub fn emit_functions_file(functions: &[FunctionIR], framework_path: &str) -> St
         .collect();
 
     let imports = emit_function_imports(functions, framework_path);
-    let bodies: Vec<_> = functions.iter().map(|f| f.emit(&module_aliased)).collect();
+    let mut taken_names: std::collections::HashSet<String> =
+        functions.iter().map(|f| f.ts_name.clone()).collect();
+    let bodies: Vec<_> = functions
+        .iter()
+        .map(|f| match f.emit_view(&mut taken_names) {
+            Some(view) => [f.emit(&module_aliased), view].join("\n\n"),
+            None => f.emit(&module_aliased),
+        })
+        .collect();
 
     format!("{}\n\n{}\n", imports, bodies.join("\n\n"))
 }
@@ -9,7 +9,8 @@ use move_model_2::model;
 use move_model_2::source_kind::SourceKind;
 use move_symbol_pool::Symbol;
 
-use super::utils::{module_import_name, sanitize_identifier};
+use super::utils::{datatype_ts_name, module_import_name, sanitize_identifier, IdentifierRenames};
+use crate::filter::Selection;
 
 // ============================================================================
 // Package Init IR (for init.ts files)
@@ -36,15 +37,21 @@ pub struct PackageInitIR {
 }
 
 impl PackageInitIR {
-    /// Build the IR from a Move package.
+    /// Build the IR from a Move package, registering the structs kept by `selection`
+    /// under their TypeScript names.
     pub fn from_package<HasSource: SourceKind>(
         pkg: &model::Package<HasSource>,
         framework_path: &str,
+        selection: &Selection,
+        renames: &IdentifierRenames,
     ) -> Self {
         let mut registrations = Vec::new();
 
         for mod_ in pkg.modules() {
-            let structs: Vec<_> = mod_.structs().collect();
+            let structs: Vec<_> = mod_
+                .structs()
+                .filter(|s| selection.keeps_datatype(pkg.address(), mod_.name(), s.name()))
+                .collect();
             if structs.is_empty() {
                 continue;
             }
@@ -61,7 +68,12 @@ impl PackageInitIR {
                 registrations.push(StructRegistration {
                     import_path: import_path.clone(),
                     module_alias: alias.clone(),
-                    struct_name: strct.name().to_string(),
+                    struct_name: datatype_ts_name(
+                        Some(renames),
+                        pkg.address(),
+                        mod_.name(),
+                        strct.name(),
+                    ),
                 });
             }
         }
@@ -218,8 +230,10 @@ impl InitLoaderIR {
 pub fn gen_package_init<HasSource: SourceKind>(
     pkg: &model::Package<HasSource>,
     framework_rel_path: &str,
+    selection: &Selection,
+    renames: &IdentifierRenames,
 ) -> String {
-    PackageInitIR::from_package(pkg, framework_rel_path).emit()
+    PackageInitIR::from_package(pkg, framework_rel_path, selection, renames).emit()
 }
 
 /// Generate _framework/init-loader.ts.
@@ -8,9 +8,11 @@
 
 mod builder;
 pub mod compat;
+mod constants;
 mod doc_utils;
 mod enums;
 mod env_config;
+mod events;
 mod format;
 mod functions;
 mod imports;
@@ -21,20 +23,26 @@ mod utils;
 
 // Re-export public API
 pub use builder::{
-    emit_module_structs_from_ir, gen_module_functions, gen_module_structs, EnumIRBuilder,
+    build_module_constants_ir, emit_module_structs_from_ir, gen_module_constants,
+    gen_module_functions, gen_module_structs, EnumIRBuilder,
     FunctionIRBuilder, StructIRBuilder,
 };
+pub use constants::{gen_init_errors, ConstantIR, ModuleConstantsIR};
 pub use enums::{EnumIR, EnumVariantIR};
-pub use functions::{emit_functions_file, FunctionIR, FunctionParamIR, FunctionStructImport, ParamTypeIR};
+pub use events::{gen_package_events, PackageEventsIR};
+pub use functions::{
+    emit_functions_file, FunctionIR, FunctionParamIR, FunctionStructImport, FunctionTypeParamIR,
+    ParamTypeIR,
+};
 pub use imports::{ImportPathResolver, TsImportsBuilder};
 pub use init::{gen_init_loader, gen_package_init};
 pub use structs::{
-    DatatypeImport, DatatypeKind, FieldIR, FieldTypeIR, PackageInfo, StructIR, StructImport,
-    TypeParamIR,
+    Ability, DatatypeImport, DatatypeKind, FieldIR, FieldTypeIR, PackageInfo, StructIR,
+    StructImport, TypeParamIR,
 };
 pub use utils::{
-    is_reserved_word, module_import_name, package_import_name, sanitize_identifier,
-    JS_RESERVED_WORDS,
+    datatype_ts_name, function_ts_name, is_reserved_word, module_import_name,
+    package_import_name, sanitize_identifier, IdentifierRenames, JS_RESERVED_WORDS,
 };
 pub use env_config::{gen_envs_index, EnvConfigIR, EnvPackageConfigIR};
 pub use format::TsFormatter;
@@ -38,6 +38,30 @@ pub struct StructIR {
     pub uses_field_to_json: bool,
     /// Struct-level documentation from Move source
     pub doc_comment: Option<String>,
+    /// Abilities of the struct, in declaration order (copy, drop, store, key)
+    pub abilities: Vec<Ability>,
+    /// Whether to emit a JSON validator and run it in `fromJSON` (`json-validators` config)
+    pub json_validators: bool,
+}
+
+/// A Move ability of a datatype.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Ability {
+    Copy,
+    Drop,
+    Store,
+    Key,
+}
+
+impl Ability {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Ability::Copy => "copy",
+            Ability::Drop => "drop",
+            Ability::Store => "store",
+            Ability::Key => "key",
+        }
+    }
 }
 
 /// Package address information for generating full type names.
@@ -186,6 +210,11 @@ impl StructIR {
         // JSON type aliases
         sections.push(self.emit_json_types());
 
+        // JSON validator (opt-in)
+        if self.json_validators {
+            sections.push(self.emit_json_validator());
+        }
+
         // The main class
         sections.push(self.emit_class());
 
@@ -270,6 +299,9 @@ impl StructIR {
         if self.uses_vector {
             framework_reified_imports.push("fieldToJSON");
         }
+        if self.has_key() {
+            framework_reified_imports.push("KeyStructClassReified");
+        }
 
         // Add ToPhantom alias if phantom struct args are used
         let imports_str = if self.uses_phantom_struct_args {
@@ -306,6 +338,14 @@ impl StructIR {
             ));
         }
 
+        // JSON validation helpers if validators are enabled
+        if self.json_validators {
+            lines.push(format!(
+                "import {{ validateJSONField, validateObject }} from '{}/validate'",
+                framework_path
+            ));
+        }
+
         // Struct imports from other modules
         for imp in &self.struct_imports {
             let name = match &imp.alias {
@@ -321,20 +361,28 @@ impl StructIR {
         } else {
             lines.push("import { bcs } from '@mysten/sui/bcs'".to_string());
         }
-        lines.push(
-            "import type { ClientWithCoreApi, SuiClientTypes } from '@mysten/sui/client'"
-                .to_string(),
-        );
-        lines.push(
-            "import type { SuiObjectData, SuiParsedData } from '@mysten/sui/jsonRpc'".to_string(),
-        );
+        if self.has_key() {
+            lines.push(
+                "import type { ClientWithCoreApi, SuiClientTypes } from '@mysten/sui/client'"
+                    .to_string(),
+            );
+            lines.push(
+                "import type { SuiObjectData, SuiParsedData } from '@mysten/sui/jsonRpc'"
+                    .to_string(),
+            );
+        }
 
-        // Utils imports - add fromHex/toHex if addresses are used
-        if self.uses_address {
-            lines
-                .push("import { fromBase64, fromHex, toHex } from '@mysten/sui/utils'".to_string());
-        } else {
-            lines.push("import { fromBase64 } from '@mysten/sui/utils'".to_string());
+        // Utils imports - fromBase64 for objects, fromHex/toHex if addresses are used
+        match (self.has_key(), self.uses_address) {
+            (true, true) => lines
+                .push("import { fromBase64, fromHex, toHex } from '@mysten/sui/utils'".to_string()),
+            (true, false) => {
+                lines.push("import { fromBase64 } from '@mysten/sui/utils'".to_string())
+            }
+            (false, true) => {
+                lines.push("import { fromHex, toHex } from '@mysten/sui/utils'".to_string())
+            }
+            (false, false) => {}
         }
 
         lines.join("\n")
@@ -396,6 +444,12 @@ impl StructIR {
         }
     }
 
+    /// Returns true if this struct has the `key` ability, i.e. instances are objects
+    /// that can be fetched by ID.
+    pub fn has_key(&self) -> bool {
+        self.abilities.contains(&Ability::Key)
+    }
+
     /// Returns true if this struct uses dynamic environment lookups (non-system package)
     pub fn uses_env(&self) -> bool {
         matches!(&self.package_info, PackageInfo::Dynamic { .. })
@@ -458,9 +512,17 @@ impl StructIR {
         let type_params_decl = self.emit_type_params_decl();
         let type_params_use = self.emit_type_params_use();
 
+        // Objects get a reified type that can also load them from the chain
+        let reified = if self.has_key() {
+            "KeyStructClassReified"
+        } else {
+            "Reified"
+        };
+
         formatdoc! {r#"
-            export type {name}Reified{type_params_decl} = Reified<{name}{type_params_use}, {name}Fields{type_params_use}>"#,
+            export type {name}Reified{type_params_decl} = {reified}<{name}{type_params_use}, {name}Fields{type_params_use}>"#,
             name = self.name,
+            reified = reified,
             type_params_decl = type_params_decl,
             type_params_use = type_params_use,
         }
@@ -505,6 +567,77 @@ impl StructIR {
         }
     }
 
+    /// Emit `validate<Name>JSONField`, which checks a `<Name>JSONField` value field by field
+    /// (see `validateJSONField` in `_framework/validate.ts`).
+    fn emit_json_validator(&self) -> String {
+        let num_type_params = self.type_params.len();
+        let checks: Vec<String> = self
+            .fields
+            .iter()
+            .map(|f| {
+                let reified = if num_type_params == 0 {
+                    f.field_type.to_reified()
+                } else {
+                    f.field_type.to_reified_runtime(num_type_params)
+                };
+                format!(
+                    "  validateJSONField({}, obj.{}, `${{path}}.{}`)",
+                    reified, f.ts_name, f.ts_name
+                )
+            })
+            .collect();
+        let body = if checks.is_empty() {
+            "  validateObject(field, path)".to_string()
+        } else {
+            format!(
+                "  const obj = validateObject(field, path)\n{}",
+                checks.join("\n")
+            )
+        };
+
+        if num_type_params == 0 {
+            formatdoc! {r#"
+                /** Checks that `field` is a valid `{name}JSONField`, throwing a `JSONValidationError` otherwise. */
+                export function validate{name}JSONField(field: unknown, path = '$'): void {{
+                {body}
+                }}"#,
+                name = self.name,
+                body = body,
+            }
+        } else {
+            formatdoc! {r#"
+                /** Checks that `field` is a valid `{name}JSONField`, throwing a `JSONValidationError` otherwise. */
+                export function validate{name}JSONField{reified_type_params}(
+                  {reified_arg_first}
+                  field: unknown,
+                  path = '$'
+                ): void {{
+                {body}
+                }}"#,
+                name = self.name,
+                reified_type_params = self.emit_reified_type_params(),
+                reified_arg_first = self.emit_reified_arg_first(),
+                body = body,
+            }
+        }
+    }
+
+    /// Emit the validator call of `fromJSON` (empty unless JSON validators are enabled).
+    fn emit_from_json_validation(&self) -> String {
+        if !self.json_validators {
+            return String::new();
+        }
+        if self.type_params.is_empty() {
+            format!("\n    validate{}JSONField(json)", self.name)
+        } else {
+            format!(
+                "\n    validate{}JSONField({}, json)",
+                self.name,
+                self.emit_type_args_for_call()
+            )
+        }
+    }
+
     fn emit_class(&self) -> String {
         // For now, handle the no-type-params case
         if self.type_params.is_empty() {
@@ -601,6 +734,7 @@ impl StructIR {
               {type_name_decl}
               static readonly $numTypeParams = 0
               static readonly $isPhantom = [] as const
+              static readonly $abilities = {abilities} as const
 
               readonly $typeName: typeof {name}.$typeName = {name}.$typeName
               readonly $fullTypeName: {full_type_as_type}
@@ -637,11 +771,7 @@ impl StructIR {
                   fromBcs: (data: Uint8Array) => {name}.fromFields(reifiedBcs.parse(data)),
                   bcs: reifiedBcs,
                   fromJSONField: (field: any) => {name}.fromJSONField(field),
-                  fromJSON: (json: Record<string, any>) => {name}.fromJSON(json),
-                  fromCoreObject: (obj: SuiClientTypes.Object<{{ content: true }}>) => {name}.fromCoreObject(obj),
-                  fromSuiParsedData: (content: SuiParsedData) => {name}.fromSuiParsedData(content),
-                  fromSuiObjectData: (content: SuiObjectData) => {name}.fromSuiObjectData(content),
-                  fetch: async (client: ClientWithCoreApi, id: string) => {name}.fetch(client, id),
+                  fromJSON: (json: Record<string, any>) => {name}.fromJSON(json),{object_reified_members}
                   new: (fields: {name}Fields) => {{
                     return new {name}([], fields)
                   }},
@@ -715,57 +845,11 @@ impl StructIR {
               static fromJSON(json: Record<string, any>): {name} {{
                 if (json.$typeName !== {name}.$typeName) {{
                   throw new Error(`not a {name} json object: expected '${{{name}.$typeName}}' but got '${{json.$typeName}}'`)
-                }}
+                }}{validate_json}
 
                 return {name}.fromJSONField(json)
               }}
-
-              static fromCoreObject(obj: SuiClientTypes.Object<{{ content: true }}>): {name} {{
-                if (!is{name}(obj.type)) {{
-                  throw new Error(`object at ${{obj.objectId}} is not a {name} object`)
-                }}
-                return {name}.fromBcs(obj.content)
-              }}
-
-              /** @deprecated `SuiParsedData` is a JSON-RPC-only type that is being phased out upstream. Use {{@link {name}.fromCoreObject}} together with `client.core.getObject({{ include: {{ content: true }} }})` for transport-agnostic parsing. */
-              static fromSuiParsedData(content: SuiParsedData): {name} {{
-                if (content.dataType !== 'moveObject') {{
-                  throw new Error('not an object')
-                }}
-                if (!is{name}(content.type)) {{
-                  throw new Error(`object at ${{(content.fields as any).id}} is not a {name} object`)
-                }}
-                return {name}.fromFieldsWithTypes(content)
-              }}
-
-              /** @deprecated `SuiObjectData` is a JSON-RPC-only type that is being phased out upstream. Use {{@link {name}.fromCoreObject}} together with `client.core.getObject({{ include: {{ content: true }} }})` for transport-agnostic parsing. */
-              static fromSuiObjectData(data: SuiObjectData): {name} {{
-                if (data.bcs) {{
-                  if (data.bcs.dataType !== 'moveObject' || !is{name}(data.bcs.type)) {{
-                    throw new Error(`object at is not a {name} object`)
-                  }}
-
-                  return {name}.fromBcs(fromBase64(data.bcs.bcsBytes))
-                }}
-                if (data.content) {{
-                  return {name}.fromSuiParsedData(data.content)
-                }}
-                throw new Error(
-                  'Both `bcs` and `content` fields are missing from the data. Include `showBcs` or `showContent` in the request.'
-                )
-              }}
-
-              static async fetch(client: ClientWithCoreApi, id: string): Promise<{name}> {{
-                const {{ object }} = await client.core.getObject({{
-                  objectId: id,
-                  include: {{ content: true }},
-                }})
-                if (!is{name}(object.type)) {{
-                  throw new Error(`object at id ${{id}} is not a {name} object`)
-                }}
-                return {name}.fromBcs(object.content)
-              }}
-            }}"#,
+            {class_tail}"#,
             name = self.name,
             type_name_decl = type_name_decl,
             full_type_as_type = full_type_as_type,
@@ -776,6 +860,10 @@ impl StructIR {
             from_fields_with_types_decodes = from_fields_with_types_decodes.join("\n"),
             to_json_fields = to_json_fields.join("\n"),
             from_json_decodes = from_json_decodes.join("\n"),
+            abilities = self.emit_abilities_array(),
+            object_reified_members = self.emit_object_reified_members(),
+            validate_json = self.emit_from_json_validation(),
+            class_tail = self.emit_class_tail_no_type_params(),
         };
 
         // Add struct-level JSDoc if available
@@ -1010,6 +1098,7 @@ impl StructIR {
               {type_name_decl}
               static readonly $numTypeParams = {num_type_params}
               static readonly $isPhantom = {is_phantom_array} as const
+              static readonly $abilities = {abilities} as const
             {inner_fields_section}
               readonly $typeName: typeof {name}.$typeName = {name}.$typeName
               readonly $fullTypeName: {full_type_name_with_generics}
@@ -1048,11 +1137,7 @@ impl StructIR {
                   fromBcs: (data: Uint8Array) => {name}.fromFields({reified_args_for_static}, reifiedBcs.parse(data)),
                   bcs: reifiedBcs,
                   fromJSONField: (field: any) => {name}.fromJSONField({reified_args_for_static}, field),
-                  fromJSON: (json: Record<string, any>) => {name}.fromJSON({reified_args_for_static}, json),
-                  fromCoreObject: (obj: SuiClientTypes.Object<{{ content: true }}>) => {name}.fromCoreObject({reified_args_for_static}, obj),
-                  fromSuiParsedData: (content: SuiParsedData) => {name}.fromSuiParsedData({reified_args_for_static}, content),
-                  fromSuiObjectData: (content: SuiObjectData) => {name}.fromSuiObjectData({reified_args_for_static}, content),
-                  fetch: async (client: ClientWithCoreApi, id: string) => {name}.fetch(client, {reified_args_for_static}, id),
+                  fromJSON: (json: Record<string, any>) => {name}.fromJSON({reified_args_for_static}, json),{object_reified_members}
                   new: (fields: {name}Fields{to_phantom_type_args}) => {{
                     return new {name}([{extract_types}], fields)
                   }},
@@ -1136,10 +1221,173 @@ impl StructIR {
                   composeSuiType({name}.$typeName, {extract_type_vars}),
                   json.$typeArgs,
                   {type_args_array}
-                )
+                ){validate_json}
 
                 return {name}.fromJSONField({type_args_for_call}, json)
               }}
+            {class_tail}"#,
+            name = self.name,
+            type_name_decl = type_name_decl,
+            num_type_params = num_type_params,
+            type_param_extends = type_param_extends,
+            type_params_use = type_params_use,
+            is_phantom_array = is_phantom_array,
+            full_type_name_with_generics = full_type_name_with_generics,
+            full_type_as_type_with_generics = full_type_as_type_with_generics,
+            type_args_field_type = type_args_field_type,
+            type_args_as_phantom = self.emit_type_args_as_phantom(),
+            reified_type_params = reified_type_params,
+            to_phantom_type_args = to_phantom_type_args,
+            extract_types = extract_types,
+            extract_type_vars = self.emit_extract_type_vars(),
+            inner_fields_section = inner_fields_section,
+            reified_bcs_init = reified_bcs_init,
+            reified_args_list = reified_args_list,
+            reified_arg_names = self.emit_reified_arg_names(),
+            reified_args_for_static = self.emit_reified_args_for_static(),
+            reified_arg_vars = self.emit_reified_arg_vars(),
+            reified_arg_first = self.emit_reified_arg_first(),
+            type_args_array = self.emit_type_args_array(),
+            type_args_for_call = self.emit_type_args_for_call(),
+            type_arg_checks = self.emit_type_arg_checks(),
+            bcs_section = bcs_section,
+            from_bcs_body = from_bcs_body,
+            field_decls = field_decls.join("\n"),
+            field_assignments = field_assignments.join("\n"),
+            from_fields_decodes = from_fields_decodes.join("\n"),
+            from_fields_with_types_decodes = from_fields_with_types_decodes.join("\n"),
+            to_json_fields = to_json_fields.join("\n"),
+            from_json_decodes = from_json_decodes.join("\n"),
+            abilities = self.emit_abilities_array(),
+            object_reified_members = self.emit_object_reified_members(),
+            validate_json = self.emit_from_json_validation(),
+            class_tail = self.emit_class_tail_with_type_params(),
+        };
+
+        // Add struct-level JSDoc if available
+        if let Some(jsdoc) = format_jsdoc(&self.doc_comment, "") {
+            format!("{}\n{}", jsdoc, class_body)
+        } else {
+            class_body
+        }
+    }
+
+    /// Emit the `$abilities` array, e.g. `['key', 'store']`.
+    fn emit_abilities_array(&self) -> String {
+        let names: Vec<String> = self
+            .abilities
+            .iter()
+            .map(|a| format!("'{}'", a.as_str()))
+            .collect();
+        format!("[{}]", names.join(", "))
+    }
+
+    /// Emit the optional members of the reified object: the JSON validator (if enabled) and
+    /// the object-only members (if the struct has `key`).
+    fn emit_object_reified_members(&self) -> String {
+        let name = &self.name;
+        let args = if self.type_params.is_empty() {
+            String::new()
+        } else {
+            format!("{}, ", self.emit_reified_args_for_static())
+        };
+        let mut members = Vec::new();
+        if self.json_validators {
+            members.push(format!("      validateJSONField: (field: unknown, path: string) => validate{name}JSONField({args}field, path),"));
+        }
+        if self.has_key() {
+            members.extend([
+                format!("      fromCoreObject: (obj: SuiClientTypes.Object<{{ content: true }}>) => {name}.fromCoreObject({args}obj),"),
+                format!("      fromSuiParsedData: (content: SuiParsedData) => {name}.fromSuiParsedData({args}content),"),
+                format!("      fromSuiObjectData: (content: SuiObjectData) => {name}.fromSuiObjectData({args}content),"),
+                format!("      fetch: async (client: ClientWithCoreApi, id: string) => {name}.fetch(client, {args}id),"),
+            ]);
+        }
+        members.iter().map(|line| format!("\n{}", line)).collect()
+    }
+
+    /// Emit the object-only methods (`fromCoreObject`, `fetch`, ...) of a `key` struct
+    /// without type params, followed by the closing brace of the class.
+    fn emit_class_tail_no_type_params(&self) -> String {
+        if !self.has_key() {
+            return "}".to_string();
+        }
+        formatdoc! {r#"
+
+              static fromCoreObject(obj: SuiClientTypes.Object<{{ content: true }}>): {name} {{
+                if (!is{name}(obj.type)) {{
+                  throw new Error(`object at ${{obj.objectId}} is not a {name} object`)
+                }}
+                return {name}.fromBcs(obj.content)
+              }}
+
+              /** @deprecated `SuiParsedData` is a JSON-RPC-only type that is being phased out upstream. Use {{@link {name}.fromCoreObject}} together with `client.core.getObject({{ include: {{ content: true }} }})` for transport-agnostic parsing. */
+              static fromSuiParsedData(content: SuiParsedData): {name} {{
+                if (content.dataType !== 'moveObject') {{
+                  throw new Error('not an object')
+                }}
+                if (!is{name}(content.type)) {{
+                  throw new Error(`object at ${{(content.fields as any).id}} is not a {name} object`)
+                }}
+                return {name}.fromFieldsWithTypes(content)
+              }}
+
+              /** @deprecated `SuiObjectData` is a JSON-RPC-only type that is being phased out upstream. Use {{@link {name}.fromCoreObject}} together with `client.core.getObject({{ include: {{ content: true }} }})` for transport-agnostic parsing. */
+              static fromSuiObjectData(data: SuiObjectData): {name} {{
+                if (data.bcs) {{
+                  if (data.bcs.dataType !== 'moveObject' || !is{name}(data.bcs.type)) {{
+                    throw new Error(`object at is not a {name} object`)
+                  }}
+
+                  return {name}.fromBcs(fromBase64(data.bcs.bcsBytes))
+                }}
+                if (data.content) {{
+                  return {name}.fromSuiParsedData(data.content)
+                }}
+                throw new Error(
+                  'Both `bcs` and `content` fields are missing from the data. Include `showBcs` or `showContent` in the request.'
+                )
+              }}
+
+              static async fetch(client: ClientWithCoreApi, id: string): Promise<{name}> {{
+                const {{ object }} = await client.core.getObject({{
+                  objectId: id,
+                  include: {{ content: true }},
+                }})
+                if (!is{name}(object.type)) {{
+                  throw new Error(`object at id ${{id}} is not a {name} object`)
+                }}
+                return {name}.fromBcs(object.content)
+              }}
+
+              /** Fetch all `{name}` objects owned by `owner`, paging through the results. */
+              static async listOwned(client: ClientWithCoreApi, owner: string): Promise<{name}[]> {{
+                const objects: {name}[] = []
+                let cursor: string | null = null
+                do {{
+                  const page = await client.core.listOwnedObjects({{
+                    owner,
+                    type: {name}.$typeName,
+                    cursor,
+                    include: {{ content: true }},
+                  }})
+                  objects.push(...page.objects.map(obj => {name}.fromCoreObject(obj)))
+                  cursor = page.hasNextPage ? page.cursor : null
+                }} while (cursor !== null)
+                return objects
+              }}
+            }}"#,
+            name = self.name,
+        }
+    }
+
+    /// Emit the object-only methods (`fromCoreObject`, `fetch`, ...) of a `key` struct
+    /// with type params, followed by the closing brace of the class.
+    fn emit_class_tail_with_type_params(&self) -> String {
+        if !self.has_key() {
+            return "}".to_string();
+        }
+        formatdoc! {r#"
 
               static fromCoreObject{reified_type_params}(
                 {reified_arg_first}
@@ -1225,46 +1473,108 @@ impl StructIR {
 
                 return {name}.fromBcs({type_args_for_call}, object.content)
               }}
+
+              /**
+               * Fetch all `{name}` objects owned by `owner`, paging through the results.{list_owned_doc}
+               */
+              static async listOwned{reified_type_params}(
+                client: ClientWithCoreApi,
+                owner: string,
+                {list_owned_type_args}
+              ): Promise<{name}{to_phantom_type_args}[]> {{
+                const type = {list_owned_type}
+                const objects: {name}{to_phantom_type_args}[] = []
+                let cursor: string | null = null
+                do {{
+                  const page = await client.core.listOwnedObjects({{
+                    owner,
+                    type,
+                    cursor,
+                    include: {{ content: true }},
+                  }})
+                  for (const obj of page.objects) {{
+                    objects.push({name}.fromCoreObject({list_owned_decode_args}, obj))
+                  }}
+                  cursor = page.hasNextPage ? page.cursor : null
+                }} while (cursor !== null)
+                return objects
+              }}
             }}"#,
             name = self.name,
-            type_name_decl = type_name_decl,
-            num_type_params = num_type_params,
-            type_param_extends = type_param_extends,
-            type_params_use = type_params_use,
-            is_phantom_array = is_phantom_array,
-            full_type_name_with_generics = full_type_name_with_generics,
-            full_type_as_type_with_generics = full_type_as_type_with_generics,
-            type_args_field_type = type_args_field_type,
-            type_args_as_phantom = self.emit_type_args_as_phantom(),
-            reified_type_params = reified_type_params,
-            to_phantom_type_args = to_phantom_type_args,
-            extract_types = extract_types,
-            extract_type_vars = self.emit_extract_type_vars(),
-            inner_fields_section = inner_fields_section,
-            reified_bcs_init = reified_bcs_init,
-            reified_args_list = reified_args_list,
-            reified_arg_names = self.emit_reified_arg_names(),
-            reified_args_for_static = self.emit_reified_args_for_static(),
-            reified_arg_vars = self.emit_reified_arg_vars(),
+            num_type_params = self.type_params.len(),
+            reified_type_params = self.emit_reified_type_params(),
             reified_arg_first = self.emit_reified_arg_first(),
-            type_args_array = self.emit_type_args_array(),
+            to_phantom_type_args = self.emit_to_phantom_type_args(),
             type_args_for_call = self.emit_type_args_for_call(),
             type_arg_checks = self.emit_type_arg_checks(),
-            bcs_section = bcs_section,
-            from_bcs_body = from_bcs_body,
-            field_decls = field_decls.join("\n"),
-            field_assignments = field_assignments.join("\n"),
-            from_fields_decodes = from_fields_decodes.join("\n"),
-            from_fields_with_types_decodes = from_fields_with_types_decodes.join("\n"),
-            to_json_fields = to_json_fields.join("\n"),
-            from_json_decodes = from_json_decodes.join("\n"),
-        };
+            list_owned_doc = self.emit_list_owned_doc(),
+            list_owned_type_args = self.emit_list_owned_type_args(),
+            list_owned_type = self.emit_list_owned_type(),
+            list_owned_decode_args = self.emit_list_owned_decode_args(),
+        }
+    }
 
-        // Add struct-level JSDoc if available
-        if let Some(jsdoc) = format_jsdoc(&self.doc_comment, "") {
-            format!("{}\n{}", jsdoc, class_body)
+    /// Returns true if every type param is phantom, in which case `listOwned` can decode objects
+    /// of any instantiation without being given the type args.
+    fn all_type_params_phantom(&self) -> bool {
+        self.type_params.iter().all(|p| p.is_phantom)
+    }
+
+    fn emit_list_owned_doc(&self) -> String {
+        if self.all_type_params_phantom() {
+            "\n   * If type arguments are given, only objects of that instantiation are returned."
+                .to_string()
         } else {
-            class_body
+            "\n   * Only objects of the given instantiation are returned.".to_string()
+        }
+    }
+
+    /// Emit the type args param of `listOwned`: `typeArg?: T,` or `typeArgs: [A, B],`
+    fn emit_list_owned_type_args(&self) -> String {
+        let param = self.emit_reified_arg_first();
+        if self.all_type_params_phantom() {
+            param.replacen(':', "?:", 1)
+        } else {
+            param
+        }
+    }
+
+    /// Emit the owned object type filter of `listOwned`. Objects of every instantiation are
+    /// listed if the (optional) type args are omitted.
+    fn emit_list_owned_type(&self) -> String {
+        let composed = format!(
+            "composeSuiType({}.$typeName, {})",
+            self.name,
+            self.emit_extract_type_vars()
+        );
+        if self.all_type_params_phantom() {
+            format!(
+                "{} ? {} : {}.$typeName",
+                self.emit_type_args_for_call(),
+                composed,
+                self.name
+            )
+        } else {
+            composed
+        }
+    }
+
+    /// Emit the type args passed to `fromCoreObject` in `listOwned`, falling back to the
+    /// object's own type args if none were given.
+    fn emit_list_owned_decode_args(&self) -> String {
+        if !self.all_type_params_phantom() {
+            return self.emit_type_args_for_call();
+        }
+        if self.type_params.len() == 1 {
+            format!(
+                "typeArg ?? (phantom(parseTypeName(obj.type).typeArgs[0]) as {})",
+                self.type_params[0].name
+            )
+        } else {
+            format!(
+                "typeArgs ?? (parseTypeName(obj.type).typeArgs.map(t => phantom(t)) as [{}])",
+                self.emit_reified_arg_names()
+            )
         }
     }
 
@@ -2211,6 +2521,8 @@ mod tests {
             has_non_phantom_type_params: false,
             uses_field_to_json: false,
             doc_comment: None,
+            abilities: vec![Ability::Key],
+            json_validators: false,
         }
     }
 
@@ -2224,6 +2536,39 @@ mod tests {
         assert!(output.contains("export class Clock"));
         assert!(output.contains("timestampMs: ToField<'u64'>"));
         assert!(output.contains("`0x2::clock::Clock`")); // Full type name with address
+        assert!(output.contains("static readonly $abilities = ['key'] as const"));
+        assert!(
+            output.contains("export type ClockReified = KeyStructClassReified<Clock, ClockFields>")
+        );
+        assert!(output.contains("static async fetch(client: ClientWithCoreApi, id: string)"));
+        assert!(output.contains(
+            "static async listOwned(client: ClientWithCoreApi, owner: string): Promise<Clock[]>"
+        ));
+        assert!(output.contains("type: Clock.$typeName,"));
+    }
+
+    #[test]
+    fn test_json_validators() {
+        let mut clock = make_clock_ir();
+        let output = clock.emit("../../_framework");
+        assert!(!output.contains("validateClockJSONField"));
+
+        clock.json_validators = true;
+        let output = clock.emit("../../_framework");
+        assert!(output.contains(
+            "import { validateJSONField, validateObject } from '../../_framework/validate'"
+        ));
+        assert!(output.contains(
+            "export function validateClockJSONField(field: unknown, path = '$'): void {"
+        ));
+        assert!(output.contains("  validateJSONField(UID.reified(), obj.id, `${path}.id`)"));
+        assert!(
+            output.contains("  validateJSONField('u64', obj.timestampMs, `${path}.timestampMs`)")
+        );
+        assert!(output.contains("    validateClockJSONField(json)\n"));
+        assert!(output.contains(
+            "validateJSONField: (field: unknown, path: string) => validateClockJSONField(field, path),"
+        ));
     }
 
     #[test]
@@ -2258,6 +2603,8 @@ mod tests {
             has_non_phantom_type_params: false,
             uses_field_to_json: false,
             doc_comment: None,
+            abilities: vec![Ability::Store],
+            json_validators: false,
         }
     }
 
@@ -2271,6 +2618,44 @@ mod tests {
         assert!(output.contains("export class Supply<T extends PhantomTypeArgument>"));
         assert!(output.contains("static readonly $isPhantom = [true] as const"));
         assert!(output.contains("extractType(T)"));
+        assert!(output.contains("static readonly $abilities = ['store'] as const"));
+        // Not an object, so it can't be fetched
+        assert!(!output.contains("fetch"));
+        assert!(!output.contains("fromCoreObject"));
+        assert!(!output.contains("listOwned"));
+    }
+
+    #[test]
+    fn test_list_owned_with_phantom_type_param() {
+        let mut strct = make_supply_ir();
+        strct.abilities = vec![Ability::Key, Ability::Store];
+        let output = strct.emit("../../_framework");
+
+        // Type args are optional, objects of any instantiation are decoded with their own
+        assert!(
+            output.contains("    typeArg?: T,\n  ): Promise<Supply<ToPhantomTypeArgument<T>>[]>")
+        );
+        assert!(output.contains(
+            "const type = typeArg ? composeSuiType(Supply.$typeName, ...[extractType(typeArg)]) : Supply.$typeName"
+        ));
+        assert!(output.contains(
+            "Supply.fromCoreObject(typeArg ?? (phantom(parseTypeName(obj.type).typeArgs[0]) as T), obj)"
+        ));
+    }
+
+    #[test]
+    fn test_list_owned_with_non_phantom_type_param() {
+        let mut strct = make_supply_ir();
+        strct.abilities = vec![Ability::Key];
+        strct.type_params[0].is_phantom = false;
+        strct.has_non_phantom_type_params = true;
+        let output = strct.emit("../../_framework");
+
+        // Non-phantom type args are needed to decode, so they're required
+        assert!(output.contains("    typeArg: T,\n  ): Promise<Supply<ToTypeArgument<T>>[]>"));
+        assert!(output
+            .contains("const type = composeSuiType(Supply.$typeName, ...[extractType(typeArg)])"));
+        assert!(output.contains("Supply.fromCoreObject(typeArg, obj)"));
     }
 
     #[test]
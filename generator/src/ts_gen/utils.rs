@@ -1,8 +1,15 @@
 //! Shared utility functions for TypeScript code generation.
 
+use std::collections::BTreeMap;
+
 use convert_case::{Case, Casing};
+use move_core_types::account_address::AccountAddress;
 use move_symbol_pool::Symbol;
 
+/// TypeScript names of structs, enums and functions renamed in `[naming.identifiers]`,
+/// keyed by package address, module name and Move name.
+pub type IdentifierRenames = BTreeMap<(AccountAddress, Symbol, Symbol), String>;
+
 /// JavaScript reserved words that cannot be used as identifiers.
 /// This comprehensive list includes:
 /// - ECMAScript reserved words (strict mode)
@@ -52,3 +59,31 @@ pub fn package_import_name(pkg_name: Symbol) -> String {
         .from_case(Case::Snake)
         .to_case(Case::Kebab)
 }
+
+/// Returns the TypeScript name of a struct or enum, which is its Move name unless renamed.
+pub fn datatype_ts_name(
+    renames: Option<&IdentifierRenames>,
+    pkg_addr: AccountAddress,
+    module: Symbol,
+    name: Symbol,
+) -> String {
+    renames
+        .and_then(|renames| renames.get(&(pkg_addr, module, name)))
+        .cloned()
+        .unwrap_or_else(|| name.to_string())
+}
+
+/// Returns the default TypeScript name of a Move function (camel case, sanitized).
+pub fn function_ts_name(move_name: &str) -> String {
+    // Use from_case(Snake) to prevent digit-letter boundaries from being treated as word splits
+    let ts_name = move_name.from_case(Case::Snake).to_case(Case::Camel);
+
+    let mut sanitized = sanitize_identifier(&ts_name);
+
+    // Handle trailing underscore in Move name
+    if move_name.ends_with('_') && !sanitized.ends_with('_') {
+        sanitized.push('_');
+    }
+
+    sanitized
+}
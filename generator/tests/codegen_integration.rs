@@ -0,0 +1,414 @@
+//! Integration tests for code generation from models built from the fixture packages.
+//!
+//! Uses the `tests/fixtures/basic` fixture. In `pkg_unpublished_toplevel`:
+//! - `main` has a `public`, an `entry`, a `public(package)` and a private function
+//! - `events` emits `Created` and the generic `Wrapped<T>`, but not `NotAnEvent`
+//! - `caller` calls the emitting functions of `events`, without emitting anything itself
+//! - `main::UnpubStruct` has a field of type `pkg_unpublished_transitive::lib::TransitiveStruct`
+//!
+//! ## Running Tests
+//! ```bash
+//! cargo test --test codegen_integration
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use move_core_types::account_address::AccountAddress;
+use move_model_2::source_model;
+use move_symbol_pool::Symbol;
+
+use sui_client_gen::driver::resolve_identifier_renames;
+use sui_client_gen::filter::Selection;
+use sui_client_gen::graphql::GraphQLCache;
+use sui_client_gen::manifest::{parse_gen_manifest_from_file, GenManifest, Naming};
+use sui_client_gen::multi_env::{build_multi_env_models, MultiEnvResult};
+use sui_client_gen::ts_gen::{self, IdentifierRenames};
+
+/// Get the path to basic test fixtures
+fn fixtures_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/basic")
+}
+
+/// Build the models for the fixture gen.toml, like the driver does.
+async fn build_fixture(manifest: &GenManifest) -> MultiEnvResult {
+    let gen_toml_path = fixtures_path().join("gen.toml");
+    let mut graphql_cache = GraphQLCache::new();
+
+    build_multi_env_models(manifest, &gen_toml_path, &mut graphql_cache, None)
+        .await
+        .expect("Failed to build model")
+}
+
+fn parse_fixture_manifest() -> GenManifest {
+    parse_gen_manifest_from_file(&fixtures_path().join("gen.toml"))
+        .expect("Failed to parse fixture gen.toml")
+}
+
+/// Packages of the default environment's model, by address.
+fn fixture_packages(
+    result: &MultiEnvResult,
+) -> BTreeMap<AccountAddress, source_model::Package<'_>> {
+    result
+        .default_model
+        .model
+        .packages()
+        .map(|pkg| (pkg.address(), pkg))
+        .collect()
+}
+
+/// Apply the manifest filters, like the driver does.
+fn fixture_selection(
+    result: &MultiEnvResult,
+    manifest: &GenManifest,
+    pkgs: &BTreeMap<AccountAddress, source_model::Package>,
+    all_functions: bool,
+) -> Selection {
+    Selection::new(
+        pkgs,
+        &result.default_model.id_map,
+        &result.top_level_addr_map,
+        &manifest.filters,
+        &manifest.dep_functions,
+        all_functions,
+    )
+    .expect("Failed to apply filters")
+}
+
+/// Find a top-level package by its name in gen.toml.
+fn top_level_package<'a, 'model>(
+    result: &MultiEnvResult,
+    pkgs: &'a BTreeMap<AccountAddress, source_model::Package<'model>>,
+    pkg_name: &str,
+) -> &'a source_model::Package<'model> {
+    pkgs.iter()
+        .find(|(addr, _)| {
+            result
+                .top_level_addr_map
+                .get(*addr)
+                .is_some_and(|name| name.as_str() == pkg_name)
+        })
+        .map(|(_, pkg)| pkg)
+        .expect("top-level package should exist")
+}
+
+/// Generate functions.ts of a top-level module, with or without `all-functions`.
+fn gen_functions(
+    result: &MultiEnvResult,
+    manifest: &GenManifest,
+    pkg_name: &str,
+    module_name: &str,
+    all_functions: bool,
+) -> String {
+    let pkgs = fixture_packages(result);
+    let selection = fixture_selection(result, manifest, &pkgs, all_functions);
+    let pkg = top_level_package(result, &pkgs, pkg_name);
+    let module = pkg
+        .modules()
+        .find(|m| m.name().as_str() == module_name)
+        .expect("module should exist");
+    let module_selection = selection
+        .module(pkg.address(), module.name())
+        .expect("module should be selected");
+
+    ts_gen::gen_module_functions(
+        &module,
+        &result.folder_names,
+        &result.top_level_addr_map,
+        0,
+        all_functions,
+        module_selection,
+        &IdentifierRenames::new(),
+    )
+}
+
+/// Generate events.ts of a top-level package.
+fn gen_events(
+    result: &MultiEnvResult,
+    manifest: &GenManifest,
+    pkg_name: &str,
+    renames: &IdentifierRenames,
+) -> String {
+    let pkgs = fixture_packages(result);
+    let selection = fixture_selection(result, manifest, &pkgs, false);
+    let pkg = top_level_package(result, &pkgs, pkg_name);
+
+    ts_gen::gen_package_events(pkg, "../_framework", &selection, renames)
+}
+
+/// Generate structs.ts of a top-level module.
+fn gen_structs(
+    result: &MultiEnvResult,
+    manifest: &GenManifest,
+    pkg_name: &str,
+    module_name: &str,
+    renames: &IdentifierRenames,
+) -> String {
+    let pkgs = fixture_packages(result);
+    let selection = fixture_selection(result, manifest, &pkgs, false);
+    let pkg = top_level_package(result, &pkgs, pkg_name);
+    let module = pkg
+        .modules()
+        .find(|m| m.name().as_str() == module_name)
+        .expect("module should exist");
+    let module_selection = selection
+        .module(pkg.address(), module.name())
+        .expect("module should be selected");
+
+    ts_gen::gen_module_structs(
+        &module,
+        &result.default_model.type_origin_table,
+        &result.default_model.version_table,
+        &result.folder_names,
+        &result.top_level_addr_map,
+        0,
+        false,
+        module_selection,
+        renames,
+    )
+}
+
+/// Resolve `[naming.identifiers]` entries against the fixture packages.
+fn resolve_renames(
+    result: &MultiEnvResult,
+    identifiers: &[(&str, &str)],
+) -> anyhow::Result<IdentifierRenames> {
+    let naming = Naming {
+        identifiers: identifiers
+            .iter()
+            .map(|(path, name)| (path.to_string(), name.to_string()))
+            .collect(),
+        ..Default::default()
+    };
+    resolve_identifier_renames(
+        &naming,
+        &fixture_packages(result),
+        &result.default_model.id_map,
+        &result.top_level_addr_map,
+    )
+}
+
+// ===========================================================================
+// FUNCTION VISIBILITY TESTS
+// ===========================================================================
+
+#[tokio::test]
+async fn test_functions_callable_only_by_default() {
+    let manifest = parse_fixture_manifest();
+    let result = build_fixture(&manifest).await;
+
+    let output = gen_functions(
+        &result,
+        &manifest,
+        "pkg_unpublished_toplevel",
+        "main",
+        false,
+    );
+
+    assert!(
+        output.contains("export function publicFn("),
+        "public functions should be generated"
+    );
+    assert!(
+        output.contains("export function entryFn("),
+        "entry functions should be generated"
+    );
+    assert!(
+        !output.contains("packageFn"),
+        "public(package) functions should not be generated by default"
+    );
+    assert!(
+        !output.contains("privateFn"),
+        "private functions should not be generated by default"
+    );
+}
+
+#[tokio::test]
+async fn test_all_functions_includes_non_callable() {
+    let manifest = parse_fixture_manifest();
+    let result = build_fixture(&manifest).await;
+
+    let output = gen_functions(&result, &manifest, "pkg_unpublished_toplevel", "main", true);
+
+    for name in ["publicFn", "entryFn", "packageFn", "privateFn"] {
+        assert!(
+            output.contains(&format!("export function {}(", name)),
+            "all-functions should generate '{}'",
+            name
+        );
+    }
+}
+
+// ===========================================================================
+// EVENT TESTS
+// ===========================================================================
+
+#[tokio::test]
+async fn test_events_found_in_bytecode() {
+    let manifest = parse_fixture_manifest();
+    let result = build_fixture(&manifest).await;
+
+    let output = gen_events(
+        &result,
+        &manifest,
+        "pkg_unpublished_toplevel",
+        &IdentifierRenames::new(),
+    );
+
+    assert!(
+        output.contains("  | { kind: 'events::Created'; event: events.Created }"),
+        "Created should be an event, got:\n{}",
+        output
+    );
+    assert!(
+        output.contains("  | { kind: 'events::Wrapped'; event: events.Wrapped<any> }"),
+        "Wrapped, emitted from a generic function, should be an event, got:\n{}",
+        output
+    );
+    assert!(
+        !output.contains("NotAnEvent"),
+        "Structs that are never emitted should not be events"
+    );
+}
+
+#[tokio::test]
+async fn test_events_attributed_to_emitting_module() {
+    let manifest = parse_fixture_manifest();
+    let result = build_fixture(&manifest).await;
+
+    let output = gen_events(
+        &result,
+        &manifest,
+        "pkg_unpublished_toplevel",
+        &IdentifierRenames::new(),
+    );
+
+    assert_eq!(
+        output.matches("kind: 'events::Created'").count(),
+        1,
+        "Created should be listed once, though `caller` calls the function emitting it"
+    );
+    assert!(
+        !output.contains("caller::") && !output.contains("./caller/structs"),
+        "Calling functions that emit events doesn't make the caller's types events"
+    );
+}
+
+// ===========================================================================
+// IDENTIFIER RENAME TESTS
+// ===========================================================================
+
+#[tokio::test]
+async fn test_resolve_identifier_renames() {
+    let manifest = parse_fixture_manifest();
+    let result = build_fixture(&manifest).await;
+
+    let renames = resolve_renames(
+        &result,
+        &[
+            ("pkg_unpublished_toplevel::main::UnpubStruct", "Unpublished"),
+            ("pkg_unpublished_toplevel::main::public_fn", "callPublic"),
+        ],
+    )
+    .expect("renames should resolve");
+
+    let pkgs = fixture_packages(&result);
+    let pkg_id = top_level_package(&result, &pkgs, "pkg_unpublished_toplevel").address();
+    let key = |name: &str| (pkg_id, Symbol::from("main"), Symbol::from(name));
+    assert_eq!(renames.len(), 2);
+    assert_eq!(renames[&key("UnpubStruct")], "Unpublished");
+    assert_eq!(renames[&key("public_fn")], "callPublic");
+
+    // Packages can also be referred to by address
+    let by_address = resolve_renames(
+        &result,
+        &[(
+            &format!("{}::main::UnpubStruct", pkg_id.to_hex_literal()),
+            "Unpublished",
+        )],
+    )
+    .expect("renames by address should resolve");
+    assert_eq!(
+        by_address,
+        renames
+            .into_iter()
+            .filter(|(k, _)| *k == key("UnpubStruct"))
+            .collect()
+    );
+}
+
+#[tokio::test]
+async fn test_resolve_identifier_renames_errors() {
+    let manifest = parse_fixture_manifest();
+    let result = build_fixture(&manifest).await;
+
+    let error = |path: &str, name: &str| {
+        resolve_renames(&result, &[(path, name)])
+            .expect_err("rename should be rejected")
+            .to_string()
+    };
+
+    assert!(error("unknown_pkg::main::UnpubStruct", "X").contains("unknown package 'unknown_pkg'"));
+    assert!(error("pkg_unpublished_toplevel::missing::UnpubStruct", "X")
+        .contains("unknown module 'missing'"));
+    assert!(error("pkg_unpublished_toplevel::main::Missing", "X")
+        .contains("which is not a struct, enum or function"));
+    assert!(error("0x1::string::String", "Utf8").contains("can't be renamed"));
+    // Renamed items must not clash with the items next to them
+    assert!(
+        error("pkg_unpublished_toplevel::main::public_fn", "entryFn")
+            .contains("which is already the name of 'main::entry_fn'")
+    );
+    assert!(
+        error("pkg_unpublished_toplevel::events::Created", "NotAnEvent")
+            .contains("which is already the name of 'events::NotAnEvent'")
+    );
+}
+
+#[tokio::test]
+async fn test_renamed_struct_used_by_importers() {
+    let manifest = parse_fixture_manifest();
+    let result = build_fixture(&manifest).await;
+
+    let renames = resolve_renames(
+        &result,
+        &[
+            (
+                "pkg_unpublished_transitive::lib::TransitiveStruct",
+                "RenamedTransitive",
+            ),
+            ("pkg_unpublished_toplevel::events::Created", "CreatedEvent"),
+        ],
+    )
+    .expect("renames should resolve");
+
+    // A struct of another package, imported for a field
+    let structs = gen_structs(
+        &result,
+        &manifest,
+        "pkg_unpublished_toplevel",
+        "main",
+        &renames,
+    );
+    assert!(
+        structs.contains(
+            "import { RenamedTransitive } from '../../_dependencies/pkg-unpublished-transitive/lib/structs'"
+        ),
+        "The renamed struct should be imported under its new name, got:\n{}",
+        structs
+    );
+    assert!(
+        structs.contains("inner: ToField<RenamedTransitive>"),
+        "Fields should use the renamed class, got:\n{}",
+        structs
+    );
+    assert!(!structs.contains("TransitiveStruct>"));
+
+    // An event class, referenced by events.ts
+    let events = gen_events(&result, &manifest, "pkg_unpublished_toplevel", &renames);
+    assert!(
+        events.contains("  | { kind: 'events::Created'; event: events.CreatedEvent }"),
+        "Events should use the renamed class and keep the Move name as kind, got:\n{}",
+        events
+    );
+}
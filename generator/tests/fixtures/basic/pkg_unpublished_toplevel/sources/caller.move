@@ -0,0 +1,14 @@
+module pkg_unpublished_toplevel::caller;
+
+use pkg_unpublished_toplevel::events;
+
+/// Not an event, though `emit_both` emits the events of the `events` module
+public struct Emitted has copy, drop {
+    count: u64,
+}
+
+public fun emit_both(value: u64): Emitted {
+    events::emit_created(value);
+    events::emit_wrapped(value);
+    Emitted { count: 2 }
+}
@@ -0,0 +1,30 @@
+module pkg_unpublished_toplevel::events;
+
+use sui::event;
+
+/// Emitted by `emit_created`
+public struct Created has copy, drop {
+    value: u64,
+}
+
+/// Emitted by the generic `emit_wrapped`
+public struct Wrapped<T: copy + drop> has copy, drop {
+    value: T,
+}
+
+/// Returned, but never emitted
+public struct NotAnEvent has copy, drop {
+    value: u64,
+}
+
+public fun emit_created(value: u64) {
+    event::emit(Created { value });
+}
+
+public fun emit_wrapped<T: copy + drop>(value: T) {
+    event::emit(Wrapped { value });
+}
+
+public fun not_an_event(value: u64): NotAnEvent {
+    NotAnEvent { value }
+}
@@ -8,3 +8,24 @@ public struct UnpubStruct has key {
     id: UID,
     inner: TransitiveStruct,
 }
+
+/// Callable from a transaction
+public fun public_fn(value: u64): u64 {
+    value
+}
+
+/// Callable from a transaction, but not from other packages
+entry fun entry_fn(value: u64) {
+    package_fn(value);
+    private_fn(value);
+}
+
+/// Only callable from the same package
+public(package) fun package_fn(value: u64): u64 {
+    value
+}
+
+/// Only callable from the same module
+fun private_fn(value: u64): u64 {
+    value
+}
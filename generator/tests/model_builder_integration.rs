@@ -25,7 +25,7 @@ use std::sync::OnceLock;
 use move_core_types::account_address::AccountAddress;
 use move_package_alt::schema::PackageName;
 
-use sui_client_gen::graphql::GraphQLClient;
+use sui_client_gen::graphql::{GraphQLCache, GraphQLHeaders};
 use sui_client_gen::model_builder::{self, ModelResult, TypeOriginTable, VersionTable};
 
 /// Cached test data - contains only the Sync fields from ModelResult.
@@ -94,13 +94,13 @@ async fn build_fixture_model_internal() -> ModelResult {
     let manifest = sui_client_gen::manifest::parse_gen_manifest_from_file(&gen_toml_path)
         .expect("Failed to parse fixture gen.toml");
 
-    // Use a GraphQL client - published packages will query testnet
+    // Use a GraphQL cache - published packages will query testnet
     let graphql_endpoint = sui_client_gen::resolve_graphql(
         manifest.config.graphql.as_deref(),
         &manifest.config.environment,
         &manifest.environments,
     );
-    let graphql_client = GraphQLClient::new(&graphql_endpoint);
+    let mut graphql_cache = GraphQLCache::new();
 
     // Resolve chain ID for the environment
     let chain_id = sui_client_gen::resolve_chain_id(
@@ -116,7 +116,10 @@ async fn build_fixture_model_internal() -> ModelResult {
         &chain_id,
         &manifest.environments,
         &manifest.dep_replacements,
-        &graphql_client,
+        &mut graphql_cache,
+        &graphql_endpoint,
+        &GraphQLHeaders::new(),
+        None,
     )
     .await
     .expect("Failed to build model")
@@ -437,7 +440,7 @@ fn test_version_table_published_package_has_versions() {
 }
 
 #[test]
-fn test_version_table_newest_is_v1() {
+fn test_version_table_original_is_v1() {
     let data = get_cached_data();
 
     let pkg_addr = get_package_addr_cached(data, "pkg_published_toplevel")
@@ -448,24 +451,23 @@ fn test_version_table_newest_is_v1() {
         .get(&pkg_addr)
         .expect("version_table should contain pkg_published_toplevel");
 
-    // The newest version (published-at) should be V1
-    let published_at_addr =
-        AccountAddress::from_hex_literal(known_addresses::PKG_PUBLISHED_TOPLEVEL_PUBLISHED_AT)
-            .unwrap();
+    // The original package is the first on-chain version
+    let original_addr =
+        AccountAddress::from_hex_literal(known_addresses::PKG_PUBLISHED_TOPLEVEL_ORIGINAL).unwrap();
 
-    let v1_version = versions
-        .get(&published_at_addr)
-        .expect("published-at address should be in version_table");
+    let original_version = versions
+        .get(&original_addr)
+        .expect("original-id address should be in version_table");
 
     assert_eq!(
-        v1_version.value(),
+        original_version.value(),
         1,
-        "Newest version (published-at) should be V1"
+        "Original package should be on-chain version 1"
     );
 }
 
 #[test]
-fn test_version_table_original_is_v2() {
+fn test_version_table_published_at_is_v2() {
     let data = get_cached_data();
 
     let pkg_addr = get_package_addr_cached(data, "pkg_published_toplevel")
@@ -476,18 +478,19 @@ fn test_version_table_original_is_v2() {
         .get(&pkg_addr)
         .expect("version_table should contain pkg_published_toplevel");
 
-    // The original version should be V2 (older)
-    let original_addr =
-        AccountAddress::from_hex_literal(known_addresses::PKG_PUBLISHED_TOPLEVEL_ORIGINAL).unwrap();
+    // The published-at package is the upgrade, i.e. on-chain version 2
+    let published_at_addr =
+        AccountAddress::from_hex_literal(known_addresses::PKG_PUBLISHED_TOPLEVEL_PUBLISHED_AT)
+            .unwrap();
 
-    let v2_version = versions
-        .get(&original_addr)
-        .expect("original-id address should be in version_table");
+    let published_at_version = versions
+        .get(&published_at_addr)
+        .expect("published-at address should be in version_table");
 
     assert_eq!(
-        v2_version.value(),
+        published_at_version.value(),
         2,
-        "Original version should be V2 (older than newest)"
+        "Upgraded package (published-at) should be on-chain version 2"
     );
 }
 
@@ -653,7 +656,7 @@ async fn test_custom_env_model_build() {
         &manifest.config.environment,
         &manifest.environments,
     );
-    let graphql_client = GraphQLClient::new(&graphql_endpoint);
+    let mut graphql_cache = GraphQLCache::new();
 
     let chain_id = sui_client_gen::resolve_chain_id(
         &manifest.config.environment,
@@ -668,7 +671,10 @@ async fn test_custom_env_model_build() {
         &chain_id,
         &manifest.environments,
         &manifest.dep_replacements,
-        &graphql_client,
+        &mut graphql_cache,
+        &graphql_endpoint,
+        &GraphQLHeaders::new(),
+        None,
     )
     .await
     .expect("Failed to build model");
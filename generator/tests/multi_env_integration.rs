@@ -3,6 +3,7 @@
 //! This test suite verifies:
 //! - `multi_env_basic`: Compatible environments (same struct structure) should succeed
 //! - `multi_env_fail`: Incompatible environments (different struct fields) should fail
+//! - `multi_env_fail` with filters: only the generated items are checked
 //!
 //! ## Running Tests
 //! ```bash
@@ -12,7 +13,7 @@
 use std::path::PathBuf;
 
 use sui_client_gen::graphql::GraphQLCache;
-use sui_client_gen::manifest::parse_gen_manifest_from_file;
+use sui_client_gen::manifest::{parse_gen_manifest_from_file, PackageFilter};
 use sui_client_gen::multi_env::build_multi_env_models;
 
 /// Get the path to multi_env_basic fixtures
@@ -39,7 +40,8 @@ async fn test_multi_env_basic_succeeds() {
 
     let mut graphql_cache = GraphQLCache::new();
 
-    let result = build_multi_env_models(&manifest, &gen_toml_path, &mut graphql_cache).await;
+    let result =
+        build_multi_env_models(&manifest, &gen_toml_path, &mut graphql_cache, None).await;
 
     assert!(
         result.is_ok(),
@@ -96,7 +98,8 @@ async fn test_multi_env_basic_asymmetry_allowed() {
 
     let mut graphql_cache = GraphQLCache::new();
 
-    let result = build_multi_env_models(&manifest, &gen_toml_path, &mut graphql_cache).await;
+    let result =
+        build_multi_env_models(&manifest, &gen_toml_path, &mut graphql_cache, None).await;
 
     assert!(
         result.is_ok(),
@@ -123,7 +126,8 @@ async fn test_multi_env_fail_incompatible() {
 
     let mut graphql_cache = GraphQLCache::new();
 
-    let result = build_multi_env_models(&manifest, &gen_toml_path, &mut graphql_cache).await;
+    let result =
+        build_multi_env_models(&manifest, &gen_toml_path, &mut graphql_cache, None).await;
 
     let error = match result {
         Err(e) => e,
@@ -154,7 +158,8 @@ async fn test_multi_env_fail_error_mentions_envs() {
 
     let mut graphql_cache = GraphQLCache::new();
 
-    let result = build_multi_env_models(&manifest, &gen_toml_path, &mut graphql_cache).await;
+    let result =
+        build_multi_env_models(&manifest, &gen_toml_path, &mut graphql_cache, None).await;
 
     let error = match result {
         Err(e) => e,
@@ -180,7 +185,8 @@ async fn test_multi_env_fail_error_mentions_field_difference() {
 
     let mut graphql_cache = GraphQLCache::new();
 
-    let result = build_multi_env_models(&manifest, &gen_toml_path, &mut graphql_cache).await;
+    let result =
+        build_multi_env_models(&manifest, &gen_toml_path, &mut graphql_cache, None).await;
 
     let error = match result {
         Err(e) => e,
@@ -195,3 +201,71 @@ async fn test_multi_env_fail_error_mentions_field_difference() {
         error_msg
     );
 }
+
+// ===========================================================================
+// FILTERED MULTI-ENV TESTS (compat checks only cover generated items)
+// ===========================================================================
+
+fn package_filter(exclude_modules: &[&str], items: &[&str]) -> PackageFilter {
+    PackageFilter {
+        exclude_modules: exclude_modules.iter().map(|s| s.to_string()).collect(),
+        items: items.iter().map(|s| s.to_string()).collect(),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_multi_env_fail_filtered_out_struct_not_checked() {
+    // DepStruct is incompatible, but neither generated nor referenced by anything generated
+    let fixtures = multi_env_fail_path();
+    let gen_toml_path = fixtures.join("gen.toml");
+
+    let mut manifest =
+        parse_gen_manifest_from_file(&gen_toml_path).expect("Failed to parse fixture gen.toml");
+    manifest.filters.insert(
+        "root".to_string(),
+        package_filter(&[], &["!main::RootStruct"]),
+    );
+    manifest
+        .filters
+        .insert("dep".to_string(), package_filter(&["lib"], &[]));
+
+    let mut graphql_cache = GraphQLCache::new();
+
+    let result =
+        build_multi_env_models(&manifest, &gen_toml_path, &mut graphql_cache, None).await;
+
+    assert!(
+        result.is_ok(),
+        "Filtered out items should not be checked for compatibility: {:?}",
+        result.err()
+    );
+}
+
+#[tokio::test]
+async fn test_multi_env_fail_referenced_struct_still_checked() {
+    // DepStruct's module is filtered out, but RootStruct's field keeps it generated
+    let fixtures = multi_env_fail_path();
+    let gen_toml_path = fixtures.join("gen.toml");
+
+    let mut manifest =
+        parse_gen_manifest_from_file(&gen_toml_path).expect("Failed to parse fixture gen.toml");
+    manifest
+        .filters
+        .insert("dep".to_string(), package_filter(&["lib"], &[]));
+
+    let mut graphql_cache = GraphQLCache::new();
+
+    let result =
+        build_multi_env_models(&manifest, &gen_toml_path, &mut graphql_cache, None).await;
+
+    let error = match result {
+        Err(e) => e,
+        Ok(_) => panic!("DepStruct is still generated, so it should be checked"),
+    };
+    assert!(
+        error.to_string().contains("DepStruct"),
+        "Error should mention the incompatible struct name: {}",
+        error
+    );
+}
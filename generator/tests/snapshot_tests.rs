@@ -10,8 +10,8 @@
 //! The fixtures are modeled after real Move structs/enums in `move/examples/`.
 
 use sui_client_gen::ts_gen::{
-    DatatypeImport, DatatypeKind, EnumIR, EnumVariantIR, FieldIR, FieldTypeIR, PackageInfo,
-    StructIR, TypeParamIR,
+    Ability, DatatypeImport, DatatypeKind, EnumIR, EnumVariantIR, FieldIR, FieldTypeIR,
+    PackageInfo, StructIR, TypeParamIR,
 };
 
 // =============================================================================
@@ -274,6 +274,8 @@ fn make_fixture_dummy_ir() -> StructIR {
         has_non_phantom_type_params: false,
         uses_field_to_json: false,
         doc_comment: None,
+        abilities: vec![Ability::Store],
+        json_validators: false,
     }
 }
 
@@ -298,6 +300,8 @@ fn make_fixture_bar_ir() -> StructIR {
         has_non_phantom_type_params: false,
         uses_field_to_json: false,
         doc_comment: None,
+        abilities: vec![Ability::Copy, Ability::Drop, Ability::Store],
+        json_validators: false,
     }
 }
 
@@ -337,6 +341,8 @@ fn make_fixture_with_generic_field_ir() -> StructIR {
         has_non_phantom_type_params: true,
         uses_field_to_json: true,
         doc_comment: None,
+        abilities: vec![Ability::Key],
+        json_validators: false,
     }
 }
 
@@ -386,6 +392,8 @@ fn make_fixture_with_two_generics_ir() -> StructIR {
         has_non_phantom_type_params: true,
         uses_field_to_json: true,
         doc_comment: None,
+        abilities: vec![Ability::Drop, Ability::Store],
+        json_validators: false,
     }
 }
 
@@ -503,6 +511,8 @@ fn make_fixture_with_special_types_ir() -> StructIR {
         has_non_phantom_type_params: true,
         uses_field_to_json: true,
         doc_comment: None,
+        abilities: vec![Ability::Key, Ability::Store],
+        json_validators: false,
     }
 }
 
@@ -575,6 +585,8 @@ fn make_fixture_with_special_types_in_vectors_ir() -> StructIR {
         has_non_phantom_type_params: true,
         uses_field_to_json: true,
         doc_comment: None,
+        abilities: vec![Ability::Key, Ability::Store],
+        json_validators: false,
     }
 }
 
@@ -755,6 +767,8 @@ fn make_fixture_foo_ir() -> StructIR {
         has_non_phantom_type_params: true,
         uses_field_to_json: true,
         doc_comment: None,
+        abilities: vec![Ability::Key],
+        json_validators: false,
     }
 }
 
@@ -869,6 +883,8 @@ fn make_enums_action_ir() -> EnumIR {
         uses_address: false,
         uses_phantom_struct_args: true,
         doc_comment: None,
+        abilities: vec![Ability::Store],
+        json_validators: false,
     }
 }
 
@@ -974,6 +990,8 @@ fn make_enums_wrapped_ir() -> StructIR {
         has_non_phantom_type_params: true,
         uses_field_to_json: true,
         doc_comment: None,
+        abilities: vec![Ability::Key, Ability::Store],
+        json_validators: false,
     }
 }
 
@@ -1009,6 +1027,14 @@ fn test_fixture_with_two_generics_snapshot() {
     insta::assert_snapshot!("fixture__with_two_generics", output);
 }
 
+#[test]
+fn test_fixture_with_two_generics_json_validators_snapshot() {
+    let mut ir = make_fixture_with_two_generics_ir();
+    ir.json_validators = true;
+    let output = ir.emit_body();
+    insta::assert_snapshot!("fixture__with_two_generics_json_validators", output);
+}
+
 #[test]
 fn test_fixture_with_special_types_snapshot() {
     let ir = make_fixture_with_special_types_ir();
@@ -1041,6 +1067,14 @@ fn test_enums_action_snapshot() {
     insta::assert_snapshot!("enums__action", output);
 }
 
+#[test]
+fn test_enums_action_json_validators_snapshot() {
+    let mut ir = make_enums_action_ir();
+    ir.json_validators = true;
+    let output = ir.emit_body();
+    insta::assert_snapshot!("enums__action_json_validators", output);
+}
+
 #[test]
 fn test_enums_wrapped_snapshot() {
     let ir = make_enums_wrapped_ir();
@@ -1289,9 +1323,17 @@ fn test_no_duplicate_null() {
 // =============================================================================
 
 use sui_client_gen::ts_gen::{
-    emit_functions_file, FunctionIR, FunctionParamIR, FunctionStructImport, ParamTypeIR,
+    emit_functions_file, FunctionIR, FunctionParamIR, FunctionStructImport, FunctionTypeParamIR,
+    ParamTypeIR,
 };
 
+fn type_param(name: &str, constraints: Vec<Ability>) -> FunctionTypeParamIR {
+    FunctionTypeParamIR {
+        name: name.to_string(),
+        constraints,
+    }
+}
+
 /// `createBar(value: u64)` - simple primitive param
 fn make_create_bar_function_ir() -> FunctionIR {
     FunctionIR {
@@ -1305,6 +1347,8 @@ fn make_create_bar_function_ir() -> FunctionIR {
             param_type: ParamTypeIR::Primitive("u64".to_string()),
             doc_comment: None,
         }],
+        returns: vec![],
+        returns_reference: false,
         struct_imports: vec![],
         uses_generic: false,
         uses_option: false,
@@ -1325,15 +1369,18 @@ fn make_create_with_generic_field_function_ir() -> FunctionIR {
         ts_name: "createWithGenericField".to_string(),
         module_name: "fixture".to_string(),
         env_pkg_name: "examples".to_string(),
-        type_params: vec!["T".to_string()],
+        type_params: vec![type_param("T", vec![Ability::Store])],
         params: vec![FunctionParamIR {
             ts_name: "genericField".to_string(),
             param_type: ParamTypeIR::TypeParam {
                 name: "T".to_string(),
                 index: 0,
+                is_key: false,
             },
             doc_comment: None,
         }],
+        returns: vec![],
+        returns_reference: false,
         struct_imports: vec![],
         uses_generic: true,
         uses_option: false,
@@ -1354,13 +1401,17 @@ fn make_create_with_two_generics_function_ir() -> FunctionIR {
         ts_name: "createWithTwoGenerics".to_string(),
         module_name: "fixture".to_string(),
         env_pkg_name: "examples".to_string(),
-        type_params: vec!["T".to_string(), "U".to_string()],
+        type_params: vec![
+            type_param("T", vec![Ability::Drop, Ability::Store]),
+            type_param("U", vec![Ability::Drop, Ability::Store]),
+        ],
         params: vec![
             FunctionParamIR {
                 ts_name: "genericField1".to_string(),
                 param_type: ParamTypeIR::TypeParam {
                     name: "T".to_string(),
                     index: 0,
+                    is_key: false,
                 },
                 doc_comment: None,
             },
@@ -1369,10 +1420,13 @@ fn make_create_with_two_generics_function_ir() -> FunctionIR {
                 param_type: ParamTypeIR::TypeParam {
                     name: "U".to_string(),
                     index: 1,
+                    is_key: false,
                 },
                 doc_comment: None,
             },
         ],
+        returns: vec![],
+        returns_reference: false,
         struct_imports: vec![],
         uses_generic: true,
         uses_option: false,
@@ -1393,7 +1447,7 @@ fn make_create_special_in_vectors_function_ir() -> FunctionIR {
         ts_name: "createSpecialInVectors".to_string(),
         module_name: "fixture".to_string(),
         env_pkg_name: "examples".to_string(),
-        type_params: vec!["T".to_string()],
+        type_params: vec![type_param("T", vec![Ability::Store])],
         params: vec![
             FunctionParamIR {
                 ts_name: "string".to_string(),
@@ -1419,6 +1473,8 @@ fn make_create_special_in_vectors_function_ir() -> FunctionIR {
                 param_type: ParamTypeIR::Vector(Box::new(ParamTypeIR::Struct {
                     class_name: "Bar".to_string(),
                     type_args: vec![],
+                    package_info: examples_pkg_for("fixture::Bar"),
+                    module_type_path: "fixture::Bar".to_string(),
                 })),
                 doc_comment: None,
             },
@@ -1435,11 +1491,14 @@ fn make_create_special_in_vectors_function_ir() -> FunctionIR {
                     ParamTypeIR::TypeParam {
                         name: "T".to_string(),
                         index: 0,
+                        is_key: false,
                     },
                 )))),
                 doc_comment: None,
             },
         ],
+        returns: vec![],
+        returns_reference: false,
         struct_imports: vec![FunctionStructImport {
             class_name: "Bar".to_string(),
             path: "./structs".to_string(),
@@ -1457,6 +1516,63 @@ fn make_create_special_in_vectors_function_ir() -> FunctionIR {
     }
 }
 
+/// `split_bar<T>(bar: &mut Bar, amount: u64): (Bar, vector<u64>, T)` - typed returns
+fn make_split_bar_function_ir() -> FunctionIR {
+    FunctionIR {
+        move_name: "split_bar".to_string(),
+        ts_name: "splitBar".to_string(),
+        module_name: "fixture".to_string(),
+        env_pkg_name: "examples".to_string(),
+        type_params: vec![type_param("T", vec![])],
+        params: vec![
+            FunctionParamIR {
+                ts_name: "bar".to_string(),
+                param_type: ParamTypeIR::Struct {
+                    class_name: "Bar".to_string(),
+                    type_args: vec![],
+                    package_info: examples_pkg_for("fixture::Bar"),
+                    module_type_path: "fixture::Bar".to_string(),
+                },
+                doc_comment: None,
+            },
+            FunctionParamIR {
+                ts_name: "amount".to_string(),
+                param_type: ParamTypeIR::Primitive("u64".to_string()),
+                doc_comment: None,
+            },
+        ],
+        returns: vec![
+            ParamTypeIR::Struct {
+                class_name: "Bar".to_string(),
+                type_args: vec![],
+                package_info: examples_pkg_for("fixture::Bar"),
+                module_type_path: "fixture::Bar".to_string(),
+            },
+            ParamTypeIR::Vector(Box::new(ParamTypeIR::Primitive("u64".to_string()))),
+            ParamTypeIR::TypeParam {
+                name: "T".to_string(),
+                index: 0,
+                is_key: false,
+            },
+        ],
+        returns_reference: false,
+        struct_imports: vec![FunctionStructImport {
+            class_name: "Bar".to_string(),
+            path: "./structs".to_string(),
+            alias: None,
+        }],
+        uses_generic: false,
+        uses_option: false,
+        aliased_util_imports: vec![],
+        uses_vector: false,
+        uses_pure: true,
+        uses_obj: true,
+        doc_comment: None,
+        is_deprecated: false,
+        deprecation_note: None,
+    }
+}
+
 // =============================================================================
 // Function Snapshot Tests
 // =============================================================================
@@ -1510,6 +1626,8 @@ fn make_deprecated_simple_function_ir() -> FunctionIR {
         env_pkg_name: "examples".to_string(),
         type_params: vec![],
         params: vec![],
+        returns: vec![],
+        returns_reference: false,
         struct_imports: vec![],
         uses_generic: false,
         uses_option: false,
@@ -1536,6 +1654,8 @@ fn make_deprecated_with_note_function_ir() -> FunctionIR {
             param_type: ParamTypeIR::Primitive("u64".to_string()),
             doc_comment: None,
         }],
+        returns: vec![],
+        returns_reference: false,
         struct_imports: vec![],
         uses_generic: false,
         uses_option: false,
@@ -1574,6 +1694,8 @@ fn make_deprecated_with_args_function_ir() -> FunctionIR {
                 doc_comment: None,
             },
         ],
+        returns: vec![],
+        returns_reference: false,
         struct_imports: vec![],
         uses_generic: false,
         uses_option: false,
@@ -1587,6 +1709,13 @@ fn make_deprecated_with_args_function_ir() -> FunctionIR {
     }
 }
 
+#[test]
+fn test_function_typed_returns_snapshot() {
+    let functions = vec![make_split_bar_function_ir()];
+    let output = emit_functions_file(&functions, "../../_framework");
+    insta::assert_snapshot!("function__typed_returns", output);
+}
+
 #[test]
 fn test_function_deprecated_snapshot() {
     let functions = vec![
@@ -1657,6 +1786,58 @@ fn test_function_generic_has_type_arg() {
     );
 }
 
+/// Split bar variant with a different name.
+fn make_renamed_split_bar_function_ir(move_name: &str, ts_name: &str) -> FunctionIR {
+    FunctionIR {
+        move_name: move_name.to_string(),
+        ts_name: ts_name.to_string(),
+        ..make_split_bar_function_ir()
+    }
+}
+
+/// Verify view names are suffixed until they don't collide with functions or other views
+#[test]
+fn test_function_view_name_collisions() {
+    let functions = vec![
+        make_renamed_split_bar_function_ir("split", "split"),
+        make_renamed_split_bar_function_ir("split_view", "splitView"),
+        make_renamed_split_bar_function_ir("split_view_", "splitView_"),
+    ];
+    let output = emit_functions_file(&functions, "../../_framework");
+
+    assert!(
+        output.contains("export async function splitView__("),
+        "View of `split` should skip the taken `splitView` and `splitView_`"
+    );
+    assert!(
+        output.contains("export async function splitViewView("),
+        "View of `split_view` should be `splitViewView`"
+    );
+    assert!(
+        output.contains("export async function splitView_View("),
+        "View of `split_view_` should be `splitView_View`"
+    );
+}
+
+/// Verify functions returning references get no view variant
+#[test]
+fn test_function_returning_reference_has_no_view() {
+    let functions = vec![FunctionIR {
+        returns_reference: true,
+        ..make_split_bar_function_ir()
+    }];
+    let output = emit_functions_file(&functions, "../../_framework");
+
+    assert!(
+        output.contains("export function splitBar("),
+        "The function itself should still be generated"
+    );
+    assert!(
+        !output.contains("splitBarView") && !output.contains("viewCall"),
+        "Functions returning references should have no view variant"
+    );
+}
+
 // =============================================================================
 // Collision Fixture Tests (Import aliasing and deduplication)
 // =============================================================================
@@ -1723,6 +1904,8 @@ fn make_collision_same_name_different_packages_ir() -> StructIR {
         has_non_phantom_type_params: false,
         uses_field_to_json: false,
         doc_comment: None,
+        abilities: vec![Ability::Key],
+        json_validators: false,
     }
 }
 
@@ -1856,6 +2039,8 @@ fn make_struct_with_phantom_type_args_ir() -> StructIR {
         has_non_phantom_type_params: false,
         uses_field_to_json: false,
         doc_comment: None,
+        abilities: vec![Ability::Store],
+        json_validators: false,
     }
 }
 
@@ -1956,6 +2141,8 @@ fn test_field_to_json_not_imported_when_unused() {
         has_non_phantom_type_params: false,
         uses_field_to_json: false, // No Vector/Option/TypeParam fields
         doc_comment: None,
+        abilities: vec![Ability::Key],
+        json_validators: false,
     };
 
     let output = emit_module_structs_from_ir(&[pool_ir], &[], "../../_framework");
@@ -2089,6 +2276,8 @@ fn make_struct_with_non_phantom_in_phantom_position_ir() -> StructIR {
         has_non_phantom_type_params: true,
         uses_field_to_json: false,
         doc_comment: None,
+        abilities: vec![Ability::Key],
+        json_validators: false,
     }
 }
 
@@ -2201,6 +2390,8 @@ fn make_enum_with_non_phantom_in_phantom_position_ir() -> EnumIR {
         uses_address: false,
         uses_phantom_struct_args: true, // True because T1 is used in phantom position
         doc_comment: None,
+        abilities: vec![Ability::Store],
+        json_validators: false,
     }
 }
 
@@ -2275,6 +2466,8 @@ fn make_documented_struct_single_line_ir() -> StructIR {
         has_non_phantom_type_params: false,
         uses_field_to_json: false,
         doc_comment: Some("A struct with single-line documentation".to_string()),
+        abilities: vec![Ability::Copy, Ability::Drop],
+        json_validators: false,
     }
 }
 
@@ -2298,6 +2491,8 @@ fn make_documented_struct_multiline_ir() -> StructIR {
         has_non_phantom_type_params: false,
         uses_field_to_json: false,
         doc_comment: Some("A struct with multi-line documentation.\n\nThis includes multiple paragraphs.".to_string()),
+        abilities: vec![Ability::Copy, Ability::Drop],
+        json_validators: false,
     }
 }
 
@@ -2321,6 +2516,8 @@ fn make_documented_struct_with_escaping_ir() -> StructIR {
         has_non_phantom_type_params: false,
         uses_field_to_json: false,
         doc_comment: Some("Doc comment with */ in the middle must be escaped".to_string()),
+        abilities: vec![Ability::Copy, Ability::Drop],
+        json_validators: false,
     }
 }
 
@@ -2393,6 +2590,8 @@ fn make_system_struct_ir() -> StructIR {
         has_non_phantom_type_params: false,
         uses_field_to_json: false,
         doc_comment: None,
+        abilities: vec![Ability::Copy, Ability::Drop, Ability::Store],
+        json_validators: false,
     }
 }
 
@@ -2421,6 +2620,8 @@ fn make_system_enum_ir() -> EnumIR {
         uses_address: false,
         uses_phantom_struct_args: false,
         doc_comment: None,
+        abilities: vec![Ability::Copy, Ability::Drop, Ability::Store],
+        json_validators: false,
     }
 }
 
@@ -2508,3 +2709,56 @@ fn test_dynamic_enum_variant_typename_delegates_via_getter() {
         output
     );
 }
+
+// =============================================================================
+// Constants and abort code snapshot tests
+// =============================================================================
+
+use move_core_types::account_address::AccountAddress;
+use move_symbol_pool::Symbol;
+use std::collections::BTreeMap;
+use sui_client_gen::ts_gen::{gen_init_errors, ConstantIR, ModuleConstantsIR};
+
+/// Constants of a module with a plain error code, a clever error and a regular constant.
+fn make_pool_constants_ir() -> ModuleConstantsIR {
+    ModuleConstantsIR {
+        constants: vec![
+            ConstantIR {
+                name: "ENotOwner".to_string(),
+                value: "0n".to_string(),
+            },
+            ConstantIR {
+                name: "EZeroInput".to_string(),
+                value: "\"Input balances cannot be zero.\"".to_string(),
+            },
+            ConstantIR {
+                name: "FEE_SCALING".to_string(),
+                value: "1000000n".to_string(),
+            },
+        ],
+        error_codes: BTreeMap::from([(0, vec!["ENotOwner".to_string()])]),
+        clever_identifiers: BTreeMap::from([(1, "EZeroInput".to_string())]),
+        clever_messages: BTreeMap::from([(2, "Input balances cannot be zero.".to_string())]),
+    }
+}
+
+#[test]
+fn test_module_constants_snapshot() {
+    let output = make_pool_constants_ir().emit("../../_framework");
+    insta::assert_snapshot!("constants__pool", output);
+}
+
+#[test]
+fn test_init_errors_snapshot() {
+    let sui = AccountAddress::TWO;
+    let app = AccountAddress::from_hex_literal("0xabc").unwrap();
+    let folder_names = BTreeMap::from([(sui, "sui".to_string()), (app, "my-app".to_string())]);
+    let top_level = BTreeMap::from([(app, Symbol::from("my_app"))]);
+    let modules = BTreeMap::from([
+        (sui, vec![Symbol::from("balance"), Symbol::from("coin")]),
+        (app, vec![Symbol::from("pool_admin")]),
+    ]);
+
+    let output = gen_init_errors(&modules, &folder_names, &top_level);
+    insta::assert_snapshot!("constants__init_errors", output);
+}
@@ -0,0 +1,89 @@
+/**
+ * Tests for the runtime ability checks: `loader.abilities` / `assertAbilities` in
+ * `_framework/loader.ts`, and the `checkAbilities` option of the generated function wrappers.
+ */
+
+import { Transaction } from '@mysten/sui/transactions'
+import { describe, expect, it } from 'vitest'
+import { assertAbilities, loader } from './gen/_framework/loader'
+import { createWithTwoGenerics } from './gen/examples/fixture/functions'
+import { Bar, Dummy, Foo, WithGenericField, WithTwoGenerics } from './gen/examples/fixture/structs'
+import { Balance } from './gen/sui/balance/structs'
+import { SUI } from './gen/sui/sui/structs'
+
+describe('loader.abilities', () => {
+  it('returns the abilities of primitives and vectors', () => {
+    expect(loader.abilities('u64')).toEqual(['copy', 'drop', 'store'])
+    expect(loader.abilities('address')).toEqual(['copy', 'drop', 'store'])
+    expect(loader.abilities('vector<vector<u8>>')).toEqual(['copy', 'drop', 'store'])
+  })
+
+  it('returns the declared abilities of structs', () => {
+    expect(loader.abilities(Bar.$typeName)).toEqual(['copy', 'drop', 'store'])
+    expect(loader.abilities(Dummy.$typeName)).toEqual(['store'])
+  })
+
+  it('drops abilities missing from non-phantom type arguments', () => {
+    expect(loader.abilities(`${WithTwoGenerics.$typeName}<u8, ${Bar.$typeName}>`)).toEqual([
+      'drop',
+      'store',
+    ])
+    expect(loader.abilities(`${WithTwoGenerics.$typeName}<u8, ${Dummy.$typeName}>`)).toEqual([
+      'store',
+    ])
+  })
+
+  it('keeps `key` only if the type arguments have `store`', () => {
+    expect(loader.abilities(`${Foo.$typeName}<${Bar.$typeName}>`)).toEqual(['key'])
+    expect(loader.abilities(`${WithGenericField.$typeName}<${Bar.$typeName}>`)).toEqual(['key'])
+    expect(
+      loader.abilities(
+        `${WithGenericField.$typeName}<${WithTwoGenerics.$typeName}<u8, ${Bar.$typeName}>>`
+      )
+    ).toEqual(['key'])
+  })
+
+  it('ignores phantom type arguments', () => {
+    expect(loader.abilities(`${Balance.$typeName}<${SUI.$typeName}>`)).toEqual(['store'])
+  })
+
+  it('never gives vectors `key`', () => {
+    expect(loader.abilities(`vector<${Foo.$typeName}<u8>>`)).toEqual([])
+  })
+
+  it('throws on unknown types', () => {
+    expect(() => loader.abilities('0x123::unknown::Type')).toThrow(/Unknown type/)
+  })
+})
+
+describe('assertAbilities', () => {
+  it('accepts types with the required abilities', () => {
+    expect(() => assertAbilities(Bar.$typeName, ['copy', 'drop'])).not.toThrow()
+    expect(() => assertAbilities('u8', [])).not.toThrow()
+  })
+
+  it('lists the missing abilities', () => {
+    expect(() => assertAbilities(Dummy.$typeName, ['drop', 'store', 'copy'])).toThrow(
+      `type ${Dummy.$typeName} is missing required abilities: drop, copy`
+    )
+  })
+})
+
+describe('checkAbilities option', () => {
+  const args = { genericField1: 1, genericField2: 2 }
+
+  it('is not checked by default', () => {
+    const tx = new Transaction()
+    expect(() => createWithTwoGenerics(tx, [Dummy.$typeName, 'u8'], args)).not.toThrow()
+  })
+
+  it('checks the type arguments against the constraints of the type parameters', () => {
+    const tx = new Transaction()
+    expect(() =>
+      createWithTwoGenerics(tx, ['u8', 'u16'], args, { checkAbilities: true })
+    ).not.toThrow()
+    expect(() =>
+      createWithTwoGenerics(tx, [Dummy.$typeName, 'u8'], args, { checkAbilities: true })
+    ).toThrow(`type ${Dummy.$typeName} is missing required abilities: drop`)
+  })
+})
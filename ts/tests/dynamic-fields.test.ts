@@ -0,0 +1,157 @@
+/**
+ * Tests for dynamic field lookups (`_framework/dynamic-fields.ts`): key encoding and value decoding
+ * of raw dynamic (object) fields and of `Table`, `Bag` and `LinkedTable` entries.
+ *
+ * The client is mocked and returns values serialized with the layouts of the Move framework, so only
+ * the requested field names and the decoding of the returned BCS values are tested here.
+ */
+
+import { bcs } from '@mysten/sui/bcs'
+import type { ClientWithCoreApi } from '@mysten/sui/client'
+import { describe, expect, it } from 'vitest'
+import {
+  bagGet,
+  getDynamicField,
+  getDynamicObjectField,
+  linkedTableGet,
+  tableGet,
+} from './gen/_framework/dynamic-fields'
+import { Bar } from './gen/examples/fixture/structs'
+import { Option } from './gen/std/option/structs'
+import { String } from './gen/std/string/structs'
+import { ID } from './gen/sui/object/structs'
+
+const PARENT = '0x00000000000000000000000000000000000000000000000000000000000000aa'
+const CHILD = '0x00000000000000000000000000000000000000000000000000000000000000bb'
+
+interface FieldName {
+  type: string
+  bcs: Uint8Array
+}
+
+/** Client whose dynamic fields all hold `value`, recording the requested field names. */
+function mockClient(value: Uint8Array): { client: ClientWithCoreApi; names: FieldName[] } {
+  const names: FieldName[] = []
+  const client = {
+    core: {
+      getDynamicField: async ({ parentId, name }: { parentId: string; name: FieldName }) => {
+        expect(parentId).toBe(PARENT)
+        names.push(name)
+        return { dynamicField: { value: { bcs: value } } }
+      },
+    },
+  } as unknown as ClientWithCoreApi
+  return { client, names }
+}
+
+describe('key encoding', () => {
+  it('encodes primitive keys', async () => {
+    const { client, names } = mockClient(bcs.bool().serialize(true).toBytes())
+
+    await getDynamicField(client, PARENT, 'u64', 7n, 'bool')
+    expect(names[0].type).toBe('u64')
+    expect(names[0].bcs).toEqual(bcs.u64().serialize(7n).toBytes())
+  })
+
+  it('encodes string and ID keys given as strings', async () => {
+    const { client, names } = mockClient(bcs.bool().serialize(true).toBytes())
+
+    await getDynamicField(client, PARENT, String.reified(), 'alice', 'bool')
+    expect(names[0].type).toBe('0x1::string::String')
+    expect(names[0].bcs).toEqual(bcs.string().serialize('alice').toBytes())
+
+    await getDynamicField(client, PARENT, ID.reified(), CHILD, 'bool')
+    expect(names[1].type).toBe('0x2::object::ID')
+    expect(names[1].bcs).toEqual(bcs.Address.serialize(CHILD).toBytes())
+  })
+
+  it('encodes option keys given as values or null', async () => {
+    const { client, names } = mockClient(bcs.bool().serialize(true).toBytes())
+    const keyType = Option.reified('u8')
+
+    await getDynamicField(client, PARENT, keyType, 3, 'bool')
+    await getDynamicField(client, PARENT, keyType, null, 'bool')
+    expect(names[0].type).toBe('0x1::option::Option<u8>')
+    expect(names[0].bcs).toEqual(new Uint8Array([1, 3]))
+    expect(names[1].bcs).toEqual(new Uint8Array([0]))
+  })
+
+  it('encodes struct keys with their BCS type', async () => {
+    const { client, names } = mockClient(bcs.bool().serialize(true).toBytes())
+
+    await getDynamicField(client, PARENT, Bar.reified(), { value: 5n }, 'bool')
+    expect(names[0].type).toBe(Bar.$typeName)
+    expect(names[0].bcs).toEqual(bcs.u64().serialize(5n).toBytes())
+  })
+
+  it('wraps the key type of dynamic object fields', async () => {
+    const { client, names } = mockClient(bcs.Address.serialize(CHILD).toBytes())
+    const valueType = {
+      ...Bar.reified(),
+      fetch: async (_client: ClientWithCoreApi, id: string) => id,
+    } as any
+
+    const id = await getDynamicObjectField(client, PARENT, 'u64', 1n, valueType)
+    expect(names[0].type).toBe('0x2::dynamic_object_field::Wrapper<u64>')
+    expect(names[0].bcs).toEqual(bcs.u64().serialize(1n).toBytes())
+    expect(id).toBe(CHILD)
+  })
+})
+
+describe('collections', () => {
+  it('decodes Table values', async () => {
+    const { client, names } = mockClient(bcs.string().serialize('one').toBytes())
+    const table = { id: PARENT, $typeArgs: ['u64', '0x1::string::String'] }
+
+    const value = await tableGet(client, table, 'u64', 1n, String.reified())
+    expect(value).toBe('one')
+    expect(names[0].bcs).toEqual(bcs.u64().serialize(1n).toBytes())
+  })
+
+  it('decodes Bag values of different types', async () => {
+    const { client } = mockClient(bcs.u64().serialize(9n).toBytes())
+
+    expect(await bagGet(client, PARENT, 'u8', 0, Bar.reified())).toBeInstanceOf(Bar)
+    expect(await bagGet(client, PARENT, 'u8', 1, 'u64')).toBe(9n)
+  })
+
+  it('decodes LinkedTable values from their nodes', async () => {
+    const node = bcs.struct('Node', {
+      prev: bcs.option(bcs.string()),
+      next: bcs.option(bcs.string()),
+      value: bcs.u64(),
+    })
+    const { client, names } = mockClient(
+      node.serialize({ prev: 'alice', next: null, value: 100n }).toBytes()
+    )
+
+    const value = await linkedTableGet(client, PARENT, String.reified(), 'bob', 'u64')
+    expect(value).toBe(100n)
+    expect(names[0].type).toBe('0x1::string::String')
+    expect(names[0].bcs).toEqual(bcs.string().serialize('bob').toBytes())
+  })
+
+  it('decodes struct values of LinkedTable nodes', async () => {
+    const node = bcs.struct('Node', {
+      prev: bcs.option(bcs.u64()),
+      next: bcs.option(bcs.u64()),
+      value: Bar.bcs,
+    })
+    const { client } = mockClient(
+      node.serialize({ prev: 1n, next: 3n, value: { value: 8n } }).toBytes()
+    )
+
+    const value = await linkedTableGet(client, PARENT, 'u64', 2n, Bar.reified())
+    expect(value).toBeInstanceOf(Bar)
+    expect(value.value).toBe(8n)
+  })
+
+  it('checks key and value types against the collection', async () => {
+    const { client } = mockClient(bcs.bool().serialize(true).toBytes())
+    const table = { id: PARENT, $typeArgs: ['u64', 'bool'] }
+
+    await expect(tableGet(client, table, 'u8', 1, 'bool')).rejects.toThrow(
+      /Collection type mismatch: expected <u64, bool>, got <u8, bool>/
+    )
+  })
+})
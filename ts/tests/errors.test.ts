@@ -0,0 +1,86 @@
+/**
+ * Tests for abort code decoding (`decodeAbort` in `_framework/errors.ts`).
+ *
+ * Plain abort codes are checked against the Sui framework's `balance` module, clever errors
+ * (Move 2024 `#[error]`) against the `pool` module of the AMM example.
+ */
+
+import { describe, expect, it } from 'vitest'
+import { getOriginalId } from '../examples/gen/_envs'
+import { decodeAbort as decodeExampleAbort } from '../examples/gen/_framework/errors'
+import { abortCodes as poolAbortCodes } from '../examples/gen/amm/pool/constants'
+import './gen/_envs'
+import { decodeAbort } from './gen/_framework/errors'
+import { ENotEnough } from './gen/sui/balance/constants'
+import { TEST_IDS } from './test-utils'
+
+/** Build a clever error abort code: `| tag (1) | reserved (15) | line (16) | identifier (16) | constant (16) |`. */
+function cleverCode(line: number, identifier: number, constant: number): bigint {
+  return (1n << 63n) | (BigInt(line) << 32n) | (BigInt(identifier) << 16n) | BigInt(constant)
+}
+
+/** Constant pool index of `value` in an `identifiers` or `messages` table. */
+function indexOf(table: Record<number, string>, value: string): number {
+  const entry = Object.entries(table).find(([, v]) => v === value)
+  if (entry === undefined) {
+    throw new Error(`'${value}' not found`)
+  }
+  return Number(entry[0])
+}
+
+describe('decodeAbort', () => {
+  it('decodes plain abort codes by the value of their error constant', async () => {
+    const decoded = await decodeAbort(TEST_IDS.SUI_FRAMEWORK, 'balance', ENotEnough)
+    expect(decoded).toEqual({
+      packageName: 'sui',
+      module: 'balance',
+      code: BigInt(ENotEnough),
+      name: 'ENotEnough',
+    })
+  })
+
+  it('accepts abort codes as numbers and strings', async () => {
+    const fromNumber = await decodeAbort('0x2', 'balance', Number(ENotEnough))
+    const fromString = await decodeAbort('0x2', 'balance', ENotEnough.toString())
+    expect(fromNumber?.name).toBe('ENotEnough')
+    expect(fromString?.name).toBe('ENotEnough')
+  })
+
+  it('decodes clever errors with their name, message and line', async () => {
+    const identifier = indexOf(poolAbortCodes.identifiers, 'EZeroInput')
+    const constant = indexOf(poolAbortCodes.messages, 'Input balances cannot be zero.')
+
+    const decoded = await decodeExampleAbort(
+      getOriginalId('amm'),
+      'pool',
+      cleverCode(42, identifier, constant)
+    )
+    expect(decoded).toMatchObject({
+      packageName: 'amm',
+      module: 'pool',
+      name: 'EZeroInput',
+      message: 'Input balances cannot be zero.',
+      line: 42,
+    })
+  })
+
+  it('decodes clever errors without an error constant', async () => {
+    // `assert!(cond)` without an error constant only carries the line
+    const decoded = await decodeExampleAbort(
+      getOriginalId('amm'),
+      'pool',
+      cleverCode(7, 0xffff, 0xffff)
+    )
+    expect(decoded).toEqual({
+      packageName: 'amm',
+      module: 'pool',
+      code: cleverCode(7, 0xffff, 0xffff),
+      line: 7,
+    })
+  })
+
+  it('returns undefined for unknown packages and modules', async () => {
+    expect(await decodeAbort(TEST_IDS.NON_EXISTING, 'balance', 0)).toBeUndefined()
+    expect(await decodeAbort(TEST_IDS.SUI_FRAMEWORK, 'no_such_module', 0)).toBeUndefined()
+  })
+})
@@ -0,0 +1,78 @@
+/**
+ * Tests for event decoding (`decodeEvent` in the generated `events.ts`), against the
+ * `PoolCreationEvent` emitted by the `pool` module of the AMM example.
+ */
+
+import { bcs } from '@mysten/sui/bcs'
+import { describe, expect, it } from 'vitest'
+import { cloneEnv, getEnv, getTypeOrigin, type EnvConfig } from '../examples/gen/_envs'
+import { decodeEvent } from '../examples/gen/amm/events'
+import { PoolCreationEvent } from '../examples/gen/amm/pool/structs'
+import { TEST_IDS } from './test-utils'
+
+const POOL_CREATION_EVENT = `${getTypeOrigin('amm', 'pool::PoolCreationEvent')}::pool::PoolCreationEvent`
+
+const SWAPPED = '0xc0ffee00000000000000000000000000000000000000000000000000c0ffeeee'
+
+/** Env in which the `amm` package has been published to `swappedAddr`. */
+function envWithAmmAt(swappedAddr: string): EnvConfig {
+  const base = getEnv('testnet')
+  const newOrigins: Record<string, string> = {}
+  for (const key of Object.keys(base.packages.amm.typeOrigins)) {
+    newOrigins[key] = swappedAddr
+  }
+  return cloneEnv(base, {
+    packages: {
+      amm: {
+        originalId: swappedAddr,
+        publishedAt: swappedAddr,
+        typeOrigins: newOrigins,
+      },
+    },
+  })
+}
+
+describe('decodeEvent', () => {
+  it('decodes events of the package into their classes', () => {
+    const decoded = decodeEvent({
+      eventType: POOL_CREATION_EVENT,
+      bcs: bcs.Address.serialize(TEST_IDS.AMM_POOL).toBytes(),
+    })
+
+    expect(decoded?.kind).toBe('pool::PoolCreationEvent')
+    expect(decoded?.event).toBeInstanceOf(PoolCreationEvent)
+    if (decoded?.kind === 'pool::PoolCreationEvent') {
+      expect(decoded.event.poolId).toBe(TEST_IDS.AMM_POOL)
+    }
+  })
+
+  it('returns undefined for events of other packages', () => {
+    const decoded = decodeEvent({
+      eventType: '0x2::coin::CurrencyCreated<0x2::sui::SUI>',
+      bcs: new Uint8Array([9]),
+    })
+    expect(decoded).toBeUndefined()
+  })
+
+  it('decodes events of the package in the given env instead of the active one', () => {
+    const event = {
+      eventType: `${SWAPPED}::pool::PoolCreationEvent`,
+      bcs: bcs.Address.serialize(TEST_IDS.AMM_POOL).toBytes(),
+    }
+
+    // The type is only known under the swapped env
+    expect(decodeEvent(event)).toBeUndefined()
+
+    const decoded = decodeEvent(event, envWithAmmAt(SWAPPED))
+    expect(decoded?.kind).toBe('pool::PoolCreationEvent')
+    expect(decoded?.event).toBeInstanceOf(PoolCreationEvent)
+  })
+
+  it('returns undefined for types of the package that are not events', () => {
+    const decoded = decodeEvent({
+      eventType: `${getTypeOrigin('amm', 'pool::Pool')}::pool::Pool`,
+      bcs: new Uint8Array(),
+    })
+    expect(decoded).toBeUndefined()
+  })
+})
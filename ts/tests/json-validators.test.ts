@@ -0,0 +1,158 @@
+/**
+ * Tests for the JSON validators generated with `json-validators = true` (see
+ * `_framework/validate.ts`), which `fromJSON` runs before decoding.
+ */
+
+import { describe, expect, it } from 'vitest'
+import { validateJSONField, JSONValidationError } from './gen/_framework/validate'
+import { Action, validateActionJSONField } from './gen/examples/enums/structs'
+import {
+  Bar,
+  validateBarJSONField,
+  validateWithTwoGenericsJSONField,
+  WithTwoGenerics,
+} from './gen/examples/fixture/structs'
+import { Option } from './gen/std/option/structs'
+import { String } from './gen/std/string/structs'
+import { ID } from './gen/sui/object/structs'
+import { SUI } from './gen/sui/sui/structs'
+
+/** Run `validate` and return the thrown `JSONValidationError`. */
+function validationError(validate: () => void): JSONValidationError {
+  try {
+    validate()
+  } catch (e) {
+    expect(e).toBeInstanceOf(JSONValidationError)
+    return e as JSONValidationError
+  }
+  throw new Error('expected a JSONValidationError')
+}
+
+describe('validateJSONField', () => {
+  it('accepts valid primitives and special types', () => {
+    validateJSONField('bool', true, '$')
+    validateJSONField('u8', 255, '$')
+    validateJSONField('u64', '18446744073709551615', '$')
+    validateJSONField('address', '0x2', '$')
+    validateJSONField('vector<u16>', [1, 2], '$')
+    validateJSONField(String.reified(), 'name', '$')
+    validateJSONField(ID.reified(), '0xab', '$')
+    validateJSONField(Option.reified('u64'), null, '$')
+    validateJSONField(Option.reified('u64'), '1', '$')
+  })
+
+  it('rejects invalid primitives', () => {
+    expect(validationError(() => validateJSONField('u8', 256, '$')).expected).toBe('u8 number')
+    expect(validationError(() => validateJSONField('u64', 1, '$')).expected).toBe(
+      'u64 integer string'
+    )
+    expect(validationError(() => validateJSONField('u64', '-1', '$')).expected).toBe(
+      'u64 integer string'
+    )
+    expect(validationError(() => validateJSONField('bool', 'true', '$')).expected).toBe('boolean')
+    expect(validationError(() => validateJSONField('address', 'abc', '$')).expected).toBe('address')
+    expect(validationError(() => validateJSONField(ID.reified(), 1, '$')).expected).toBe(
+      'object ID'
+    )
+  })
+
+  it('reports the path of invalid vector elements and option values', () => {
+    const err = validationError(() => validateJSONField('vector<u64>', ['1', 2], '$.values'))
+    expect(err.path).toBe('$.values[1]')
+    expect(err.value).toBe(2)
+
+    expect(validationError(() => validateJSONField(Option.reified('u64'), 1, '$')).path).toBe('$')
+  })
+})
+
+describe('generated struct validators', () => {
+  it('accepts valid struct JSON', () => {
+    validateBarJSONField({ value: '5' })
+    validateWithTwoGenericsJSONField([Bar.reified(), 'vector<u8>'], {
+      genericField1: { value: '1' },
+      genericField2: [1, 2],
+    })
+  })
+
+  it('reports the path of invalid nested fields', () => {
+    const err = validationError(() =>
+      validateWithTwoGenericsJSONField([Bar.reified(), 'u8'], {
+        genericField1: { value: 1 },
+        genericField2: 1,
+      })
+    )
+    expect(err.path).toBe('$.genericField1.value')
+    expect(err.message).toBe(
+      'invalid JSON at $.genericField1.value: expected u64 integer string, got number'
+    )
+  })
+
+  it('rejects non-object struct JSON', () => {
+    expect(validationError(() => validateBarJSONField([])).expected).toBe('object')
+  })
+
+  it('runs in fromJSON', () => {
+    const reified = WithTwoGenerics.r('u8', 'u8')
+    const json = reified.new({ genericField1: 1, genericField2: 2 }).toJSON()
+
+    expect(reified.fromJSON(json).genericField2).toBe(2)
+    expect(() => reified.fromJSON({ ...json, genericField2: '2' })).toThrow(
+      'invalid JSON at $.genericField2: expected u8 number'
+    )
+  })
+})
+
+describe('generated enum validators', () => {
+  const typeArgs: [typeof Bar.r, typeof SUI.p] = [Bar.r, SUI.p]
+
+  it('accepts valid variants', () => {
+    validateActionJSONField(typeArgs, { $kind: 'Stop' })
+    validateActionJSONField(typeArgs, {
+      $kind: 'Pause',
+      duration: 10,
+      genericField: { value: '1' },
+      phantomField: { value: '100' },
+      reifiedField: null,
+    })
+    validateActionJSONField(typeArgs, {
+      $kind: 'Jump',
+      vec: ['1', { value: '2' }, { value: '3' }, '4'],
+    })
+  })
+
+  it('rejects unknown variants', () => {
+    const err = validationError(() => validateActionJSONField(typeArgs, { $kind: 'Run' }))
+    expect(err.path).toBe('$.$kind')
+    expect(err.expected).toBe("'Stop' | 'Pause' | 'Jump'")
+  })
+
+  it('checks the fields of the variant', () => {
+    const pause = validationError(() =>
+      validateActionJSONField(typeArgs, {
+        $kind: 'Pause',
+        duration: 10,
+        genericField: { value: '1' },
+        phantomField: { value: 100 },
+        reifiedField: null,
+      })
+    )
+    expect(pause.path).toBe('$.phantomField.value')
+
+    const jump = validationError(() =>
+      validateActionJSONField(typeArgs, { $kind: 'Jump', vec: ['1', { value: '2' }] })
+    )
+    expect(jump.path).toBe('$.vec')
+    expect(jump.expected).toBe('array of 4 elements')
+  })
+
+  it('runs in fromJSON', () => {
+    const reified = Action.r(Bar.r, SUI.p)
+    expect(() =>
+      reified.fromJSON({
+        $typeName: Action.$typeName,
+        $typeArgs: [Bar.$typeName, SUI.$typeName],
+        $kind: 'Run',
+      })
+    ).toThrow(JSONValidationError)
+  })
+})
@@ -0,0 +1,122 @@
+/**
+ * Tests for `listOwned`, generated for structs with the `key` ability.
+ *
+ * The client is mocked, so only the type filter, the paging and the decoding of the returned
+ * objects are tested here.
+ */
+
+import { bcs } from '@mysten/sui/bcs'
+import type { ClientWithCoreApi } from '@mysten/sui/client'
+import { describe, expect, it } from 'vitest'
+import { WithGenericField } from './gen/examples/fixture/structs'
+import { Coin } from './gen/sui/coin/structs'
+import { SUI } from './gen/sui/sui/structs'
+
+const OWNER = '0x00000000000000000000000000000000000000000000000000000000000000aa'
+const USDC = '0x00000000000000000000000000000000000000000000000000000000000000cc::usdc::USDC'
+
+function objectId(i: number): string {
+  return `0x${i.toString(16).padStart(64, '0')}`
+}
+
+interface OwnedObject {
+  objectId: string
+  type: string
+  content: Uint8Array
+}
+
+interface ListOwnedRequest {
+  owner: string
+  type: string
+  cursor: string | null
+}
+
+/** Client returning `pages` of owned objects one after the other, recording the requests. */
+function mockClient(pages: OwnedObject[][]): {
+  client: ClientWithCoreApi
+  requests: ListOwnedRequest[]
+} {
+  const requests: ListOwnedRequest[] = []
+  const client = {
+    core: {
+      listOwnedObjects: async (request: ListOwnedRequest) => {
+        requests.push(request)
+        const page = requests.length - 1
+        const hasNextPage = page + 1 < pages.length
+        return {
+          objects: pages[page],
+          hasNextPage,
+          cursor: hasNextPage ? `cursor-${page + 1}` : null,
+        }
+      },
+    },
+  } as unknown as ClientWithCoreApi
+  return { client, requests }
+}
+
+function coin(i: number, type: string, value: bigint): OwnedObject {
+  return {
+    objectId: objectId(i),
+    type: `0x2::coin::Coin<${type}>`,
+    content: bcs
+      .struct('Coin', { id: bcs.Address, balance: bcs.u64() })
+      .serialize({ id: objectId(i), balance: value })
+      .toBytes(),
+  }
+}
+
+describe('listOwned', () => {
+  it('pages through all owned objects', async () => {
+    const { client, requests } = mockClient([
+      [coin(1, '0x2::sui::SUI', 1n), coin(2, '0x2::sui::SUI', 2n)],
+      [coin(3, '0x2::sui::SUI', 3n)],
+    ])
+
+    const coins = await Coin.listOwned(client, OWNER, SUI.p)
+    expect(coins.map(c => c.balance.value)).toEqual([1n, 2n, 3n])
+    expect(coins.map(c => c.id)).toEqual([objectId(1), objectId(2), objectId(3)])
+    expect(requests.map(r => r.cursor)).toEqual([null, 'cursor-1'])
+  })
+
+  it('filters by the given instantiation', async () => {
+    const { client, requests } = mockClient([[coin(1, '0x2::sui::SUI', 1n)]])
+
+    await Coin.listOwned(client, OWNER, SUI.p)
+    expect(requests[0].owner).toBe(OWNER)
+    expect(requests[0].type).toBe('0x2::coin::Coin<0x2::sui::SUI>')
+  })
+
+  it('lists every instantiation if all type params are phantom and none are given', async () => {
+    const { client, requests } = mockClient([[coin(1, '0x2::sui::SUI', 1n), coin(2, USDC, 2n)]])
+
+    const coins = await Coin.listOwned(client, OWNER)
+    expect(requests[0].type).toBe('0x2::coin::Coin')
+    expect(coins.map(c => c.$typeArgs)).toEqual([['0x2::sui::SUI'], [USDC]])
+  })
+
+  it('decodes non-phantom type params with the given type args', async () => {
+    const type = `${WithGenericField.$typeName}<u64>`
+    const { client, requests } = mockClient([
+      [
+        {
+          objectId: objectId(1),
+          type,
+          content: bcs
+            .struct('WithGenericField', { id: bcs.Address, genericField: bcs.u64() })
+            .serialize({ id: objectId(1), genericField: 42n })
+            .toBytes(),
+        },
+      ],
+    ])
+
+    const objects = await WithGenericField.listOwned(client, OWNER, 'u64')
+    expect(requests[0].type).toBe(type)
+    expect(objects[0].genericField).toBe(42n)
+  })
+
+  it('returns an empty list if nothing is owned', async () => {
+    const { client } = mockClient([[]])
+
+    expect(await Coin.listOwned(client, OWNER, SUI.p)).toEqual([])
+  })
+})
@@ -0,0 +1,111 @@
+/**
+ * Tests for view calls (`viewCall` in `_framework/view.ts`), which back the `<function>View`
+ * variants in `functions.ts`.
+ *
+ * The client is mocked, so only the decoding of the returned BCS values is tested here.
+ */
+
+import { bcs } from '@mysten/sui/bcs'
+import type { ClientWithCoreApi } from '@mysten/sui/client'
+import { afterAll, beforeEach, describe, expect, it } from 'vitest'
+import { cloneEnv, getEnv, setActiveEnv, type EnvConfig } from './gen/_envs'
+import { viewCall } from './gen/_framework/view'
+import { Dummy } from './gen/examples/fixture/structs'
+import { Balance } from './gen/sui/balance/structs'
+
+afterAll(() => {
+  setActiveEnv('testnet')
+})
+
+beforeEach(() => {
+  setActiveEnv('testnet')
+})
+
+const SWAPPED = '0xc0ffee00000000000000000000000000000000000000000000000000c0ffeeee'
+
+/** Env in which the `examples` package has been republished to `swappedAddr`. */
+function envWithExamplesAt(swappedAddr: string): EnvConfig {
+  const base = getEnv('testnet')
+  const newOrigins: Record<string, string> = {}
+  for (const key of Object.keys(base.packages.examples.typeOrigins)) {
+    newOrigins[key] = swappedAddr
+  }
+  return cloneEnv(base, {
+    packages: {
+      examples: {
+        originalId: swappedAddr,
+        publishedAt: swappedAddr,
+        typeOrigins: newOrigins,
+      },
+    },
+  })
+}
+
+/** Client whose simulated transactions return `result`. */
+function mockClient(result: unknown): ClientWithCoreApi {
+  return {
+    core: {
+      simulateTransaction: async () => result,
+    },
+  } as unknown as ClientWithCoreApi
+}
+
+/** Client whose simulated transactions succeed, the last call returning `values`. */
+function returning(...values: Uint8Array[]): ClientWithCoreApi {
+  return mockClient({
+    $kind: 'Transaction',
+    commandResults: [{ returnValues: values.map(value => ({ bcs: value })) }],
+  })
+}
+
+const build = () => {}
+
+describe('viewCall', () => {
+  it('decodes primitive and vector return values', async () => {
+    const client = returning(
+      bcs.u64().serialize(42n).toBytes(),
+      bcs.bool().serialize(true).toBytes(),
+      bcs.vector(bcs.u8()).serialize([1, 2, 3]).toBytes()
+    )
+
+    const values = await viewCall(client, build, ['u64', 'bool', 'vector<u8>'])
+    expect(values).toEqual([42n, true, [1, 2, 3]])
+  })
+
+  it('decodes struct return values into their classes', async () => {
+    const client = returning(bcs.u64().serialize(1000n).toBytes())
+
+    const [balance] = await viewCall(client, build, ['0x2::balance::Balance<0x2::sui::SUI>'])
+    expect(balance).toBeInstanceOf(Balance)
+    expect((balance as Balance<any>).value).toBe(1000n)
+  })
+
+  it('resolves return types in the given env instead of the active one', async () => {
+    const client = returning(bcs.bool().serialize(true).toBytes())
+    const type = `${SWAPPED}::fixture::Dummy`
+
+    // The type is only known under the swapped env
+    await expect(viewCall(client, build, [type])).rejects.toThrow(/Unknown type/)
+
+    const [dummy] = await viewCall(client, build, [type], { env: envWithExamplesAt(SWAPPED) })
+    expect(dummy).toBeInstanceOf(Dummy)
+    expect((dummy as Dummy).dummyField).toBe(true)
+  })
+
+  it('throws if the simulated transaction fails', async () => {
+    const client = mockClient({
+      $kind: 'FailedTransaction',
+      FailedTransaction: { status: { error: { message: 'MoveAbort' } } },
+    })
+
+    await expect(viewCall(client, build, ['u64'])).rejects.toThrow(/View call failed/)
+  })
+
+  it('throws if the number of return values does not match', async () => {
+    const client = returning(bcs.u64().serialize(1n).toBytes())
+
+    await expect(viewCall(client, build, ['u64', 'u64'])).rejects.toThrow(
+      /returned 1 values, expected 2/
+    )
+  })
+})